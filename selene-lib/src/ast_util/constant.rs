@@ -0,0 +1,237 @@
+//! Evaluates expressions made only of literals, like `1 + 2`, `not true`, or `"a" == "b"`.
+use full_moon::{
+    ast,
+    tokenizer::{StringLiteralQuoteType, Symbol, TokenReference, TokenType},
+};
+
+/// The value of an expression that is the same every time it runs.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Constant {
+    Nil,
+    Boolean(bool),
+    Number(f64),
+    String(String),
+}
+
+impl Constant {
+    /// Evaluates an expression, returning `None` if its value isn't known ahead of time.
+    pub fn evaluate(expression: &ast::Expression) -> Option<Self> {
+        #[cfg_attr(
+            feature = "force_exhaustive_checks",
+            deny(non_exhaustive_omitted_patterns)
+        )]
+        match expression {
+            ast::Expression::Parentheses { expression, .. } => Self::evaluate(expression),
+
+            ast::Expression::Number(token) => parse_number(&token.token().to_string()),
+            ast::Expression::String(token) => parse_string(token),
+
+            ast::Expression::Symbol(token) => parse_symbol(token),
+
+            ast::Expression::UnaryOperator { unop, expression } => {
+                evaluate_unary_operator(unop, Self::evaluate(expression)?)
+            }
+
+            ast::Expression::BinaryOperator { lhs, binop, rhs } => {
+                evaluate_binary_operator(lhs, binop, rhs)
+            }
+
+            ast::Expression::Function(_)
+            | ast::Expression::FunctionCall(_)
+            | ast::Expression::TableConstructor(_)
+            | ast::Expression::Var(_) => None,
+
+            #[cfg(feature = "roblox")]
+            ast::Expression::IfExpression(_)
+            | ast::Expression::InterpolatedString(_)
+            | ast::Expression::TypeAssertion { .. } => None,
+
+            _ => None,
+        }
+    }
+
+    /// Whether the value would pass an `if` check, which is everything but `nil` and `false`.
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Constant::Nil | Constant::Boolean(false))
+    }
+}
+
+fn parse_symbol(token: &TokenReference) -> Option<Constant> {
+    match token.token_type() {
+        TokenType::Symbol {
+            symbol: Symbol::Nil,
+        } => Some(Constant::Nil),
+        TokenType::Symbol {
+            symbol: Symbol::True,
+        } => Some(Constant::Boolean(true)),
+        TokenType::Symbol {
+            symbol: Symbol::False,
+        } => Some(Constant::Boolean(false)),
+        _ => None,
+    }
+}
+
+fn parse_number(text: &str) -> Option<Constant> {
+    // Luau allows `1_000`
+    let text = text.replace('_', "");
+
+    let number = if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        u64::from_str_radix(hex, 16).ok()? as f64
+    } else if let Some(binary) = text.strip_prefix("0b").or_else(|| text.strip_prefix("0B")) {
+        u64::from_str_radix(binary, 2).ok()? as f64
+    } else {
+        text.parse().ok()?
+    };
+
+    Some(Constant::Number(number))
+}
+
+fn parse_string(token: &TokenReference) -> Option<Constant> {
+    let TokenType::StringLiteral {
+        literal,
+        quote_type,
+        ..
+    } = token.token_type()
+    else {
+        return None;
+    };
+
+    match quote_type {
+        // A newline right after the opening brackets is skipped
+        StringLiteralQuoteType::Brackets => Some(Constant::String(
+            literal
+                .strip_prefix("\r\n")
+                .or_else(|| literal.strip_prefix('\n'))
+                .unwrap_or(literal)
+                .to_owned(),
+        )),
+
+        // Escapes aren't worth interpreting here
+        _ if literal.contains('\\') => None,
+
+        _ => Some(Constant::String(literal.to_string())),
+    }
+}
+
+fn evaluate_unary_operator(unop: &ast::UnOp, value: Constant) -> Option<Constant> {
+    match (unop, value) {
+        (ast::UnOp::Not(_), value) => Some(Constant::Boolean(!value.is_truthy())),
+        (ast::UnOp::Minus(_), Constant::Number(number)) => Some(Constant::Number(-number)),
+        (ast::UnOp::Hash(_), Constant::String(string)) => {
+            Some(Constant::Number(string.len() as f64))
+        }
+        _ => None,
+    }
+}
+
+fn evaluate_binary_operator(
+    lhs: &ast::Expression,
+    binop: &ast::BinOp,
+    rhs: &ast::Expression,
+) -> Option<Constant> {
+    let lhs = Constant::evaluate(lhs)?;
+
+    // `and` and `or` don't need the right side when the left side decides the result
+    match binop {
+        ast::BinOp::And(_) if !lhs.is_truthy() => return Some(lhs),
+        ast::BinOp::Or(_) if lhs.is_truthy() => return Some(lhs),
+        ast::BinOp::And(_) | ast::BinOp::Or(_) => return Constant::evaluate(rhs),
+        _ => {}
+    }
+
+    let rhs = Constant::evaluate(rhs)?;
+
+    match binop {
+        ast::BinOp::TwoEqual(_) => return Some(Constant::Boolean(lhs == rhs)),
+        ast::BinOp::TildeEqual(_) => return Some(Constant::Boolean(lhs != rhs)),
+        _ => {}
+    }
+
+    let (lhs, rhs) = match (lhs, rhs) {
+        (Constant::Number(lhs), Constant::Number(rhs)) => (lhs, rhs),
+
+        (Constant::String(lhs), Constant::String(rhs)) => {
+            return match binop {
+                ast::BinOp::TwoDots(_) => Some(Constant::String(lhs + &rhs)),
+                ast::BinOp::LessThan(_) => Some(Constant::Boolean(lhs < rhs)),
+                ast::BinOp::LessThanEqual(_) => Some(Constant::Boolean(lhs <= rhs)),
+                ast::BinOp::GreaterThan(_) => Some(Constant::Boolean(lhs > rhs)),
+                ast::BinOp::GreaterThanEqual(_) => Some(Constant::Boolean(lhs >= rhs)),
+                _ => None,
+            };
+        }
+
+        // Coercing between strings and numbers depends on the version, so it isn't evaluated
+        _ => return None,
+    };
+
+    Some(match binop {
+        ast::BinOp::Plus(_) => Constant::Number(lhs + rhs),
+        ast::BinOp::Minus(_) => Constant::Number(lhs - rhs),
+        ast::BinOp::Star(_) => Constant::Number(lhs * rhs),
+        ast::BinOp::Slash(_) => Constant::Number(lhs / rhs),
+        ast::BinOp::Caret(_) => Constant::Number(lhs.powf(rhs)),
+        ast::BinOp::LessThan(_) => Constant::Boolean(lhs < rhs),
+        ast::BinOp::LessThanEqual(_) => Constant::Boolean(lhs <= rhs),
+        ast::BinOp::GreaterThan(_) => Constant::Boolean(lhs > rhs),
+        ast::BinOp::GreaterThanEqual(_) => Constant::Boolean(lhs >= rhs),
+
+        // `%` and `//` on integers depend on the version, so they aren't evaluated
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(code: &str) -> Option<Constant> {
+        let ast = full_moon::parse(&format!("local _ = {code}")).unwrap();
+
+        let Some(ast::Stmt::LocalAssignment(local_assignment)) = ast.nodes().stmts().next() else {
+            unreachable!();
+        };
+
+        Constant::evaluate(local_assignment.expressions().iter().next().unwrap())
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(evaluate("nil"), Some(Constant::Nil));
+        assert_eq!(evaluate("(true)"), Some(Constant::Boolean(true)));
+        assert_eq!(evaluate("0x10"), Some(Constant::Number(16.0)));
+        assert_eq!(evaluate("1.5e2"), Some(Constant::Number(150.0)));
+        assert_eq!(evaluate("'a'"), Some(Constant::String("a".to_owned())));
+        assert_eq!(evaluate("[[\nb]]"), Some(Constant::String("b".to_owned())));
+        assert_eq!(evaluate(r#""\n""#), None);
+        assert_eq!(evaluate("x"), None);
+        assert_eq!(evaluate("{}"), None);
+    }
+
+    #[test]
+    fn test_operators() {
+        assert_eq!(evaluate("1 - 1"), Some(Constant::Number(0.0)));
+        assert_eq!(evaluate("-(2 ^ 2)"), Some(Constant::Number(-4.0)));
+        assert_eq!(evaluate("#'abc'"), Some(Constant::Number(3.0)));
+        assert_eq!(
+            evaluate("'a' .. 'b'"),
+            Some(Constant::String("ab".to_owned()))
+        );
+        assert_eq!(evaluate("not nil"), Some(Constant::Boolean(true)));
+        assert_eq!(evaluate("1 == 1.0"), Some(Constant::Boolean(true)));
+        assert_eq!(evaluate("'a' ~= 1"), Some(Constant::Boolean(true)));
+        assert_eq!(evaluate("'a' < 'b'"), Some(Constant::Boolean(true)));
+        assert_eq!(evaluate("'1' + 1"), None);
+        assert_eq!(evaluate("x + 1"), None);
+        assert_eq!(evaluate("7 % 2"), None);
+    }
+
+    #[test]
+    fn test_short_circuit() {
+        assert_eq!(evaluate("false and x"), Some(Constant::Boolean(false)));
+        assert_eq!(evaluate("1 or x"), Some(Constant::Number(1.0)));
+        assert_eq!(evaluate("true and 2"), Some(Constant::Number(2.0)));
+        assert_eq!(evaluate("x and false"), None);
+        assert_eq!(evaluate("true and x"), None);
+    }
+}
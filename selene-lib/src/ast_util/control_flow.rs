@@ -0,0 +1,724 @@
+//! A control flow graph over the statements of a single function body.
+//! Nested functions are not part of the graph, and get their own when needed.
+// Remove this once lints use all of the graph, rather than just what's reachable
+#![allow(dead_code)]
+use std::collections::{HashMap, VecDeque};
+
+use full_moon::{
+    ast,
+    node::Node,
+    tokenizer::{Symbol, TokenType},
+};
+use id_arena::Id;
+
+use super::{
+    name_paths::name_path_from_prefix_suffix,
+    range,
+    scopes::{ScopeManager, Variable},
+    strip_parentheses,
+};
+use crate::standard_library::{Field, FieldKind, StandardLibrary};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Clone, Copy, Debug)]
+pub enum NodeKind<'a> {
+    /// Where the function body starts.
+    Entry,
+
+    /// Where the function body returns, either from a `return` or from running off the end.
+    /// Calls that never return, like `error`, don't lead here.
+    Exit,
+
+    /// A statement. Statements with blocks, like `if` and `while`, are where their condition is checked.
+    Stmt(&'a ast::Stmt),
+
+    /// A `return`, `break`, or `continue`.
+    LastStmt(&'a ast::LastStmt),
+
+    /// Where the condition of an `elseif` is checked.
+    ElseIf(&'a ast::ElseIf),
+
+    /// Where the `until` condition of a `repeat` is checked.
+    Until(&'a ast::Repeat),
+}
+
+impl NodeKind<'_> {
+    /// The code run at this node, if any.
+    /// Statements with blocks only include their condition, or the header of `for` loops.
+    pub fn range(&self) -> Option<(usize, usize)> {
+        match *self {
+            NodeKind::Entry | NodeKind::Exit => None,
+
+            NodeKind::Stmt(stmt) => match stmt {
+                ast::Stmt::Do(_) | ast::Stmt::Repeat(_) => None,
+                ast::Stmt::If(if_block) => Some(range(if_block.condition())),
+                ast::Stmt::While(while_loop) => Some(range(while_loop.condition())),
+
+                ast::Stmt::GenericFor(generic_for) => {
+                    Some((range(stmt).0, range(generic_for.do_token()).1))
+                }
+
+                ast::Stmt::NumericFor(numeric_for) => {
+                    Some((range(stmt).0, range(numeric_for.do_token()).1))
+                }
+
+                _ => Some(range(stmt)),
+            },
+
+            NodeKind::LastStmt(last_stmt) => Some(range(last_stmt)),
+            NodeKind::ElseIf(else_if) => Some(range(else_if.condition())),
+            NodeKind::Until(repeat) => Some(range(repeat.until())),
+        }
+    }
+}
+
+/// How control gets from one node to the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeKind {
+    Always,
+
+    /// Taken when the node's condition is truthy, such as into the block of an `if`.
+    IfTrue,
+
+    /// Taken when the node's condition is falsy, such as past the end of a `while`.
+    IfFalse,
+}
+
+#[derive(Debug)]
+pub struct ControlFlowNode<'a> {
+    pub kind: NodeKind<'a>,
+    pub successors: Vec<(NodeId, EdgeKind)>,
+}
+
+impl<'a> ControlFlowNode<'a> {
+    /// The condition deciding between the `IfTrue` and `IfFalse` edges, if there is one.
+    /// `for` loops have those edges too, but no condition.
+    pub fn condition(&self) -> Option<&'a ast::Expression> {
+        match self.kind {
+            NodeKind::Stmt(ast::Stmt::If(if_block)) => Some(if_block.condition()),
+            NodeKind::Stmt(ast::Stmt::While(while_loop)) => Some(while_loop.condition()),
+            NodeKind::ElseIf(else_if) => Some(else_if.condition()),
+            NodeKind::Until(repeat) => Some(repeat.until()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ControlFlowGraph<'a> {
+    nodes: Vec<ControlFlowNode<'a>>,
+    reachable: Vec<bool>,
+    stmt_nodes: HashMap<*const ast::Stmt, NodeId>,
+    last_stmt_nodes: HashMap<*const ast::LastStmt, NodeId>,
+}
+
+impl<'a> ControlFlowGraph<'a> {
+    const ENTRY: NodeId = NodeId(0);
+    const EXIT: NodeId = NodeId(1);
+
+    /// Creates the graph for a function body, or the top level of a file.
+    /// The scope manager and standard library are used to find calls that never return, like `error`.
+    pub fn new(
+        block: &'a ast::Block,
+        scope_manager: &ScopeManager,
+        standard_library: &StandardLibrary,
+    ) -> Self {
+        let mut builder = ControlFlowBuilder {
+            graph: ControlFlowGraph {
+                nodes: Vec::new(),
+                reachable: Vec::new(),
+                stmt_nodes: HashMap::new(),
+                last_stmt_nodes: HashMap::new(),
+            },
+            scope_manager,
+            standard_library,
+            loops: Vec::new(),
+            labels: Vec::new(),
+        };
+
+        builder.add_node(NodeKind::Entry);
+        builder.add_node(NodeKind::Exit);
+
+        let exits = builder.visit_block(block, vec![(Self::ENTRY, EdgeKind::Always)]);
+        builder.connect(exits, Self::EXIT);
+
+        let mut graph = builder.graph;
+        graph.reachable = graph.find_reachable();
+        graph
+    }
+
+    pub fn entry(&self) -> NodeId {
+        Self::ENTRY
+    }
+
+    pub fn exit(&self) -> NodeId {
+        Self::EXIT
+    }
+
+    pub fn node(&self, id: NodeId) -> &ControlFlowNode<'a> {
+        &self.nodes[id.0]
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &ControlFlowNode<'a>)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (NodeId(index), node))
+    }
+
+    /// Whether control can get to the node from the start of the function body.
+    pub fn is_reachable(&self, id: NodeId) -> bool {
+        self.reachable[id.0]
+    }
+
+    /// The node for a statement inside this function body.
+    /// Returns `None` for statements that are part of another function body.
+    pub fn stmt_node(&self, stmt: &ast::Stmt) -> Option<NodeId> {
+        self.stmt_nodes.get(&(stmt as *const _)).copied()
+    }
+
+    /// The node for a `return`, `break`, or `continue` inside this function body.
+    pub fn last_stmt_node(&self, last_stmt: &ast::LastStmt) -> Option<NodeId> {
+        self.last_stmt_nodes.get(&(last_stmt as *const _)).copied()
+    }
+
+    /// The reachable nodes that go straight to the exit.
+    /// These are `return`s, and the last statements before running off the end of the function body.
+    pub fn exits(&self) -> impl Iterator<Item = &ControlFlowNode<'a>> {
+        self.nodes.iter().enumerate().filter_map(|(index, node)| {
+            (self.reachable[index]
+                && node
+                    .successors
+                    .iter()
+                    .any(|&(successor, _)| successor == Self::EXIT))
+            .then_some(node)
+        })
+    }
+
+    /// Runs a forward dataflow analysis until it settles, returning the state at the start of every reachable node.
+    /// `transfer` gives the state leaving a node along one of its edges, given the state at the start of it.
+    /// `join` merges the state from another edge into the state at the start of a node.
+    pub fn forward_dataflow<S: Clone + PartialEq>(
+        &self,
+        entry_state: S,
+        mut transfer: impl FnMut(&ControlFlowNode<'a>, EdgeKind, &S) -> S,
+        mut join: impl FnMut(&mut S, S),
+    ) -> HashMap<NodeId, S> {
+        let mut states = HashMap::new();
+        states.insert(Self::ENTRY, entry_state);
+
+        let mut queue = VecDeque::from([Self::ENTRY]);
+
+        while let Some(id) = queue.pop_front() {
+            let node = &self.nodes[id.0];
+            let state = states[&id].clone();
+
+            for &(successor, edge_kind) in &node.successors {
+                let successor_state = transfer(node, edge_kind, &state);
+
+                match states.get_mut(&successor) {
+                    Some(existing_state) => {
+                        let previous_state = existing_state.clone();
+                        join(existing_state, successor_state);
+
+                        if *existing_state != previous_state {
+                            queue.push_back(successor);
+                        }
+                    }
+
+                    None => {
+                        states.insert(successor, successor_state);
+                        queue.push_back(successor);
+                    }
+                }
+            }
+        }
+
+        states
+    }
+
+    fn find_reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.nodes.len()];
+        let mut queue = VecDeque::from([Self::ENTRY]);
+        reachable[Self::ENTRY.0] = true;
+
+        while let Some(id) = queue.pop_front() {
+            for &(successor, _) in &self.nodes[id.0].successors {
+                if !reachable[successor.0] {
+                    reachable[successor.0] = true;
+                    queue.push_back(successor);
+                }
+            }
+        }
+
+        reachable
+    }
+}
+
+/// The edges leaving the code visited so far, which get connected to whatever runs next.
+type Exits = Vec<(NodeId, EdgeKind)>;
+
+struct LoopContext {
+    /// Where `continue` goes, which is the condition of the loop.
+    continue_target: NodeId,
+    breaks: Exits,
+}
+
+struct ControlFlowBuilder<'a, 'b> {
+    graph: ControlFlowGraph<'a>,
+    scope_manager: &'b ScopeManager,
+    standard_library: &'b StandardLibrary,
+    loops: Vec<LoopContext>,
+    // Labels of every block we're in, as `goto` can go to labels in any enclosing block
+    labels: Vec<HashMap<String, NodeId>>,
+}
+
+impl<'a> ControlFlowBuilder<'a, '_> {
+    fn add_node(&mut self, kind: NodeKind<'a>) -> NodeId {
+        let id = NodeId(self.graph.nodes.len());
+
+        self.graph.nodes.push(ControlFlowNode {
+            kind,
+            successors: Vec::new(),
+        });
+
+        id
+    }
+
+    fn add_stmt_node(&mut self, stmt: &'a ast::Stmt) -> NodeId {
+        let id = self.add_node(NodeKind::Stmt(stmt));
+        self.graph.stmt_nodes.insert(stmt, id);
+        id
+    }
+
+    fn connect(&mut self, exits: Exits, target: NodeId) {
+        for (id, edge_kind) in exits {
+            self.graph.nodes[id.0].successors.push((target, edge_kind));
+        }
+    }
+
+    fn visit_block(&mut self, block: &'a ast::Block, mut exits: Exits) -> Exits {
+        // Labels are made ahead of time so that `goto` can go forward to them
+        let mut labels = HashMap::new();
+        for stmt in block.stmts() {
+            if let Some(name) = label_name(stmt) {
+                labels.insert(name, self.add_stmt_node(stmt));
+            }
+        }
+        self.labels.push(labels);
+
+        for stmt in block.stmts() {
+            exits = self.visit_stmt(stmt, exits);
+        }
+
+        if let Some(last_stmt) = block.last_stmt() {
+            exits = self.visit_last_stmt(last_stmt, exits);
+        }
+
+        self.labels.pop();
+        exits
+    }
+
+    fn visit_loop_block(
+        &mut self,
+        block: &'a ast::Block,
+        exits: Exits,
+        continue_target: NodeId,
+    ) -> (Exits, Exits) {
+        self.loops.push(LoopContext {
+            continue_target,
+            breaks: Vec::new(),
+        });
+
+        let exits = self.visit_block(block, exits);
+        let loop_context = self.loops.pop().unwrap();
+
+        (exits, loop_context.breaks)
+    }
+
+    fn visit_stmt(&mut self, stmt: &'a ast::Stmt, exits: Exits) -> Exits {
+        let id = match self.graph.stmt_node(stmt) {
+            Some(id) => id,
+            None => self.add_stmt_node(stmt),
+        };
+
+        self.connect(exits, id);
+
+        match stmt {
+            ast::Stmt::Do(do_) => self.visit_block(do_.block(), vec![(id, EdgeKind::Always)]),
+
+            ast::Stmt::If(if_block) => {
+                let mut after = self.visit_block(if_block.block(), vec![(id, EdgeKind::IfTrue)]);
+                let mut condition = id;
+
+                for else_if in if_block.else_if().into_iter().flatten() {
+                    let else_if_id = self.add_node(NodeKind::ElseIf(else_if));
+                    self.connect(vec![(condition, EdgeKind::IfFalse)], else_if_id);
+
+                    after.extend(
+                        self.visit_block(else_if.block(), vec![(else_if_id, EdgeKind::IfTrue)]),
+                    );
+
+                    condition = else_if_id;
+                }
+
+                match if_block.else_block() {
+                    Some(else_block) => after
+                        .extend(self.visit_block(else_block, vec![(condition, EdgeKind::IfFalse)])),
+
+                    None => after.push((condition, EdgeKind::IfFalse)),
+                }
+
+                after
+            }
+
+            ast::Stmt::While(while_loop) => {
+                let (body_exits, mut after) =
+                    self.visit_loop_block(while_loop.block(), vec![(id, EdgeKind::IfTrue)], id);
+                self.connect(body_exits, id);
+
+                if !is_constant_bool(while_loop.condition(), true) {
+                    after.push((id, EdgeKind::IfFalse));
+                }
+
+                after
+            }
+
+            ast::Stmt::NumericFor(numeric_for) => self.visit_for_loop(id, numeric_for.block()),
+
+            ast::Stmt::GenericFor(generic_for) => self.visit_for_loop(id, generic_for.block()),
+
+            ast::Stmt::Repeat(repeat) => {
+                let until_id = self.add_node(NodeKind::Until(repeat));
+
+                let (body_exits, mut after) =
+                    self.visit_loop_block(repeat.block(), vec![(id, EdgeKind::Always)], until_id);
+                self.connect(body_exits, until_id);
+
+                if !is_constant_bool(repeat.until(), true) {
+                    self.connect(vec![(until_id, EdgeKind::IfFalse)], id);
+                }
+
+                if !is_constant_bool(repeat.until(), false) {
+                    after.push((until_id, EdgeKind::IfTrue));
+                }
+
+                after
+            }
+
+            ast::Stmt::FunctionCall(call) if self.is_never_returning_call(call) => Vec::new(),
+
+            #[cfg(feature = "lua52")]
+            ast::Stmt::Goto(goto) => {
+                let name = goto.label_name().token().to_string();
+
+                // Unknown labels are a syntax error, so there's nothing to go to
+                if let Some(&target) = self
+                    .labels
+                    .iter()
+                    .rev()
+                    .find_map(|labels| labels.get(&name))
+                {
+                    self.connect(vec![(id, EdgeKind::Always)], target);
+                }
+
+                Vec::new()
+            }
+
+            _ => vec![(id, EdgeKind::Always)],
+        }
+    }
+
+    fn visit_for_loop(&mut self, id: NodeId, block: &'a ast::Block) -> Exits {
+        let (body_exits, mut after) =
+            self.visit_loop_block(block, vec![(id, EdgeKind::IfTrue)], id);
+        self.connect(body_exits, id);
+
+        after.push((id, EdgeKind::IfFalse));
+        after
+    }
+
+    fn visit_last_stmt(&mut self, last_stmt: &'a ast::LastStmt, exits: Exits) -> Exits {
+        let id = self.add_node(NodeKind::LastStmt(last_stmt));
+        self.graph.last_stmt_nodes.insert(last_stmt, id);
+        self.connect(exits, id);
+
+        match last_stmt {
+            ast::LastStmt::Return(_) => {
+                self.connect(vec![(id, EdgeKind::Always)], ControlFlowGraph::EXIT);
+            }
+
+            ast::LastStmt::Break(_) => {
+                if let Some(loop_context) = self.loops.last_mut() {
+                    loop_context.breaks.push((id, EdgeKind::Always));
+                }
+            }
+
+            #[cfg(feature = "roblox")]
+            ast::LastStmt::Continue(_) => {
+                if let Some(loop_context) = self.loops.last() {
+                    let continue_target = loop_context.continue_target;
+                    self.connect(vec![(id, EdgeKind::Always)], continue_target);
+                }
+            }
+
+            _ => {}
+        }
+
+        Vec::new()
+    }
+
+    fn is_never_returning_call(&self, call: &ast::FunctionCall) -> bool {
+        // Only check calls like `error()` and `os.exit()`, not `f()()`
+        let call_count = call
+            .suffixes()
+            .filter(|suffix| matches!(suffix, ast::Suffix::Call(_)))
+            .count();
+
+        if call_count != 1 {
+            return false;
+        }
+
+        let Some(name_path) = name_path_from_prefix_suffix(call.prefix(), call.suffixes()) else {
+            return false;
+        };
+
+        // `local error = function() end` isn't the standard library's `error`
+        if let Some((start, _)) = call.prefix().range() {
+            if let Some(reference) = self.scope_manager.reference_at_byte(start.bytes()) {
+                if reference.resolved.is_some() {
+                    return false;
+                }
+            }
+        }
+
+        matches!(
+            self.standard_library.find_global(&name_path),
+            Some(Field {
+                field_kind: FieldKind::Function(function_behavior),
+                ..
+            }) if function_behavior.never_returns
+        )
+    }
+}
+
+fn label_name(stmt: &ast::Stmt) -> Option<String> {
+    match stmt {
+        #[cfg(feature = "lua52")]
+        ast::Stmt::Label(label) => Some(label.name().token().to_string()),
+
+        _ => None,
+    }
+}
+
+fn is_constant_bool(expression: &ast::Expression, value: bool) -> bool {
+    match strip_parentheses(expression) {
+        ast::Expression::Symbol(symbol) => {
+            let expected = if value { "true" } else { "false" };
+
+            matches!(
+                symbol.token_type(),
+                TokenType::Symbol { symbol } if symbol.to_string() == expected
+            )
+        }
+
+        _ => false,
+    }
+}
+
+/// The locals known to not be nil when the expression is truthy (or falsy, when `truthy` is false).
+pub fn guarded_variables(
+    scope_manager: &ScopeManager,
+    expression: &ast::Expression,
+    truthy: bool,
+) -> Vec<Id<Variable>> {
+    match expression {
+        ast::Expression::Var(ast::Var::Name(name)) if truthy => scope_manager
+            .reference_at_byte(name.token().start_position().bytes())
+            .and_then(|reference| reference.resolved)
+            .into_iter()
+            .collect(),
+
+        ast::Expression::Parentheses { expression, .. } => {
+            guarded_variables(scope_manager, expression, truthy)
+        }
+
+        ast::Expression::UnaryOperator {
+            unop: ast::UnOp::Not(_),
+            expression,
+        } => guarded_variables(scope_manager, expression, !truthy),
+
+        ast::Expression::BinaryOperator { lhs, binop, rhs } => match binop {
+            // `x and y` being truthy means both are, `x or y` being falsy means both are
+            ast::BinOp::And(_) if truthy => {
+                let mut variables = guarded_variables(scope_manager, lhs, true);
+                variables.extend(guarded_variables(scope_manager, rhs, true));
+                variables
+            }
+
+            ast::BinOp::Or(_) if !truthy => {
+                let mut variables = guarded_variables(scope_manager, lhs, false);
+                variables.extend(guarded_variables(scope_manager, rhs, false));
+                variables
+            }
+
+            // Otherwise, only what both sides guard is known
+            ast::BinOp::And(_) | ast::BinOp::Or(_) => {
+                let rhs_variables = guarded_variables(scope_manager, rhs, truthy);
+
+                guarded_variables(scope_manager, lhs, truthy)
+                    .into_iter()
+                    .filter(|variable| rhs_variables.contains(variable))
+                    .collect()
+            }
+
+            // `x ~= nil` and `x == nil`
+            ast::BinOp::TildeEqual(_) | ast::BinOp::TwoEqual(_) => {
+                let is_not_equal = matches!(binop, ast::BinOp::TildeEqual(_));
+                if is_not_equal != truthy {
+                    return Vec::new();
+                }
+
+                let compared = if is_nil(rhs) {
+                    lhs
+                } else if is_nil(lhs) {
+                    rhs
+                } else {
+                    return Vec::new();
+                };
+
+                guarded_variables(scope_manager, compared, true)
+            }
+
+            _ => Vec::new(),
+        },
+
+        _ => Vec::new(),
+    }
+}
+
+fn is_nil(expression: &ast::Expression) -> bool {
+    matches!(
+        expression,
+        ast::Expression::Symbol(symbol)
+            if matches!(symbol.token_type(), TokenType::Symbol { symbol: Symbol::Nil })
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reachable_statements(code: &str) -> Vec<(String, bool)> {
+        let ast = full_moon::parse(code).unwrap();
+        let scope_manager = ScopeManager::new(&ast);
+        let standard_library = StandardLibrary::from_name("lua51").unwrap();
+
+        let graph = ControlFlowGraph::new(ast.nodes(), &scope_manager, &standard_library);
+
+        let mut statements = Vec::new();
+
+        for stmt in ast.nodes().stmts() {
+            let id = graph.stmt_node(stmt).unwrap();
+            statements.push((stmt.to_string().trim().to_owned(), graph.is_reachable(id)));
+        }
+
+        statements
+    }
+
+    #[test]
+    fn test_straight_line() {
+        assert_eq!(
+            reachable_statements("a()\nb()\nreturn"),
+            vec![("a()".to_owned(), true), ("b()".to_owned(), true)]
+        );
+    }
+
+    #[test]
+    fn test_never_returns() {
+        assert_eq!(
+            reachable_statements("error('x')\nb()"),
+            vec![("error('x')".to_owned(), true), ("b()".to_owned(), false)]
+        );
+
+        assert_eq!(
+            reachable_statements("local error = print\nerror('x')\nb()"),
+            vec![
+                ("local error = print".to_owned(), true),
+                ("error('x')".to_owned(), true),
+                ("b()".to_owned(), true)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_branches() {
+        let ast =
+            full_moon::parse("if a then return elseif b then return else error('x') end\nc()")
+                .unwrap();
+        let scope_manager = ScopeManager::new(&ast);
+        let standard_library = StandardLibrary::from_name("lua51").unwrap();
+        let graph = ControlFlowGraph::new(ast.nodes(), &scope_manager, &standard_library);
+
+        let stmts = ast.nodes().stmts().collect::<Vec<_>>();
+        assert!(graph.is_reachable(graph.stmt_node(stmts[0]).unwrap()));
+        assert!(!graph.is_reachable(graph.stmt_node(stmts[1]).unwrap()));
+        assert!(graph.is_reachable(graph.exit()));
+
+        let if_node = graph.node(graph.stmt_node(stmts[0]).unwrap());
+        assert_eq!(if_node.successors.len(), 2);
+        assert_eq!(if_node.successors[0].1, EdgeKind::IfTrue);
+        assert_eq!(if_node.successors[1].1, EdgeKind::IfFalse);
+    }
+
+    #[test]
+    fn test_loops() {
+        assert_eq!(
+            reachable_statements("while true do a() end\nb()"),
+            vec![
+                ("while true do a() end".to_owned(), true),
+                ("b()".to_owned(), false)
+            ]
+        );
+
+        assert_eq!(
+            reachable_statements("while true do break end\nb()"),
+            vec![
+                ("while true do break end".to_owned(), true),
+                ("b()".to_owned(), true)
+            ]
+        );
+
+        assert_eq!(
+            reachable_statements("repeat a() until false\nb()"),
+            vec![
+                ("repeat a() until false".to_owned(), true),
+                ("b()".to_owned(), false)
+            ]
+        );
+
+        assert_eq!(
+            reachable_statements("for i = 1, 10 do return end\nb()"),
+            vec![
+                ("for i = 1, 10 do return end".to_owned(), true),
+                ("b()".to_owned(), true)
+            ]
+        );
+    }
+
+    #[cfg(feature = "lua52")]
+    #[test]
+    fn test_goto() {
+        assert_eq!(
+            reachable_statements("goto skip\na()\n::skip::\nb()"),
+            vec![
+                ("goto skip".to_owned(), true),
+                ("a()".to_owned(), false),
+                ("::skip::".to_owned(), true),
+                ("b()".to_owned(), true)
+            ]
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! Finds every function in a file, for lints that measure each function on its own.
+use full_moon::{ast, node::Node, visitors::Visitor};
+
+use super::range;
+
+type Range = (usize, usize);
+
+/// Calls `callback` with every function in the file, along with the range of its signature,
+/// such as `local function foo(a, b)` or `function(a, b)`.
+pub fn visit_functions(ast: &ast::Ast, callback: impl FnMut(Range, &ast::FunctionBody)) {
+    let mut visitor = FunctionsVisitor { callback };
+    visitor.visit_ast(ast);
+}
+
+struct FunctionsVisitor<F> {
+    callback: F,
+}
+
+impl<F: FnMut(Range, &ast::FunctionBody)> Visitor for FunctionsVisitor<F> {
+    fn visit_local_function(&mut self, local_function: &ast::LocalFunction) {
+        (self.callback)(
+            (
+                range(local_function.function_token()).0,
+                range(local_function.body().parameters_parentheses()).1,
+            ),
+            local_function.body(),
+        );
+    }
+
+    fn visit_function_declaration(&mut self, function_declaration: &ast::FunctionDeclaration) {
+        (self.callback)(
+            (
+                range(function_declaration.function_token()).0,
+                range(function_declaration.body().parameters_parentheses()).1,
+            ),
+            function_declaration.body(),
+        );
+    }
+
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        if let ast::Expression::Function(function_box) = expression {
+            let function_body = function_box.body();
+
+            (self.callback)(
+                (
+                    expression.start_position().unwrap().bytes(),
+                    range(function_body.parameters_parentheses()).1,
+                ),
+                function_body,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visit_functions() {
+        let code = "local function a(x) end\nfunction b.c(y, z) end\nlocal d = function() end";
+        let ast = full_moon::parse(code).unwrap();
+
+        let mut signatures = Vec::new();
+        visit_functions(&ast, |signature, _| {
+            signatures.push(&code[signature.0..signature.1]);
+        });
+
+        assert_eq!(
+            signatures,
+            vec!["function a(x)", "function b.c(y, z)", "function()"]
+        );
+    }
+}
@@ -1,23 +1,44 @@
-// Remove this once loop_tracker is used again
-#![allow(dead_code)]
-
 use full_moon::{ast, node::Node, visitors::Visitor};
 
+use super::range;
+
+type Range = (usize, usize);
+
 #[derive(Debug, Clone, Copy)]
 struct LoopDepth {
     byte: usize,
     depth: u32,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoopKind {
+    NumericFor,
+    GenericFor,
+    While,
+    Repeat,
+}
+
+#[derive(Debug)]
+pub struct Loop {
+    pub kind: LoopKind,
+    pub block: Range,
+    // The names the loop declares, like `i` in `for i = 1, 10` or `k, v` in `for k, v in pairs(t)`
+    pub variables: Vec<Range>,
+}
+
 #[derive(Debug)]
 pub struct LoopTracker {
     loop_depths: Vec<LoopDepth>,
+    loops: Vec<Loop>,
+    function_bodies: Vec<Range>,
 }
 
 impl LoopTracker {
     pub fn new(ast: &ast::Ast) -> Self {
         let mut visitor = LoopTrackerVisitor {
             loop_depths: Vec::new(),
+            loops: Vec::new(),
+            function_bodies: Vec::new(),
             depth: 0,
         };
 
@@ -32,7 +53,34 @@ impl LoopTracker {
 
         loop_depths.sort_by_cached_key(|loop_depth| loop_depth.byte);
 
-        Self { loop_depths }
+        Self {
+            loop_depths,
+            loops: visitor.loops,
+            function_bodies: visitor.function_bodies,
+        }
+    }
+
+    /// Every loop in the file, outermost loops first.
+    pub fn loops(&self) -> &[Loop] {
+        &self.loops
+    }
+
+    /// The loops whose block contains the byte, outermost first.
+    pub fn loops_at_byte(&self, byte: usize) -> impl Iterator<Item = &Loop> {
+        self.loops
+            .iter()
+            .filter(move |tracked| tracked.block.0 <= byte && byte <= tracked.block.1)
+    }
+
+    /// The innermost loop that runs the code at the byte on every iteration.
+    /// Loops outside of the function the byte is in don't count, since they only define the function.
+    pub fn innermost_loop_at_byte(&self, byte: usize) -> Option<&Loop> {
+        self.loops_at_byte(byte).last().filter(|tracked| {
+            !self
+                .function_bodies
+                .iter()
+                .any(|&(start, end)| tracked.block.0 <= start && start <= byte && byte <= end)
+        })
     }
 
     pub fn depth_at_byte(&self, byte: usize) -> u32 {
@@ -54,10 +102,24 @@ impl LoopTracker {
 
 struct LoopTrackerVisitor {
     loop_depths: Vec<LoopDepth>,
+    loops: Vec<Loop>,
+    function_bodies: Vec<Range>,
     depth: u32,
 }
 
 impl LoopTrackerVisitor {
+    fn add_loop(&mut self, kind: LoopKind, block: &ast::Block, variables: Vec<Range>) {
+        if block.range().is_some() {
+            self.loops.push(Loop {
+                kind,
+                block: range(block),
+                variables,
+            });
+        }
+
+        self.add_loop_depth(block);
+    }
+
     fn add_loop_depth(&mut self, node: impl Node) {
         self.depth += 1;
 
@@ -86,8 +148,18 @@ impl LoopTrackerVisitor {
 }
 
 impl Visitor for LoopTrackerVisitor {
+    fn visit_function_body(&mut self, node: &ast::FunctionBody) {
+        if node.range().is_some() {
+            self.function_bodies.push(range(node));
+        }
+    }
+
     fn visit_generic_for(&mut self, node: &ast::GenericFor) {
-        self.add_loop_depth(node.block());
+        self.add_loop(
+            LoopKind::GenericFor,
+            node.block(),
+            node.names().iter().map(range).collect(),
+        );
     }
 
     fn visit_generic_for_end(&mut self, node: &ast::GenericFor) {
@@ -95,7 +167,11 @@ impl Visitor for LoopTrackerVisitor {
     }
 
     fn visit_numeric_for(&mut self, node: &ast::NumericFor) {
-        self.add_loop_depth(node.block());
+        self.add_loop(
+            LoopKind::NumericFor,
+            node.block(),
+            vec![range(node.index_variable())],
+        );
     }
 
     fn visit_numeric_for_end(&mut self, node: &ast::NumericFor) {
@@ -103,7 +179,7 @@ impl Visitor for LoopTrackerVisitor {
     }
 
     fn visit_while(&mut self, node: &ast::While) {
-        self.add_loop_depth(node.block());
+        self.add_loop(LoopKind::While, node.block(), Vec::new());
     }
 
     fn visit_while_end(&mut self, node: &ast::While) {
@@ -111,7 +187,7 @@ impl Visitor for LoopTrackerVisitor {
     }
 
     fn visit_repeat(&mut self, node: &ast::Repeat) {
-        self.add_loop_depth(node.block());
+        self.add_loop(LoopKind::Repeat, node.block(), Vec::new());
     }
 
     fn visit_repeat_end(&mut self, node: &ast::Repeat) {
@@ -179,4 +255,66 @@ mod tests {
         "#,
         );
     }
+
+    #[test]
+    fn loops_at_byte() {
+        let code = r#"
+            for i = 1, 10 do
+                for key, value in pairs({}) do
+                    inside()
+                end
+            end
+
+            outside()
+        "#;
+
+        let ast = full_moon::parse(code).unwrap();
+        let loop_tracker = LoopTracker::new(&ast);
+
+        let kinds = loop_tracker
+            .loops_at_byte(code.find("inside").unwrap())
+            .map(|tracked| (tracked.kind, tracked.variables.len()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            vec![(LoopKind::NumericFor, 1), (LoopKind::GenericFor, 2)]
+        );
+
+        assert_eq!(
+            loop_tracker
+                .loops_at_byte(code.find("outside").unwrap())
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn innermost_loop_at_byte() {
+        let code = r#"
+            while true do
+                for i = 1, 10 do
+                    inside()
+                end
+
+                local function callback()
+                    defined()
+                end
+            end
+        "#;
+
+        let ast = full_moon::parse(code).unwrap();
+        let loop_tracker = LoopTracker::new(&ast);
+
+        assert_eq!(
+            loop_tracker
+                .innermost_loop_at_byte(code.find("inside").unwrap())
+                .map(|tracked| tracked.kind),
+            Some(LoopKind::NumericFor)
+        );
+
+        assert!(loop_tracker
+            .innermost_loop_at_byte(code.find("defined").unwrap())
+            .is_none());
+    }
 }
@@ -6,12 +6,16 @@ use full_moon::{
     tokenizer::{self, Position, TokenReference},
 };
 
+pub mod constant;
+pub mod control_flow;
 mod extract_static_token;
-mod loop_tracker;
+pub mod functions;
+pub mod loop_tracker;
 pub mod name_paths;
 mod purge_trivia;
 pub mod scopes;
 mod side_effects;
+pub mod std_types;
 mod strip_parentheses;
 pub mod visit_nodes;
 
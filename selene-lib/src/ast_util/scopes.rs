@@ -1,4 +1,7 @@
-use std::{borrow::Cow, collections::HashSet};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashSet},
+};
 
 use full_moon::{
     ast::{self, VarExpression},
@@ -10,7 +13,7 @@ use id_arena::{Arena, Id};
 
 use crate::ast_util::extract_static_token;
 
-use super::expression_to_ident;
+use super::{expression_to_ident, name_paths::*};
 
 type Range = (usize, usize);
 
@@ -42,6 +45,29 @@ impl ScopeManager {
             .find(|reference| byte >= reference.identifier.0 && byte <= reference.identifier.1)
     }
 
+    /// The value a variable holds, if it's known and the variable is never assigned to again.
+    pub fn variable_value(&self, variable_id: Id<Variable>) -> Option<&AssignedValue> {
+        let variable = self.variables.get(variable_id)?;
+        let value = variable.value.as_ref()?;
+
+        // `x.y = z` only writes to a field, and doesn't replace the value
+        let assignments = variable
+            .references
+            .iter()
+            .filter(|reference_id| {
+                let reference = &self.references[**reference_id];
+                reference.write == Some(ReferenceWrite::Assign) && reference.indexing.is_none()
+            })
+            .count();
+
+        // The definition itself counts as one
+        if assignments <= 1 {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
     pub fn reference_at_byte_with_id(&self, byte: usize) -> Option<(Id<Reference>, &Reference)> {
         for (id, reference) in &self.references {
             if byte >= reference.identifier.0 && byte <= reference.identifier.1 {
@@ -52,11 +78,16 @@ impl ScopeManager {
         None
     }
 
-    fn variable_in_scope(&self, scope: Id<Scope>, variable_name: &str) -> VariableInScope {
+    fn variable_in_scope(
+        &self,
+        scope: Id<Scope>,
+        variable_name: &str,
+        undefined_locals: &[Id<Variable>],
+    ) -> VariableInScope {
         if let Some(scope) = self.scopes.get(scope) {
             for variable_id in scope.variables.iter().rev() {
                 let variable = &self.variables[*variable_id];
-                if variable.name == variable_name {
+                if variable.name == variable_name && !undefined_locals.contains(variable_id) {
                     return VariableInScope::Found(*variable_id);
                 }
             }
@@ -141,12 +172,44 @@ pub struct Variable {
     pub references: Vec<Id<Reference>>,
     pub shadowed: Option<Id<Variable>>,
     pub is_self: bool,
+    pub is_parameter: bool,
     pub value: Option<AssignedValue>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AssignedValue {
-    StaticTable { has_fields: bool },
+    /// A literal, such as `1`, `"text"`, `true`, or `nil`
+    Literal(LiteralType),
+
+    /// A table constructor, such as `{ x = 1 }`.
+    /// `fields` are the ones with names, along with their values if they're known.
+    StaticTable {
+        has_fields: bool,
+        fields: BTreeMap<String, Option<AssignedValue>>,
+    },
+
+    /// The value at a name path, such as `workspace` or `game.Workspace`
+    NamePath {
+        name_path: Vec<String>,
+        initial_reference: Id<Reference>,
+    },
+
+    /// What a call to a name path returns, such as `Instance.new("Part")`.
+    /// `return_index` is which of the values returned this is, for `local a, b = f()`.
+    FunctionCall {
+        call_name_path: Vec<String>,
+        initial_reference: Id<Reference>,
+        constant_arguments: Vec<Option<String>>,
+        return_index: usize,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiteralType {
+    Bool,
+    Nil,
+    Number,
+    String,
 }
 
 #[derive(Debug)]
@@ -163,6 +226,10 @@ struct ScopeVisitor {
 
     captured_references: HashSet<Range>,
 
+    // Locals whose statement is still being visited, such as `f` inside `local f = function() f() end`.
+    // They aren't defined until the statement finishes, so references to them are to whatever was there before.
+    undefined_locals: Vec<Id<Variable>>,
+
     // sigh
     else_blocks: HashSet<Range>,
 }
@@ -257,17 +324,124 @@ fn get_name_path_from_call(call: &ast::FunctionCall) -> Option<Vec<String>> {
     Some(path)
 }
 
-fn get_assigned_value(expression: &ast::Expression) -> Option<AssignedValue> {
-    if let ast::Expression::TableConstructor(table_constructor) = expression {
-        return Some(AssignedValue::StaticTable {
-            has_fields: !table_constructor.fields().is_empty(),
-        });
+fn string_literal(token: &TokenReference) -> Option<String> {
+    match token.token_type() {
+        TokenType::StringLiteral { literal, .. } => Some(literal.to_string()),
+        _ => None,
     }
+}
 
-    None
+fn constant_arguments(args: &ast::FunctionArgs) -> Vec<Option<String>> {
+    match args {
+        ast::FunctionArgs::Parentheses { arguments, .. } => arguments
+            .iter()
+            .map(|argument| match argument {
+                ast::Expression::String(token) => string_literal(token),
+                _ => None,
+            })
+            .collect(),
+
+        ast::FunctionArgs::String(token) => vec![string_literal(token)],
+
+        _ => Vec::new(),
+    }
 }
 
 impl ScopeVisitor {
+    /// Finds the value of an expression that has already been read.
+    /// `return_index` is which value is being assigned when the expression is a call returning multiple.
+    fn assigned_value(
+        &self,
+        expression: &ast::Expression,
+        return_index: usize,
+    ) -> Option<AssignedValue> {
+        if return_index > 0 && !matches!(expression, ast::Expression::FunctionCall(_)) {
+            return None;
+        }
+
+        let initial_reference = |node: &dyn Node| {
+            self.scope_manager
+                .reference_at_byte_with_id(node.start_position()?.bytes())
+                .map(|(id, _)| id)
+        };
+
+        match expression {
+            ast::Expression::Number(_) => Some(AssignedValue::Literal(LiteralType::Number)),
+            ast::Expression::String(_) => Some(AssignedValue::Literal(LiteralType::String)),
+            ast::Expression::Symbol(token) => match token.token_type() {
+                TokenType::Symbol {
+                    symbol: Symbol::True | Symbol::False,
+                } => Some(AssignedValue::Literal(LiteralType::Bool)),
+                TokenType::Symbol {
+                    symbol: Symbol::Nil,
+                } => Some(AssignedValue::Literal(LiteralType::Nil)),
+                _ => None,
+            },
+
+            ast::Expression::TableConstructor(table_constructor) => {
+                let mut fields = BTreeMap::new();
+
+                for field in table_constructor.fields() {
+                    let (name, value) = match field {
+                        ast::Field::NameKey { key, value, .. } => (key.token().to_string(), value),
+                        ast::Field::ExpressionKey {
+                            key: ast::Expression::String(key),
+                            value,
+                            ..
+                        } => match string_literal(key) {
+                            Some(name) => (name, value),
+                            None => continue,
+                        },
+                        _ => continue,
+                    };
+
+                    fields.insert(name, self.assigned_value(value, 0));
+                }
+
+                Some(AssignedValue::StaticTable {
+                    has_fields: !table_constructor.fields().is_empty(),
+                    fields,
+                })
+            }
+
+            ast::Expression::Var(_) => Some(AssignedValue::NamePath {
+                name_path: name_path(expression)?,
+                initial_reference: initial_reference(expression)?,
+            }),
+
+            ast::Expression::FunctionCall(call) => {
+                let mut keep_going = true;
+                let suffixes: Vec<&ast::Suffix> = call
+                    .suffixes()
+                    .take_while(|suffix| take_while_keep_going(suffix, &mut keep_going))
+                    .collect();
+
+                // Calling what a call returns, like `f()()`
+                if suffixes.len() != call.suffixes().count() {
+                    return None;
+                }
+
+                let args = match suffixes.last()? {
+                    ast::Suffix::Call(ast::Call::AnonymousCall(args)) => args,
+                    ast::Suffix::Call(ast::Call::MethodCall(method_call)) => method_call.args(),
+                    _ => return None,
+                };
+
+                Some(AssignedValue::FunctionCall {
+                    call_name_path: name_path_from_prefix_suffix(
+                        call.prefix(),
+                        suffixes.iter().copied(),
+                    )?,
+                    initial_reference: initial_reference(call)?,
+                    constant_arguments: constant_arguments(args),
+                    return_index,
+                })
+            }
+
+            _ => None,
+        }
+    }
+
     fn from_ast(ast: &ast::Ast) -> Self {
         if let Some(scope) = create_scope(ast.nodes()) {
             let mut scopes = Arena::new();
@@ -284,6 +458,7 @@ impl ScopeVisitor {
 
                 captured_references: HashSet::new(),
                 scope_stack: vec![id],
+                undefined_locals: Vec::new(),
 
                 else_blocks: HashSet::new(),
             };
@@ -310,10 +485,11 @@ impl ScopeVisitor {
 
     fn find_variable(&self, variable_name: &str) -> Option<(Id<Variable>, Id<Scope>)> {
         for scope_id in self.scope_stack.iter().rev().copied() {
-            match self
-                .scope_manager
-                .variable_in_scope(scope_id, variable_name)
-            {
+            match self.scope_manager.variable_in_scope(
+                scope_id,
+                variable_name,
+                &self.undefined_locals,
+            ) {
                 VariableInScope::Found(id) => return Some((id, scope_id)),
                 VariableInScope::NotFound => {}
                 VariableInScope::Blocked => return None,
@@ -803,20 +979,29 @@ impl Visitor for ScopeVisitor {
 
     fn visit_local_assignment(&mut self, local_assignment: &ast::LocalAssignment) {
         let mut expressions = local_assignment.expressions().iter();
+        let expression_count = local_assignment.expressions().len();
 
-        for name_token in local_assignment.names() {
+        for (index, name_token) in local_assignment.names().iter().enumerate() {
             let expression = expressions.next();
 
             if let Some(expression) = expression {
                 self.read_expression(expression);
             }
 
-            self.define_name_full_with_variable(
+            // `local a, b = f()` assigns `b` to the second value `f` returns
+            let value = match expression {
+                Some(expression) => self.assigned_value(expression, 0),
+                None => local_assignment.expressions().last().and_then(|last| {
+                    self.assigned_value(last.value(), index + 1 - expression_count)
+                }),
+            };
+
+            let variable = self.define_name_full_with_variable(
                 &name_token.token().to_string(),
                 range(name_token),
                 range(local_assignment),
                 Variable {
-                    value: expression.and_then(get_assigned_value),
+                    value,
                     ..Default::default()
                 },
             );
@@ -824,9 +1009,16 @@ impl Visitor for ScopeVisitor {
             if let Some(expression) = expression {
                 self.write_name(name_token, Some(range(expression)));
             }
+
+            self.undefined_locals.push(variable);
         }
     }
 
+    fn visit_local_assignment_end(&mut self, local_assignment: &ast::LocalAssignment) {
+        let remaining = self.undefined_locals.len() - local_assignment.names().len();
+        self.undefined_locals.truncate(remaining);
+    }
+
     fn visit_block(&mut self, block: &ast::Block) {
         if let Some((start, end)) = block.range() {
             if self.else_blocks.contains(&(start.bytes(), end.bytes())) {
@@ -910,7 +1102,15 @@ impl Visitor for ScopeVisitor {
 
         for parameter in body.parameters() {
             if let ast::Parameter::Ellipsis(token) | ast::Parameter::Name(token) = parameter {
-                self.define_name(token, range(token));
+                self.define_name_full_with_variable(
+                    &token.token().to_string(),
+                    range(token),
+                    range(token),
+                    Variable {
+                        is_parameter: true,
+                        ..Variable::default()
+                    },
+                );
             }
         }
     }
@@ -937,6 +1137,9 @@ impl Visitor for ScopeVisitor {
                 Some(range(declaration.name())),
                 ReferenceWrite::Extend,
             );
+        } else if self.find_variable(&base.token().to_string()).is_some() {
+            // Gives a value to a local declared earlier, like `local helper` before `function helper()`
+            self.write_name(base, Some(range(declaration)));
         }
 
         self.read_name(base);
@@ -0,0 +1,158 @@
+//! Finds which standard library structs locals hold, using the values the scope manager knows they're assigned.
+//! For example, `local part = Instance.new("Part")` holds a `Part`, and `local ws = workspace` holds a `Workspace`.
+use super::scopes::{AssignedValue, Reference, ScopeManager};
+use crate::standard_library::{Field, FieldKind, FunctionBehavior, StandardLibrary};
+
+// Values can refer to other locals, so this stops `local a = b.c; local b = a.d`-like cycles
+const MAX_DEPTH: usize = 16;
+
+/// Where a name path like `part.Parent.Name` is looked up from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NamePathRoot {
+    /// The struct held by the start of the name path, or `None` if the name path starts at a global.
+    pub struct_name: Option<String>,
+
+    /// How many names at the start of the name path are the local and its known table fields,
+    /// such as 1 for `part` in `part.Parent.Name`.
+    pub length: usize,
+}
+
+pub struct StdTypes<'a> {
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+}
+
+impl<'a> StdTypes<'a> {
+    pub fn new(scope_manager: &'a ScopeManager, standard_library: &'a StandardLibrary) -> Self {
+        Self {
+            scope_manager,
+            standard_library,
+        }
+    }
+
+    /// Finds where the name path starting at the given byte is looked up from.
+    /// Returns `None` if it starts with a local that isn't known to hold a struct.
+    pub fn name_path_root(&self, byte: usize, name_path: &[String]) -> Option<NamePathRoot> {
+        match self.scope_manager.reference_at_byte(byte) {
+            Some(reference) => self.reference_root(reference, name_path, 0),
+            None => Some(NamePathRoot::default()),
+        }
+    }
+
+    /// Finds the field a name path points to, starting from its root.
+    pub fn find_field(&self, root: &NamePathRoot, name_path: &[String]) -> Option<&'a Field> {
+        match &root.struct_name {
+            Some(struct_name) if name_path.len() > root.length => self
+                .standard_library
+                .find_struct_field(struct_name, &name_path[root.length..]),
+            Some(_) => None,
+            None => self.standard_library.find_global(name_path),
+        }
+    }
+
+    /// The standard library function a value was returned from, such as `string.match` for `local x = s:match(p)`.
+    pub fn called_function(&self, value: &AssignedValue) -> Option<&'a FunctionBehavior> {
+        self.called_function_at_depth(value, 0)
+    }
+
+    fn called_function_at_depth(
+        &self,
+        value: &AssignedValue,
+        depth: usize,
+    ) -> Option<&'a FunctionBehavior> {
+        let AssignedValue::FunctionCall {
+            call_name_path,
+            initial_reference,
+            ..
+        } = value
+        else {
+            return None;
+        };
+
+        let root = self.reference_root(
+            &self.scope_manager.references[*initial_reference],
+            call_name_path,
+            depth,
+        )?;
+
+        match &self.find_field(&root, call_name_path)?.field_kind {
+            FieldKind::Function(function) => Some(function),
+            _ => None,
+        }
+    }
+
+    fn reference_root(
+        &self,
+        reference: &Reference,
+        name_path: &[String],
+        depth: usize,
+    ) -> Option<NamePathRoot> {
+        let Some(variable_id) = reference.resolved else {
+            return Some(NamePathRoot::default());
+        };
+
+        let mut value = self.scope_manager.variable_value(variable_id)?;
+        let mut length = 1;
+
+        // `local services = { players = game:GetService("Players") }` lets `services.players` be used
+        while let AssignedValue::StaticTable { fields, .. } = value {
+            value = fields.get(name_path.get(length)?)?.as_ref()?;
+            length += 1;
+        }
+
+        Some(NamePathRoot {
+            struct_name: Some(self.value_struct(value, depth + 1)?),
+            length,
+        })
+    }
+
+    fn value_struct(&self, value: &AssignedValue, depth: usize) -> Option<String> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        match value {
+            AssignedValue::NamePath {
+                name_path,
+                initial_reference,
+            } => {
+                let root = self.reference_root(
+                    &self.scope_manager.references[*initial_reference],
+                    name_path,
+                    depth,
+                )?;
+
+                // `local ws2 = ws`
+                if root.struct_name.is_some() && name_path.len() == root.length {
+                    return root.struct_name;
+                }
+
+                match &self.find_field(&root, name_path)?.field_kind {
+                    FieldKind::Struct(struct_name) => Some(struct_name.to_owned()),
+                    _ => None,
+                }
+            }
+
+            AssignedValue::FunctionCall {
+                constant_arguments,
+                return_index,
+                ..
+            } => {
+                let function = self.called_function_at_depth(value, depth)?;
+
+                let constant_arguments: Vec<Option<&str>> =
+                    constant_arguments.iter().map(Option::as_deref).collect();
+
+                let struct_name = function.return_struct(*return_index, &constant_arguments)?;
+
+                // Functions like `Instance.new` can name structs the standard library doesn't have
+                self.standard_library
+                    .structs
+                    .contains_key(struct_name)
+                    .then(|| struct_name.to_owned())
+            }
+
+            AssignedValue::Literal(_) | AssignedValue::StaticTable { .. } => None,
+        }
+    }
+}
@@ -3,7 +3,7 @@
     feature = "force_exhaustive_checks",
     feature(non_exhaustive_omitted_patterns_lint)
 )]
-use std::{collections::HashMap, error::Error, fmt};
+use std::{collections::HashMap, error::Error, fmt, time::Duration};
 
 use full_moon::ast::Ast;
 use serde::{
@@ -16,6 +16,7 @@ mod lint_filtering;
 pub mod lints;
 mod possible_std;
 pub mod standard_library;
+pub mod targets;
 mod text;
 
 #[cfg(test)]
@@ -57,7 +58,7 @@ impl fmt::Display for CheckerError {
 
 impl Error for CheckerError {}
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 #[serde(default)]
 #[serde(rename_all = "kebab-case")]
 #[serde(deny_unknown_fields)]
@@ -65,17 +66,103 @@ pub struct CheckerConfig<V> {
     pub config: HashMap<String, V>,
     #[serde(alias = "rules")]
     pub lints: HashMap<String, LintVariation>,
-    pub std: Option<String>,
+    pub std: Option<StdConfig>,
     pub exclude: Vec<String>,
 
+    /// Maximum number of diagnostics allowed per lint before the run fails.
+    #[serde(deserialize_with = "deserialize_budgets")]
+    pub budgets: HashMap<String, usize>,
+
     // Not locked behind Roblox feature so that selene.toml for Roblox will
     // run even without it.
     pub roblox_std_source: RobloxStdSource,
 }
 
+// Budgets for lints that don't exist would silently never fail, such as for a typo
+fn deserialize_budgets<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashMap<String, usize>, D::Error> {
+    let budgets = HashMap::<String, usize>::deserialize(deserializer)?;
+
+    if let Some(name) = budgets.keys().filter(|name| !lint_exists(name)).min() {
+        return Err(serde::de::Error::custom(format!(
+            "unknown lint `{name}` in budgets"
+        )));
+    }
+
+    Ok(budgets)
+}
+
 impl<V> CheckerConfig<V> {
-    pub fn std(&self) -> &str {
-        self.std.as_deref().unwrap_or("lua51")
+    /// The standard libraries files are checked against, such as `["lua51+roblox"]` for `std = "lua51+roblox"`.
+    pub fn std_targets(&self) -> Vec<&str> {
+        match &self.std {
+            Some(StdConfig::Single(std_text)) => vec![std_text.as_str()],
+            Some(StdConfig::Targets(targets)) if !targets.is_empty() => {
+                targets.iter().map(String::as_str).collect()
+            }
+            // Empty lists are rejected when deserializing, but can still be made by hand
+            Some(StdConfig::Targets(_)) | None => vec!["lua51"],
+        }
+    }
+
+    /// The same config, but only checking against `target`. Used to create a checker for each target.
+    pub fn for_target(&self, target: &str) -> Self
+    where
+        V: Clone,
+    {
+        Self {
+            std: Some(StdConfig::Single(target.to_owned())),
+            ..self.clone()
+        }
+    }
+}
+
+/// The `std` option, which is either a single standard library, such as `std = "lua51+roblox"`,
+/// or a list of targets that every file is checked against separately, such as `std = ["lua51", "luau"]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StdConfig {
+    Single(String),
+    Targets(Vec<String>),
+}
+
+// Not `#[serde(untagged)]`, since its errors don't say what was expected
+impl<'de> Deserialize<'de> for StdConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StdConfigVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for StdConfigVisitor {
+            type Value = StdConfig;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a standard library name, or a list of them")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(StdConfig::Single(value.to_owned()))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut targets = Vec::new();
+
+                while let Some(target) = seq.next_element()? {
+                    targets.push(target);
+                }
+
+                if targets.is_empty() {
+                    return Err(serde::de::Error::custom(
+                        "expected at least one standard library",
+                    ));
+                }
+
+                Ok(StdConfig::Targets(targets))
+            }
+        }
+
+        deserializer.deserialize_any(StdConfigVisitor)
     }
 }
 
@@ -86,6 +173,7 @@ impl<V> Default for CheckerConfig<V> {
             lints: HashMap::new(),
             std: None,
             exclude: Vec::new(),
+            budgets: HashMap::new(),
 
             roblox_std_source: RobloxStdSource::default(),
         }
@@ -220,9 +308,14 @@ macro_rules! use_lints {
 
                     context: Context {
                         standard_library,
-                        user_set_standard_library: config.std.as_ref().map(|std_text| {
-                            std_text.split('+').map(ToOwned::to_owned).collect()
-                        }),
+                        user_set_standard_library: match &config.std {
+                            Some(StdConfig::Single(std_text)) => {
+                                Some(std_text.split('+').map(ToOwned::to_owned).collect())
+                            }
+
+                            // Each target gets its own checker through `CheckerConfig::for_target`
+                            Some(StdConfig::Targets(_)) | None => None,
+                        },
                     },
 
                     config,
@@ -230,7 +323,13 @@ macro_rules! use_lints {
             }
 
             pub fn test_on(&self, ast: &Ast) -> Vec<CheckerDiagnostic> {
+                self.test_on_with_timings(ast).0
+            }
+
+            /// Same as `test_on`, but also returns how long each lint's pass took.
+            pub fn test_on_with_timings(&self, ast: &Ast) -> (Vec<CheckerDiagnostic>, Vec<(&'static str, Duration)>) {
                 let mut diagnostics = Vec::new();
+                let mut timings = Vec::with_capacity(ALL_LINTS.len());
 
                 let ast_context = AstContext::from_ast(ast);
 
@@ -240,7 +339,10 @@ macro_rules! use_lints {
 
                         let lint_pass = {
                             profiling::scope!(&format!("lint: {}", stringify!($name)));
-                            lint.pass(ast, &self.context, &ast_context)
+                            let start = std::time::Instant::now();
+                            let lint_pass = lint.pass(ast, &self.context, &ast_context);
+                            timings.push((stringify!($name), start.elapsed()));
+                            lint_pass
                         };
 
                         diagnostics.extend(&mut lint_pass.into_iter().map(|diagnostic| {
@@ -271,7 +373,9 @@ macro_rules! use_lints {
                     self.get_lint_severity(&self.invalid_lint_filter, "invalid_lint_filter"),
                 );
 
-                diagnostics
+                lint_filtering::remove_duplicate_diagnostics(&mut diagnostics);
+
+                (diagnostics, timings)
             }
 
             fn get_lint_severity<R: Lint>(&self, _lint: &R, name: &'static str) -> Severity {
@@ -296,34 +400,62 @@ pub fn lint_exists(name: &str) -> bool {
 
 use_lints! {
     almost_swapped: lints::almost_swapped::AlmostSwappedLint,
+    and_or_ternary: lints::and_or_ternary::AndOrTernaryLint,
+    bad_format_string: lints::bad_format_string::BadFormatStringLint,
+    bad_pattern: lints::bad_pattern::BadPatternLint,
     bad_string_escape: lints::bad_string_escape::BadStringEscapeLint,
     compare_nan: lints::compare_nan::CompareNanLint,
+    constant_condition: lints::constant_condition::ConstantConditionLint,
     constant_table_comparison: lints::constant_table_comparison::ConstantTableComparisonLint,
+    dead_store: lints::dead_store::DeadStoreLint,
     deprecated: lints::deprecated::DeprecatedLint,
     divide_by_zero: lints::divide_by_zero::DivideByZeroLint,
+    duplicate_definition: lints::duplicate_definition::DuplicateDefinitionLint,
     duplicate_keys: lints::duplicate_keys::DuplicateKeysLint,
     empty_if: lints::empty_if::EmptyIfLint,
     empty_loop: lints::empty_loop::EmptyLoopLint,
+    file_length: lints::file_length::FileLengthLint,
+    for_variable_assignment: lints::for_variable_assignment::ForVariableAssignmentLint,
+    function_length: lints::function_length::FunctionLengthLint,
+    global_lookup_in_loop: lints::global_lookup_in_loop::GlobalLookupInLoopLint,
     global_usage: lints::global_usage::GlobalLint,
+    high_cognitive_complexity: lints::high_cognitive_complexity::HighCognitiveComplexityLint,
     high_cyclomatic_complexity: lints::high_cyclomatic_complexity::HighCyclomaticComplexityLint,
     if_same_then_else: lints::if_same_then_else::IfSameThenElseLint,
     ifs_same_cond: lints::ifs_same_cond::IfsSameCondLint,
+    incompatible_comparison: lints::incompatible_comparison::IncompatibleComparisonLint,
+    incompatible_lua_version: lints::incompatible_lua_version::IncompatibleLuaVersionLint,
+    inconsistent_return: lints::inconsistent_return::InconsistentReturnLint,
     incorrect_standard_library_use: lints::standard_library::StandardLibraryLint,
     invalid_lint_filter: lints::invalid_lint_filter::InvalidLintFilterLint,
     manual_table_clone: lints::manual_table_clone::ManualTableCloneLint,
+    max_nesting_depth: lints::max_nesting_depth::MaxNestingDepthLint,
+    max_parameters: lints::max_parameters::MaxParametersLint,
     mismatched_arg_count: lints::mismatched_arg_count::MismatchedArgCountLint,
     mixed_table: lints::mixed_table::MixedTableLint,
     multiple_statements: lints::multiple_statements::MultipleStatementsLint,
     must_use: lints::must_use::MustUseLint,
+    pairs_table_mutation: lints::pairs_table_mutation::PairsTableMutationLint,
     parenthese_conditions: lints::parenthese_conditions::ParentheseConditionsLint,
+    possibly_nil_index: lints::possibly_nil_index::PossiblyNilIndexLint,
     restricted_module_paths: lints::restricted_module_paths::RestrictedModulePathsLint,
+    self_assignment: lints::self_assignment::SelfAssignmentLint,
     shadowing: lints::shadowing::ShadowingLint,
+    string_concat_in_loop: lints::string_concat_in_loop::StringConcatInLoopLint,
+    suspicious_precedence: lints::suspicious_precedence::SuspiciousPrecedenceLint,
     suspicious_reverse_loop: lints::suspicious_reverse_loop::SuspiciousReverseLoopLint,
+    table_insert_front_in_loop: lints::table_insert_front_in_loop::TableInsertFrontInLoopLint,
     type_check_inside_call: lints::type_check_inside_call::TypeCheckInsideCallLint,
     unbalanced_assignments: lints::unbalanced_assignments::UnbalancedAssignmentsLint,
     undefined_variable: lints::undefined_variable::UndefinedVariableLint,
+    uninitialized_variable: lints::uninitialized_variable::UninitializedVariableLint,
     unscoped_variables: lints::unscoped_variables::UnscopedVariablesLint,
+    unreachable_code: lints::unreachable_code::UnreachableCodeLint,
+    unused_label: lints::unused_label::UnusedLabelLint,
+    unused_parameter: lints::unused_parameter::UnusedParameterLint,
+    unused_varargs: lints::unused_varargs::UnusedVarargsLint,
     unused_variable: lints::unused_variable::UnusedVariableLint,
+    use_before_definition: lints::use_before_definition::UseBeforeDefinitionLint,
 
     #[cfg(feature = "roblox")]
     {
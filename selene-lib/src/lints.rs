@@ -1,4 +1,7 @@
-use crate::{ast_util::scopes::ScopeManager, standard_library::StandardLibrary};
+use crate::{
+    ast_util::{loop_tracker::LoopTracker, scopes::ScopeManager},
+    standard_library::StandardLibrary,
+};
 use std::convert::TryInto;
 
 use codespan_reporting::diagnostic::{
@@ -8,34 +11,62 @@ use full_moon::{ast::Ast, node::Node};
 use serde::de::DeserializeOwned;
 
 pub mod almost_swapped;
+pub mod and_or_ternary;
+pub mod bad_format_string;
+pub mod bad_pattern;
 pub mod bad_string_escape;
 pub mod compare_nan;
+pub mod constant_condition;
 pub mod constant_table_comparison;
+pub mod dead_store;
 pub mod deprecated;
 pub mod divide_by_zero;
+pub mod duplicate_definition;
 pub mod duplicate_keys;
 pub mod empty_if;
 pub mod empty_loop;
+pub mod file_length;
+pub mod for_variable_assignment;
+pub mod function_length;
+pub mod global_lookup_in_loop;
 pub mod global_usage;
+pub mod high_cognitive_complexity;
 pub mod high_cyclomatic_complexity;
 pub mod if_same_then_else;
 pub mod ifs_same_cond;
+pub mod incompatible_comparison;
+pub mod incompatible_lua_version;
+pub mod inconsistent_return;
 pub mod invalid_lint_filter;
 pub mod manual_table_clone;
+pub mod max_nesting_depth;
+pub mod max_parameters;
 pub mod mismatched_arg_count;
 pub mod mixed_table;
 pub mod multiple_statements;
 pub mod must_use;
+pub mod pairs_table_mutation;
 pub mod parenthese_conditions;
+pub mod possibly_nil_index;
 pub mod restricted_module_paths;
+pub mod self_assignment;
 pub mod shadowing;
 pub mod standard_library;
+pub mod string_concat_in_loop;
+pub mod suspicious_precedence;
 pub mod suspicious_reverse_loop;
+pub mod table_insert_front_in_loop;
 pub mod type_check_inside_call;
 pub mod unbalanced_assignments;
 pub mod undefined_variable;
+pub mod uninitialized_variable;
+pub mod unreachable_code;
 pub mod unscoped_variables;
+pub mod unused_label;
+pub mod unused_parameter;
+pub mod unused_varargs;
 pub mod unused_variable;
+pub mod use_before_definition;
 
 #[cfg(feature = "roblox")]
 pub mod roblox_incorrect_color3_new_bounds;
@@ -237,12 +268,14 @@ impl Context {
 
 #[derive(Debug)]
 pub struct AstContext {
+    pub loop_tracker: LoopTracker,
     pub scope_manager: ScopeManager,
 }
 
 impl AstContext {
     pub fn from_ast(ast: &Ast) -> Self {
         Self {
+            loop_tracker: LoopTracker::new(ast),
             scope_manager: ScopeManager::new(ast),
         }
     }
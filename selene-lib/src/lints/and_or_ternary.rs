@@ -0,0 +1,205 @@
+use super::*;
+use crate::{
+    ast_util::{
+        name_paths::{name_path_from_prefix_suffix, take_while_keep_going},
+        range,
+        std_types::StdTypes,
+        strip_parentheses,
+    },
+    standard_library::{FieldKind, LuaVersion},
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::{Symbol, TokenType},
+    visitors::Visitor,
+};
+
+pub struct AndOrTernaryLint;
+
+impl Lint for AndOrTernaryLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(AndOrTernaryLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = AndOrTernaryVisitor {
+            std_types: StdTypes::new(&ast_context.scope_manager, &context.standard_library),
+            luau: context
+                .standard_library
+                .lua_versions
+                .contains(&LuaVersion::Luau),
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct AndOrTernaryVisitor<'a> {
+    std_types: StdTypes<'a>,
+    // Luau has `if x then y else z` expressions, which don't have this problem
+    luau: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn text<N: Node + std::fmt::Display>(node: &N) -> String {
+    node.to_string().trim().to_owned()
+}
+
+fn is_falsy_literal(expression: &ast::Expression) -> bool {
+    matches!(
+        expression,
+        ast::Expression::Symbol(token)
+            if matches!(
+                token.token_type(),
+                TokenType::Symbol {
+                    symbol: Symbol::False | Symbol::Nil
+                }
+            )
+    )
+}
+
+impl AndOrTernaryVisitor<'_> {
+    // The name of the standard library function being called, if its first return value can be nil
+    fn nilable_call_name(&self, expression: &ast::Expression) -> Option<String> {
+        let ast::Expression::FunctionCall(call) = expression else {
+            return None;
+        };
+
+        let mut keep_going = true;
+        let suffixes = call
+            .suffixes()
+            .take_while(|suffix| take_while_keep_going(suffix, &mut keep_going))
+            .collect::<Vec<_>>();
+
+        // Calling what a call returns, like `f()()`
+        if suffixes.len() != call.suffixes().count() {
+            return None;
+        }
+
+        let name_path = name_path_from_prefix_suffix(call.prefix(), suffixes.iter().copied())?;
+
+        let root = self
+            .std_types
+            .name_path_root(range(call.prefix()).0, &name_path)?;
+
+        let FieldKind::Function(function) =
+            &self.std_types.find_field(&root, &name_path)?.field_kind
+        else {
+            return None;
+        };
+
+        function
+            .returns
+            .first()?
+            .is_nilable()
+            .then(|| name_path.join("."))
+    }
+}
+
+impl Visitor for AndOrTernaryVisitor<'_> {
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        let ast::Expression::BinaryOperator {
+            lhs,
+            binop: ast::BinOp::Or(_),
+            rhs: otherwise,
+        } = expression
+        else {
+            return;
+        };
+
+        let ast::Expression::BinaryOperator {
+            lhs: condition,
+            binop: ast::BinOp::And(_),
+            rhs: value,
+        } = lhs.as_ref()
+        else {
+            return;
+        };
+
+        let stripped_value = strip_parentheses(value);
+
+        let (message, value_label) = if is_falsy_literal(stripped_value) {
+            (
+                format!(
+                    "this always evaluates to `{}`, since `{}` is falsy",
+                    text(otherwise),
+                    text(stripped_value)
+                ),
+                "always falsy".to_owned(),
+            )
+        } else if let Some(function_name) = self.nilable_call_name(stripped_value) {
+            (
+                format!(
+                    "this evaluates to `{}` whenever `{function_name}` returns nil",
+                    text(otherwise)
+                ),
+                format!("`{function_name}` can return nil"),
+            )
+        } else {
+            return;
+        };
+
+        let (condition, value, otherwise) = (text(condition), text(value), text(otherwise));
+
+        let note = if self.luau {
+            format!("try: `if {condition} then {value} else {otherwise}` instead")
+        } else {
+            "`x and y or z` only works when `y` can't be false or nil, try an `if` statement instead"
+                .to_owned()
+        };
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "and_or_ternary",
+            message,
+            Label::new(range::<_, usize>(expression)),
+            vec![note],
+            vec![Label::new_with_message(
+                range::<_, usize>(stripped_value),
+                value_label,
+            )],
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::test_util::{test_lint, test_lint_config, TestUtilConfig},
+        *,
+    };
+    use crate::standard_library::StandardLibrary;
+
+    #[test]
+    fn test_and_or_ternary() {
+        test_lint(
+            AndOrTernaryLint::new(()).unwrap(),
+            "and_or_ternary",
+            "and_or_ternary",
+        );
+    }
+
+    #[test]
+    fn test_luau() {
+        test_lint_config(
+            AndOrTernaryLint::new(()).unwrap(),
+            "and_or_ternary",
+            "luau",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("luau").unwrap(),
+                ..Default::default()
+            },
+        );
+    }
+}
@@ -0,0 +1,658 @@
+use super::*;
+use crate::ast_util::{range, scopes::ScopeManager, strip_parentheses};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::{StringLiteralQuoteType, Symbol, TokenReference, TokenType},
+    visitors::Visitor,
+};
+
+pub struct BadFormatStringLint;
+
+impl Lint for BadFormatStringLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Error;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(BadFormatStringLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = BadFormatStringVisitor {
+            context,
+            scope_manager: &ast_context.scope_manager,
+            luau: context.standard_library.lua_version().0.has_luau(),
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FormatFunction {
+    Format,
+    Date,
+    Pack,
+    Unpack,
+    PackSize,
+}
+
+impl FormatFunction {
+    fn from_string_method(name: &str) -> Option<Self> {
+        match name {
+            "format" => Some(FormatFunction::Format),
+            "pack" => Some(FormatFunction::Pack),
+            "unpack" => Some(FormatFunction::Unpack),
+            "packsize" => Some(FormatFunction::PackSize),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            FormatFunction::Format => "string.format",
+            FormatFunction::Date => "os.date",
+            FormatFunction::Pack => "string.pack",
+            FormatFunction::Unpack => "string.unpack",
+            FormatFunction::PackSize => "string.packsize",
+        }
+    }
+}
+
+/// What a placeholder in a format string expects its argument to be.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Expects {
+    Number,
+    String,
+    Anything,
+}
+
+struct Placeholder {
+    range: (usize, usize),
+    expects: Expects,
+}
+
+struct InvalidFormat {
+    range: (usize, usize),
+    message: String,
+}
+
+struct BadFormatStringVisitor<'a> {
+    context: &'a Context,
+    scope_manager: &'a ScopeManager,
+    luau: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl BadFormatStringVisitor<'_> {
+    // `string.format(...)` and `os.date(...)`, as long as `string` and `os` aren't locals
+    fn library_call<'b>(
+        &self,
+        call: &'b ast::FunctionCall,
+    ) -> Option<(FormatFunction, &'b ast::FunctionArgs)> {
+        let ast::Prefix::Name(library) = call.prefix() else {
+            return None;
+        };
+
+        let mut suffixes = call.suffixes();
+
+        let (
+            Some(ast::Suffix::Index(ast::Index::Dot { name, .. })),
+            Some(ast::Suffix::Call(ast::Call::AnonymousCall(args))),
+            None,
+        ) = (suffixes.next(), suffixes.next(), suffixes.next())
+        else {
+            return None;
+        };
+
+        let library_name = library.token().to_string();
+        let function_name = name.token().to_string();
+
+        let function = match (library_name.as_str(), function_name.as_str()) {
+            ("os", "date") => FormatFunction::Date,
+            ("string", function_name) => FormatFunction::from_string_method(function_name)?,
+            _ => return None,
+        };
+
+        if self
+            .scope_manager
+            .reference_at_byte(library.token().start_position().bytes())
+            .is_some_and(|reference| reference.resolved.is_some())
+        {
+            return None;
+        }
+
+        self.context
+            .standard_library
+            .find_global(&[library_name, function_name])?;
+
+        Some((function, args))
+    }
+
+    fn check_call(
+        &mut self,
+        function: FormatFunction,
+        format: &ast::Expression,
+        format_token: &TokenReference,
+        arguments: Vec<&ast::Expression>,
+        call_range: (usize, usize),
+    ) {
+        let TokenType::StringLiteral {
+            literal,
+            multi_line_depth,
+            quote_type,
+        } = format_token.token_type()
+        else {
+            return;
+        };
+
+        let (token_start, _): (usize, usize) = range(format_token);
+        let content_start = token_start
+            + match quote_type {
+                StringLiteralQuoteType::Brackets => multi_line_depth + 2,
+                _ => 1,
+            };
+
+        let parsed = match function {
+            FormatFunction::Format => parse_string_format(literal, self.luau),
+            FormatFunction::Date => parse_os_date(literal).map(|()| Vec::new()),
+            FormatFunction::Pack | FormatFunction::Unpack | FormatFunction::PackSize => {
+                parse_pack_format(literal, function == FormatFunction::PackSize)
+            }
+        };
+
+        let placeholders = match parsed {
+            Ok(placeholders) => placeholders,
+            Err(invalid) => {
+                self.diagnostics.push(Diagnostic::new(
+                    "bad_format_string",
+                    invalid.message,
+                    Label::new((
+                        content_start + invalid.range.0,
+                        content_start + invalid.range.1,
+                    )),
+                ));
+
+                return;
+            }
+        };
+
+        // Only `string.format` and `string.pack` take values for their format
+        if !matches!(function, FormatFunction::Format | FormatFunction::Pack) {
+            return;
+        }
+
+        let placeholder_range = |placeholder: &Placeholder| {
+            (
+                content_start + placeholder.range.0,
+                content_start + placeholder.range.1,
+            )
+        };
+
+        for (placeholder, argument) in placeholders.iter().zip(&arguments) {
+            let Some(given) = mismatched_literal(argument, placeholder.expects) else {
+                continue;
+            };
+
+            let expected = if placeholder.expects == Expects::Number {
+                "a number"
+            } else {
+                "a string"
+            };
+
+            self.diagnostics.push(Diagnostic::new_complete(
+                "bad_format_string",
+                format!("this is {given}, but the format expects {expected}"),
+                Label::new::<usize>(range(argument)),
+                Vec::new(),
+                vec![Label::new_with_message(
+                    placeholder_range(placeholder),
+                    format!("expects {expected}"),
+                )],
+            ));
+        }
+
+        // `string.format("%d %d", f())` could be given any number of values
+        let has_trailing_values = arguments.last().is_some_and(|argument| match argument {
+            ast::Expression::FunctionCall(_) => true,
+            ast::Expression::Symbol(symbol) => matches!(
+                symbol.token_type(),
+                TokenType::Symbol {
+                    symbol: Symbol::Ellipsis
+                }
+            ),
+            _ => false,
+        });
+
+        let given = arguments.len() - usize::from(has_trailing_values);
+        let needed = placeholders.len();
+
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+
+        if given > needed {
+            let (extra_start, _): (usize, usize) = range(arguments[needed]);
+            let (_, extra_end): (usize, usize) = range(arguments[given - 1]);
+
+            self.diagnostics.push(Diagnostic::new_complete(
+                "bad_format_string",
+                format!(
+                    "`{}` was given {given} value{} to format, but its format only uses {needed}",
+                    function.name(),
+                    plural(given),
+                ),
+                Label::new((extra_start, extra_end)),
+                Vec::new(),
+                vec![Label::new_with_message(
+                    range::<_, usize>(format),
+                    format!("this uses {needed} value{}", plural(needed)),
+                )],
+            ));
+        } else if given < needed && !has_trailing_values {
+            let missing = &placeholders[given..];
+
+            self.diagnostics.push(Diagnostic::new_complete(
+                "bad_format_string",
+                format!(
+                    "`{}` was given {given} value{} to format, but its format needs {needed}",
+                    function.name(),
+                    plural(given),
+                ),
+                Label::new(call_range),
+                Vec::new(),
+                missing
+                    .iter()
+                    .map(|placeholder| {
+                        Label::new_with_message(
+                            placeholder_range(placeholder),
+                            "no value for this".to_owned(),
+                        )
+                    })
+                    .collect(),
+            ));
+        }
+    }
+}
+
+impl Visitor for BadFormatStringVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        // `string.format("%d", 1)`
+        if let Some((function, ast::FunctionArgs::Parentheses { arguments, .. })) =
+            self.library_call(call)
+        {
+            let mut arguments = arguments.iter();
+
+            let Some(format) = arguments.next() else {
+                return;
+            };
+
+            if let ast::Expression::String(format_token) = strip_parentheses(format) {
+                self.check_call(
+                    function,
+                    format,
+                    format_token,
+                    arguments.collect(),
+                    range(call),
+                );
+            }
+
+            return;
+        }
+
+        // `("%d"):format(1)`
+        let ast::Prefix::Expression(prefix) = call.prefix() else {
+            return;
+        };
+
+        let ast::Expression::String(format_token) = strip_parentheses(prefix) else {
+            return;
+        };
+
+        let mut suffixes = call.suffixes();
+
+        let (Some(ast::Suffix::Call(ast::Call::MethodCall(method_call))), None) =
+            (suffixes.next(), suffixes.next())
+        else {
+            return;
+        };
+
+        let Some(function) = FormatFunction::from_string_method(&method_call.name().to_string())
+        else {
+            return;
+        };
+
+        if let ast::FunctionArgs::Parentheses { arguments, .. } = method_call.args() {
+            self.check_call(
+                function,
+                prefix,
+                format_token,
+                arguments.iter().collect(),
+                range(call),
+            );
+        }
+    }
+}
+
+// What kind of literal the expression is, if it's definitely not what the placeholder expects.
+// Strings holding numbers are fine for numbers and vice versa, since Lua converts between them.
+fn mismatched_literal(expression: &ast::Expression, expects: Expects) -> Option<&'static str> {
+    if expects == Expects::Anything {
+        return None;
+    }
+
+    match strip_parentheses(expression) {
+        ast::Expression::String(token) => match token.token_type() {
+            TokenType::StringLiteral { literal, .. }
+                if expects == Expects::Number && !is_numeric_string(literal) =>
+            {
+                Some("a string")
+            }
+
+            _ => None,
+        },
+
+        ast::Expression::Symbol(symbol) => match symbol.token_type() {
+            TokenType::Symbol {
+                symbol: Symbol::Nil,
+            } => Some("nil"),
+
+            TokenType::Symbol {
+                symbol: Symbol::True | Symbol::False,
+            } => Some("a boolean"),
+
+            _ => None,
+        },
+
+        ast::Expression::TableConstructor(_) => Some("a table"),
+        ast::Expression::Function(_) => Some("a function"),
+
+        _ => None,
+    }
+}
+
+fn is_numeric_string(text: &str) -> bool {
+    let text = text.trim();
+
+    text.parse::<f64>().is_ok()
+        || text
+            .strip_prefix("0x")
+            .or_else(|| text.strip_prefix("0X"))
+            .is_some_and(|hex| u64::from_str_radix(hex, 16).is_ok())
+}
+
+/// Parses the format of `string.format`, returning each placeholder that takes a value.
+fn parse_string_format(format: &str, luau: bool) -> Result<Vec<Placeholder>, InvalidFormat> {
+    let mut placeholders = Vec::new();
+    let mut chars = format.char_indices().peekable();
+
+    while let Some((start, char)) = chars.next() {
+        if char != '%' {
+            continue;
+        }
+
+        if chars.next_if(|&(_, char)| char == '%').is_some() {
+            continue;
+        }
+
+        if luau {
+            if let Some((index, _)) = chars.next_if(|&(_, char)| char == '*') {
+                placeholders.push(Placeholder {
+                    range: (start, index + 1),
+                    expects: Expects::Anything,
+                });
+
+                continue;
+            }
+        }
+
+        while chars
+            .next_if(|&(_, char)| matches!(char, '-' | '+' | ' ' | '#' | '0'))
+            .is_some()
+        {}
+
+        for part in ["width", "precision"] {
+            if part == "precision" && chars.next_if(|&(_, char)| char == '.').is_none() {
+                break;
+            }
+
+            let mut digits = 0;
+            while let Some((index, _)) = chars.next_if(|(_, char)| char.is_ascii_digit()) {
+                digits += 1;
+
+                if digits > 2 {
+                    return Err(InvalidFormat {
+                        range: (start, index + 1),
+                        message: format!("the {part} of a format can only be up to 99"),
+                    });
+                }
+            }
+        }
+
+        let Some((index, conversion)) = chars.next() else {
+            return Err(InvalidFormat {
+                range: (start, format.len()),
+                message: "format string ends with an unfinished `%`".to_owned(),
+            });
+        };
+
+        let range = (start, index + conversion.len_utf8());
+
+        let expects = match conversion {
+            'c' | 'd' | 'i' | 'o' | 'u' | 'x' | 'X' | 'e' | 'E' | 'f' | 'F' | 'g' | 'G' | 'a'
+            | 'A' => Expects::Number,
+            's' | 'q' | 'p' => Expects::Anything,
+
+            _ => {
+                return Err(InvalidFormat {
+                    range,
+                    message: format!(
+                        "invalid conversion `{}` in format",
+                        &format[range.0..range.1]
+                    ),
+                })
+            }
+        };
+
+        placeholders.push(Placeholder { range, expects });
+    }
+
+    Ok(placeholders)
+}
+
+/// Parses the format of `os.date`, which doesn't take any values.
+fn parse_os_date(format: &str) -> Result<(), InvalidFormat> {
+    // `!` is for UTC, and `*t` is for returning a table instead
+    let offset = usize::from(format.starts_with('!'));
+    if format[offset..].starts_with("*t") {
+        return Ok(());
+    }
+
+    let mut chars = format.char_indices().skip(offset);
+
+    while let Some((start, char)) = chars.next() {
+        if char != '%' {
+            continue;
+        }
+
+        let Some((index, conversion)) = chars.next() else {
+            return Err(InvalidFormat {
+                range: (start, format.len()),
+                message: "format string ends with an unfinished `%`".to_owned(),
+            });
+        };
+
+        // `%E` and `%O` modify the conversion after them
+        let (valid, end) = match conversion {
+            'E' | 'O' => {
+                let modifiable = if conversion == 'E' {
+                    "cCxXyY"
+                } else {
+                    "deHImMSuUVwWy"
+                };
+
+                match chars.next() {
+                    Some((index, modified)) => {
+                        (modifiable.contains(modified), index + modified.len_utf8())
+                    }
+                    None => (false, format.len()),
+                }
+            }
+
+            _ => (
+                "aAbBcCdDeFgGhHIjmMnprRStTuUVwWxXyYzZ%".contains(conversion),
+                index + conversion.len_utf8(),
+            ),
+        };
+
+        if !valid {
+            return Err(InvalidFormat {
+                range: (start, end),
+                message: format!(
+                    "invalid conversion `{}` in `os.date` format",
+                    &format[start..end]
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses the format of `string.pack`, `string.unpack`, and `string.packsize`,
+/// returning each option that takes a value.
+fn parse_pack_format(format: &str, fixed_size: bool) -> Result<Vec<Placeholder>, InvalidFormat> {
+    let mut placeholders = Vec::new();
+    let mut chars = format.char_indices().peekable();
+
+    while let Some((start, option)) = chars.next() {
+        let mut end = start + option.len_utf8();
+
+        // `i4`, `s8`, and so on
+        let mut size = String::new();
+        if matches!(option, 'i' | 'I' | 's' | '!') {
+            while let Some((index, digit)) = chars.next_if(|(_, char)| char.is_ascii_digit()) {
+                size.push(digit);
+                end = index + 1;
+            }
+        }
+
+        if !size.is_empty() && !(1..=16).contains(&size.parse::<u32>().unwrap_or(u32::MAX)) {
+            return Err(InvalidFormat {
+                range: (start, end),
+                message: format!("the size of `{option}` must be between 1 and 16"),
+            });
+        }
+
+        let expects = match option {
+            ' ' | '<' | '>' | '=' | '!' | 'x' => continue,
+
+            // `X` aligns to the option after it, without using it
+            'X' => {
+                if chars
+                    .next_if(|(_, char)| "bBhHlLjJTiIfdn".contains(*char))
+                    .is_none()
+                {
+                    return Err(InvalidFormat {
+                        range: (start, end),
+                        message: "`X` must be followed by an option with a size, like `Xi4`"
+                            .to_owned(),
+                    });
+                }
+
+                while chars.next_if(|(_, char)| char.is_ascii_digit()).is_some() {}
+                continue;
+            }
+
+            'b' | 'B' | 'h' | 'H' | 'l' | 'L' | 'j' | 'J' | 'T' | 'i' | 'I' | 'f' | 'd' | 'n' => {
+                Expects::Number
+            }
+
+            's' | 'z' => {
+                if fixed_size {
+                    return Err(InvalidFormat {
+                        range: (start, end),
+                        message: format!(
+                            "`string.packsize` can't be used with `{option}`, which has a variable size"
+                        ),
+                    });
+                }
+
+                Expects::String
+            }
+
+            _ => {
+                return Err(InvalidFormat {
+                    range: (start, end),
+                    message: format!("invalid option `{option}` in pack format"),
+                })
+            }
+        };
+
+        placeholders.push(Placeholder {
+            range: (start, end),
+            expects,
+        });
+    }
+
+    Ok(placeholders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::test_util::{test_lint, test_lint_config, TestUtilConfig},
+        *,
+    };
+    use crate::StandardLibrary;
+
+    #[test]
+    fn test_bad_format_string() {
+        test_lint(
+            BadFormatStringLint::new(()).unwrap(),
+            "bad_format_string",
+            "bad_format_string",
+        );
+    }
+
+    #[test]
+    fn test_os_date() {
+        test_lint(
+            BadFormatStringLint::new(()).unwrap(),
+            "bad_format_string",
+            "os_date",
+        );
+    }
+
+    #[test]
+    fn test_string_pack() {
+        test_lint_config(
+            BadFormatStringLint::new(()).unwrap(),
+            "bad_format_string",
+            "string_pack",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("lua53").unwrap(),
+                ..Default::default()
+            },
+        );
+    }
+
+    #[cfg(feature = "roblox")]
+    #[test]
+    fn test_luau() {
+        test_lint_config(
+            BadFormatStringLint::new(()).unwrap(),
+            "bad_format_string",
+            "luau",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("luau").unwrap(),
+                ..Default::default()
+            },
+        );
+    }
+}
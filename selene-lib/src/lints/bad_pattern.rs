@@ -0,0 +1,571 @@
+use super::*;
+use crate::ast_util::{range, scopes::ScopeManager, strip_parentheses};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::{StringLiteralQuoteType, Symbol, TokenReference, TokenType},
+    visitors::Visitor,
+};
+
+// Lua errors with "too many captures" past this
+const MAX_CAPTURES: usize = 32;
+
+pub struct BadPatternLint;
+
+impl Lint for BadPatternLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(BadPatternLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = BadPatternVisitor {
+            context,
+            scope_manager: &ast_context.scope_manager,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct PatternIssue {
+    // Indexes into the characters of the string, rather than bytes
+    start: usize,
+    end: usize,
+    message: String,
+    note: Option<String>,
+}
+
+struct BadPatternVisitor<'a> {
+    context: &'a Context,
+    scope_manager: &'a ScopeManager,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl BadPatternVisitor<'_> {
+    // The arguments after the string being searched, for `string.match(text, ...)`
+    fn library_call_arguments<'b>(
+        &self,
+        call: &'b ast::FunctionCall,
+    ) -> Option<(String, Vec<&'b ast::Expression>)> {
+        let ast::Prefix::Name(library) = call.prefix() else {
+            return None;
+        };
+
+        let mut suffixes = call.suffixes();
+
+        let (
+            Some(ast::Suffix::Index(ast::Index::Dot { name, .. })),
+            Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+                arguments,
+                ..
+            }))),
+            None,
+        ) = (suffixes.next(), suffixes.next(), suffixes.next())
+        else {
+            return None;
+        };
+
+        if library.token().to_string() != "string"
+            || self
+                .scope_manager
+                .reference_at_byte(library.token().start_position().bytes())
+                .is_some_and(|reference| reference.resolved.is_some())
+        {
+            return None;
+        }
+
+        let function_name = name.token().to_string();
+
+        self.context
+            .standard_library
+            .find_global(&["string", &function_name])?;
+
+        Some((function_name, arguments.iter().skip(1).collect()))
+    }
+
+    // `text:match(...)`
+    fn method_call_arguments<'b>(
+        &self,
+        call: &'b ast::FunctionCall,
+    ) -> Option<(String, Vec<&'b ast::Expression>)> {
+        let Some(ast::Suffix::Call(ast::Call::MethodCall(method_call))) = call.suffixes().last()
+        else {
+            return None;
+        };
+
+        let ast::FunctionArgs::Parentheses { arguments, .. } = method_call.args() else {
+            return None;
+        };
+
+        Some((
+            method_call.name().token().to_string(),
+            arguments.iter().collect(),
+        ))
+    }
+
+    fn check_call(&mut self, function_name: &str, arguments: &[&ast::Expression]) {
+        if !matches!(function_name, "find" | "match" | "gmatch" | "gsub") {
+            return;
+        }
+
+        let Some(ast::Expression::String(pattern_token)) =
+            arguments.first().map(|pattern| strip_parentheses(pattern))
+        else {
+            return;
+        };
+
+        // `string.find(text, pattern, 1, true)` doesn't use patterns
+        if function_name == "find"
+            && arguments.get(2).is_some_and(|plain| {
+                !matches!(
+                    strip_parentheses(plain),
+                    ast::Expression::Symbol(symbol)
+                        if matches!(
+                            symbol.token_type(),
+                            TokenType::Symbol {
+                                symbol: Symbol::Nil | Symbol::False
+                            }
+                        )
+                )
+            })
+        {
+            return;
+        }
+
+        let Some(pattern) = StringValue::new(pattern_token) else {
+            return;
+        };
+
+        let (issues, captures) = check_pattern(&pattern.chars);
+        self.report(&pattern, issues);
+
+        if function_name != "gsub" {
+            return;
+        }
+
+        if let Some(ast::Expression::String(replacement_token)) = arguments
+            .get(1)
+            .map(|replacement| strip_parentheses(replacement))
+        {
+            if let Some(replacement) = StringValue::new(replacement_token) {
+                let issues = check_replacement(&replacement.chars, captures);
+                self.report(&replacement, issues);
+            }
+        }
+    }
+
+    fn report(&mut self, string: &StringValue, issues: Vec<PatternIssue>) {
+        for issue in issues {
+            self.diagnostics.push(Diagnostic::new_complete(
+                "bad_pattern",
+                issue.message,
+                Label::new(string.byte_range(issue.start, issue.end)),
+                issue.note.into_iter().collect(),
+                Vec::new(),
+            ));
+        }
+    }
+}
+
+impl Visitor for BadPatternVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if let Some((function_name, arguments)) = self
+            .library_call_arguments(call)
+            .or_else(|| self.method_call_arguments(call))
+        {
+            self.check_call(&function_name, &arguments);
+        }
+    }
+}
+
+/// The characters of a string literal after escapes, along with where each one starts in the code.
+struct StringValue {
+    chars: Vec<char>,
+    starts: Vec<usize>,
+    end: usize,
+}
+
+impl StringValue {
+    // Returns `None` for strings with invalid escapes, which `bad_string_escape` covers
+    fn new(token: &TokenReference) -> Option<Self> {
+        let TokenType::StringLiteral {
+            literal,
+            multi_line_depth,
+            quote_type,
+        } = token.token_type()
+        else {
+            return None;
+        };
+
+        let (token_start, token_end): (usize, usize) = range(token);
+
+        // The length of the quotes, or `[[` and `]]`
+        let delimiter = match quote_type {
+            StringLiteralQuoteType::Brackets => multi_line_depth + 2,
+            _ => 1,
+        };
+
+        let content_start = token_start + delimiter;
+
+        let mut value = StringValue {
+            chars: Vec::new(),
+            starts: Vec::new(),
+            end: token_end - delimiter,
+        };
+
+        let mut chars = literal.char_indices().peekable();
+
+        while let Some((index, char)) = chars.next() {
+            let start = content_start + index;
+
+            if char != '\\' || *quote_type == StringLiteralQuoteType::Brackets {
+                value.push(char, start);
+                continue;
+            }
+
+            let (_, escape) = chars.next()?;
+
+            let escaped = match escape {
+                'a' => '\x07',
+                'b' => '\x08',
+                'f' => '\x0c',
+                'n' | '\n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                'v' => '\x0b',
+                '\\' | '"' | '\'' => escape,
+
+                'z' => {
+                    while chars.next_if(|(_, char)| char.is_whitespace()).is_some() {}
+                    continue;
+                }
+
+                'x' => {
+                    let mut code = String::new();
+                    for _ in 0..2 {
+                        code.push(chars.next()?.1);
+                    }
+
+                    char::from(u8::from_str_radix(&code, 16).ok()?)
+                }
+
+                'u' => {
+                    chars.next_if(|(_, char)| *char == '{')?;
+
+                    let mut code = String::new();
+                    while let Some((_, digit)) = chars.next_if(|(_, char)| *char != '}') {
+                        code.push(digit);
+                    }
+
+                    chars.next()?;
+                    char::from_u32(u32::from_str_radix(&code, 16).ok()?)?
+                }
+
+                '0'..='9' => {
+                    let mut code = escape.to_string();
+                    while code.len() < 3 {
+                        match chars.next_if(|(_, char)| char.is_ascii_digit()) {
+                            Some((_, digit)) => code.push(digit),
+                            None => break,
+                        }
+                    }
+
+                    char::from(code.parse::<u8>().ok()?)
+                }
+
+                _ => return None,
+            };
+
+            value.push(escaped, start);
+        }
+
+        Some(value)
+    }
+
+    fn push(&mut self, char: char, start: usize) {
+        self.chars.push(char);
+        self.starts.push(start);
+    }
+
+    fn byte_range(&self, start: usize, end: usize) -> (usize, usize) {
+        (
+            self.starts.get(start).copied().unwrap_or(self.end),
+            self.starts.get(end).copied().unwrap_or(self.end),
+        )
+    }
+}
+
+fn is_class(char: char) -> bool {
+    // `%z` was replaced by `\0` in Lua 5.2, but still works
+    matches!(
+        char.to_ascii_lowercase(),
+        'a' | 'c' | 'd' | 'g' | 'l' | 'p' | 's' | 'u' | 'w' | 'x' | 'z'
+    )
+}
+
+// `%y` isn't an error, but only matches `y`, which is almost certainly not what was meant
+fn check_class(chars: &[char], index: usize, issues: &mut Vec<PatternIssue>) {
+    let class = chars[index + 1];
+
+    if class.is_ascii_alphabetic() && !is_class(class) {
+        issues.push(PatternIssue {
+            start: index,
+            end: index + 2,
+            message: format!("`%{class}` is not a character class, so only matches `{class}`"),
+            note: Some(format!(
+                "if you meant to match `{class}`, write it without the `%`"
+            )),
+        });
+    }
+}
+
+/// Checks a pattern the same way Lua does when it's used, returning any issues and the amount of captures.
+/// Once an error is found, no more of the pattern is checked.
+fn check_pattern(chars: &[char]) -> (Vec<PatternIssue>, usize) {
+    let mut issues = Vec::new();
+
+    // Where each capture starts, and whether it's been closed
+    let mut captures: Vec<(usize, bool)> = Vec::new();
+
+    let mut index = usize::from(chars.first() == Some(&'^'));
+
+    macro_rules! error {
+        ($start:expr, $end:expr, $($message:tt)+) => {{
+            issues.push(PatternIssue {
+                start: $start,
+                end: $end,
+                message: format!($($message)+),
+                note: None,
+            });
+
+            return (issues, captures.len());
+        }};
+    }
+
+    while index < chars.len() {
+        match chars[index] {
+            '(' => {
+                if captures.len() == MAX_CAPTURES {
+                    error!(
+                        index,
+                        index + 1,
+                        "pattern has more than {MAX_CAPTURES} captures"
+                    );
+                }
+
+                captures.push((index, false));
+                index += 1;
+            }
+
+            ')' => {
+                let Some(capture) = captures.iter_mut().rev().find(|(_, closed)| !closed) else {
+                    error!(index, index + 1, "unmatched `)` in pattern");
+                };
+
+                capture.1 = true;
+                index += 1;
+            }
+
+            '%' => {
+                let Some(&escaped) = chars.get(index + 1) else {
+                    error!(index, index + 1, "pattern ends with an unfinished `%`");
+                };
+
+                match escaped {
+                    'b' => {
+                        if index + 3 >= chars.len() {
+                            error!(
+                                index,
+                                chars.len(),
+                                "`%b` needs two characters after it, like `%b()`"
+                            );
+                        }
+
+                        index += 4;
+                    }
+
+                    'f' => {
+                        if chars.get(index + 2) != Some(&'[') {
+                            error!(index, index + 2, "`%f` needs a set after it, like `%f[%w]`");
+                        }
+
+                        match check_set(chars, index + 2, &mut issues) {
+                            Some(end) => index = end,
+                            None => return (issues, captures.len()),
+                        }
+                    }
+
+                    '0'..='9' => {
+                        let capture = escaped.to_digit(10).unwrap() as usize;
+
+                        match captures.get(capture.wrapping_sub(1)) {
+                            Some((_, true)) => {}
+
+                            Some((_, false)) => error!(
+                                index,
+                                index + 2,
+                                "`%{capture}` refers to a capture that isn't finished yet"
+                            ),
+
+                            None => error!(
+                                index,
+                                index + 2,
+                                "`%{capture}` refers to a capture that doesn't exist"
+                            ),
+                        }
+
+                        index += 2;
+                    }
+
+                    _ => {
+                        check_class(chars, index, &mut issues);
+                        index += 2;
+                    }
+                }
+            }
+
+            '[' => match check_set(chars, index, &mut issues) {
+                Some(end) => index = end,
+                None => return (issues, captures.len()),
+            },
+
+            _ => index += 1,
+        }
+    }
+
+    if let Some(&(start, _)) = captures.iter().find(|(_, closed)| !closed) {
+        error!(start, start + 1, "unfinished capture in pattern");
+    }
+
+    (issues, captures.len())
+}
+
+// Checks a set like `[%w_]` starting at the `[`, returning the index after the `]`
+fn check_set(chars: &[char], start: usize, issues: &mut Vec<PatternIssue>) -> Option<usize> {
+    let mut index = start + 1;
+
+    if chars.get(index) == Some(&'^') {
+        index += 1;
+    }
+
+    // The first character is always part of the set, so `[]]` matches `]`
+    loop {
+        let Some(&char) = chars.get(index) else {
+            issues.push(PatternIssue {
+                start,
+                end: start + 1,
+                message: "unclosed `[` in pattern".to_owned(),
+                note: Some("use `%[` to match a `[`".to_owned()),
+            });
+
+            return None;
+        };
+
+        if char == '%' {
+            if index + 1 >= chars.len() {
+                issues.push(PatternIssue {
+                    start: index,
+                    end: index + 1,
+                    message: "pattern ends with an unfinished `%`".to_owned(),
+                    note: None,
+                });
+
+                return None;
+            }
+
+            check_class(chars, index, issues);
+            index += 1;
+        }
+
+        index += 1;
+
+        if chars.get(index) == Some(&']') {
+            return Some(index + 1);
+        }
+    }
+}
+
+// Checks the replacement string of `string.gsub`, where `%1` and such refer to captures
+fn check_replacement(chars: &[char], captures: usize) -> Vec<PatternIssue> {
+    let mut issues = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        if chars[index] != '%' {
+            index += 1;
+            continue;
+        }
+
+        match chars.get(index + 1) {
+            Some('%') => {}
+
+            Some(&digit) if digit.is_ascii_digit() => {
+                // With no captures, `%1` is the whole match, same as `%0`
+                let capture = digit.to_digit(10).unwrap() as usize;
+
+                if capture > captures.max(1) {
+                    let has = match captures {
+                        0 => "has no captures".to_owned(),
+                        1 => "only has 1 capture".to_owned(),
+                        _ => format!("only has {captures} captures"),
+                    };
+
+                    issues.push(PatternIssue {
+                        start: index,
+                        end: index + 2,
+                        message: format!(
+                            "`%{capture}` refers to a capture that doesn't exist, as the pattern {has}"
+                        ),
+                        note: None,
+                    });
+                }
+            }
+
+            _ => {
+                issues.push(PatternIssue {
+                    start: index,
+                    end: (index + 2).min(chars.len()),
+                    message: "invalid use of `%` in replacement string".to_owned(),
+                    note: Some("use `%%` to insert a `%`".to_owned()),
+                });
+            }
+        }
+
+        index += 2;
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_bad_pattern() {
+        test_lint(
+            BadPatternLint::new(()).unwrap(),
+            "bad_pattern",
+            "bad_pattern",
+        );
+    }
+
+    #[test]
+    fn test_replacements() {
+        test_lint(
+            BadPatternLint::new(()).unwrap(),
+            "bad_pattern",
+            "replacements",
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::ast_util::constant::Constant;
 use std::convert::Infallible;
 
 use full_moon::{
@@ -56,23 +57,8 @@ struct Comparison {
     range: (usize, usize),
 }
 
-fn value_is_zero(value: &ast::Expression) -> bool {
-    if let ast::Expression::Number(token) = value {
-        token.token().to_string() == "0"
-    } else {
-        false
-    }
-}
-
 fn expression_is_nan(node: &ast::Expression) -> bool {
-    if_chain::if_chain! {
-        if let ast::Expression::BinaryOperator { lhs, binop: ast::BinOp::Slash(_), rhs } = node;
-        if value_is_zero(lhs) && value_is_zero(rhs);
-        then {
-            return true;
-        }
-    }
-    false
+    matches!(Constant::evaluate(node), Some(Constant::Number(number)) if number.is_nan())
 }
 
 impl Visitor for CompareNanVisitor {
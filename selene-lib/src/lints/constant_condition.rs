@@ -0,0 +1,296 @@
+use super::*;
+use crate::ast_util::{constant::Constant, purge_trivia, range, HasSideEffects};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::{Symbol, TokenType},
+    visitors::{Visit, Visitor},
+};
+
+pub struct ConstantConditionLint;
+
+impl Lint for ConstantConditionLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(ConstantConditionLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = ConstantConditionVisitor {
+            reported: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct ConstantConditionVisitor {
+    // Conditions already reported, so the comparisons inside them aren't reported again
+    reported: Vec<(usize, usize)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn is_true_literal(expression: &ast::Expression) -> bool {
+    matches!(
+        expression,
+        ast::Expression::Symbol(token)
+            if *token.token_type() == TokenType::Symbol { symbol: Symbol::True }
+    )
+}
+
+fn is_comparison(binop: &ast::BinOp) -> bool {
+    matches!(
+        binop,
+        ast::BinOp::TwoEqual(_)
+            | ast::BinOp::TildeEqual(_)
+            | ast::BinOp::GreaterThan(_)
+            | ast::BinOp::GreaterThanEqual(_)
+            | ast::BinOp::LessThan(_)
+            | ast::BinOp::LessThanEqual(_)
+    )
+}
+
+fn truthiness(constant: &Constant) -> &'static str {
+    if constant.is_truthy() {
+        "truthy"
+    } else {
+        "falsy"
+    }
+}
+
+// Finds whether a loop body can exit the loop with `break`, `return`, or `goto`
+#[derive(Default)]
+struct LoopExitVisitor {
+    // Loops and functions inside the body, whose `break`s and `return`s don't exit it
+    loop_depth: usize,
+    function_depth: usize,
+    found: bool,
+}
+
+impl Visitor for LoopExitVisitor {
+    fn visit_function_body(&mut self, _: &ast::FunctionBody) {
+        self.function_depth += 1;
+    }
+
+    fn visit_function_body_end(&mut self, _: &ast::FunctionBody) {
+        self.function_depth -= 1;
+    }
+
+    fn visit_generic_for(&mut self, _: &ast::GenericFor) {
+        self.loop_depth += 1;
+    }
+
+    fn visit_generic_for_end(&mut self, _: &ast::GenericFor) {
+        self.loop_depth -= 1;
+    }
+
+    fn visit_numeric_for(&mut self, _: &ast::NumericFor) {
+        self.loop_depth += 1;
+    }
+
+    fn visit_numeric_for_end(&mut self, _: &ast::NumericFor) {
+        self.loop_depth -= 1;
+    }
+
+    fn visit_while(&mut self, _: &ast::While) {
+        self.loop_depth += 1;
+    }
+
+    fn visit_while_end(&mut self, _: &ast::While) {
+        self.loop_depth -= 1;
+    }
+
+    fn visit_repeat(&mut self, _: &ast::Repeat) {
+        self.loop_depth += 1;
+    }
+
+    fn visit_repeat_end(&mut self, _: &ast::Repeat) {
+        self.loop_depth -= 1;
+    }
+
+    fn visit_last_stmt(&mut self, last_stmt: &ast::LastStmt) {
+        if self.function_depth > 0 {
+            return;
+        }
+
+        match last_stmt {
+            ast::LastStmt::Return(_) => self.found = true,
+            ast::LastStmt::Break(_) if self.loop_depth == 0 => self.found = true,
+            _ => {}
+        }
+    }
+
+    #[cfg(any(feature = "lua52", feature = "luajit"))]
+    fn visit_goto(&mut self, _: &ast::lua52::Goto) {
+        if self.function_depth == 0 {
+            self.found = true;
+        }
+    }
+}
+
+impl ConstantConditionVisitor {
+    fn check_condition(&mut self, condition: &ast::Expression) {
+        let Some(constant) = Constant::evaluate(condition) else {
+            return;
+        };
+
+        self.report_condition(condition, &constant, Vec::new());
+    }
+
+    fn report_condition(
+        &mut self,
+        condition: &ast::Expression,
+        constant: &Constant,
+        notes: Vec<String>,
+    ) {
+        let range = range(condition);
+        self.reported.push(range);
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "constant_condition",
+            format!("this condition is always {}", truthiness(constant)),
+            Label::new(range),
+            notes,
+            Vec::new(),
+        ));
+    }
+
+    fn already_reported(&self, (start, end): (usize, usize)) -> bool {
+        self.reported
+            .iter()
+            .any(|&(reported_start, reported_end)| reported_start <= start && end <= reported_end)
+    }
+}
+
+impl Visitor for ConstantConditionVisitor {
+    fn visit_if(&mut self, if_block: &ast::If) {
+        // `if true then return end` is how to return early in the middle of a block, like when debugging
+        let is_early_return = is_true_literal(if_block.condition())
+            && if_block.block().stmts().next().is_none()
+            && matches!(
+                if_block.block().last_stmt(),
+                Some(ast::LastStmt::Return(_) | ast::LastStmt::Break(_))
+            );
+
+        if !is_early_return {
+            self.check_condition(if_block.condition());
+        }
+
+        for else_if in if_block.else_if().into_iter().flatten() {
+            self.check_condition(else_if.condition());
+        }
+    }
+
+    fn visit_while(&mut self, while_loop: &ast::While) {
+        let condition = while_loop.condition();
+
+        // `while true do` is the usual way to loop until a `break`, or forever
+        if is_true_literal(condition) {
+            return;
+        }
+
+        let Some(constant) = Constant::evaluate(condition) else {
+            return;
+        };
+
+        if !constant.is_truthy() {
+            self.report_condition(
+                condition,
+                &constant,
+                vec!["this loop will never run".to_owned()],
+            );
+            return;
+        }
+
+        let mut loop_exit_visitor = LoopExitVisitor::default();
+        while_loop.block().visit(&mut loop_exit_visitor);
+
+        if !loop_exit_visitor.found {
+            self.report_condition(
+                condition,
+                &constant,
+                vec![
+                    "this loop never stops, if that's intended, use `while true do` instead"
+                        .to_owned(),
+                ],
+            );
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        let ast::Expression::BinaryOperator { lhs, binop, rhs } = expression else {
+            return;
+        };
+
+        if !is_comparison(binop) {
+            return;
+        }
+
+        let range = range(expression);
+        if self.already_reported(range) {
+            return;
+        }
+
+        if let (Some(_), Some(_), Some(result)) = (
+            Constant::evaluate(lhs),
+            Constant::evaluate(rhs),
+            Constant::evaluate(expression),
+        ) {
+            self.diagnostics.push(Diagnostic::new(
+                "constant_condition",
+                format!(
+                    "this comparison is always {}",
+                    result == Constant::Boolean(true)
+                ),
+                Label::new(range),
+            ));
+
+            return;
+        }
+
+        // Only the NaN check idiom is allowed, `x ~= x` for "is NaN" and `x == x` for "isn't NaN",
+        // which is what `compare_nan` suggests. Other expressions compared to themselves are reported.
+        if matches!(binop, ast::BinOp::TwoEqual(_) | ast::BinOp::TildeEqual(_))
+            && matches!(lhs.as_ref(), ast::Expression::Var(_))
+        {
+            return;
+        }
+
+        if lhs.has_side_effects() || rhs.has_side_effects() {
+            return;
+        }
+
+        if purge_trivia(lhs.as_ref()).to_string() != purge_trivia(rhs.as_ref()).to_string() {
+            return;
+        }
+
+        self.diagnostics.push(Diagnostic::new(
+            "constant_condition",
+            format!("comparing `{}` to itself", lhs.to_string().trim()),
+            Label::new(range),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_constant_condition() {
+        test_lint(
+            ConstantConditionLint::new(()).unwrap(),
+            "constant_condition",
+            "constant_condition",
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use super::*;
+use crate::ast_util::{
+    range,
+    scopes::{ScopeManager, Variable},
+};
+use std::{collections::HashMap, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::{Symbol, TokenType},
+    visitors::{Visit, Visitor},
+};
+use id_arena::Id;
+
+pub struct DeadStoreLint;
+
+impl Lint for DeadStoreLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(DeadStoreLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let scope_manager = &ast_context.scope_manager;
+
+        let mut function_ranges_visitor = FunctionRangesVisitor::default();
+        function_ranges_visitor.visit_ast(ast);
+        let function_ranges = function_ranges_visitor.ranges;
+
+        let mut reads = scope_manager
+            .references
+            .iter()
+            .filter(|(_, reference)| reference.read)
+            .filter_map(|(_, reference)| Some((reference.identifier.0, reference.resolved?)))
+            .collect::<Vec<_>>();
+        reads.sort_by_key(|(position, _)| *position);
+
+        let mut visitor = DeadStoreVisitor {
+            scope_manager,
+            function_ranges,
+            reads,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+#[derive(Default)]
+struct FunctionRangesVisitor {
+    ranges: Vec<(usize, usize)>,
+}
+
+impl Visitor for FunctionRangesVisitor {
+    fn visit_function_body(&mut self, body: &ast::FunctionBody) {
+        self.ranges.push(range(body));
+    }
+}
+
+// Finds `break`, `continue`, and `goto`, which could skip over the assignment that seems to overwrite a value
+#[derive(Default)]
+struct JumpVisitor {
+    function_depth: usize,
+    found: bool,
+}
+
+impl Visitor for JumpVisitor {
+    fn visit_function_body(&mut self, _: &ast::FunctionBody) {
+        self.function_depth += 1;
+    }
+
+    fn visit_function_body_end(&mut self, _: &ast::FunctionBody) {
+        self.function_depth -= 1;
+    }
+
+    fn visit_last_stmt(&mut self, last_stmt: &ast::LastStmt) {
+        if self.function_depth == 0 && !matches!(last_stmt, ast::LastStmt::Return(_)) {
+            self.found = true;
+        }
+    }
+
+    #[cfg(any(feature = "lua52", feature = "luajit"))]
+    fn visit_goto(&mut self, _: &ast::lua52::Goto) {
+        if self.function_depth == 0 {
+            self.found = true;
+        }
+    }
+}
+
+struct Store {
+    range: (usize, usize),
+    name: String,
+}
+
+struct DeadStoreVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    function_ranges: Vec<(usize, usize)>,
+    reads: Vec<(usize, Id<Variable>)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl DeadStoreVisitor<'_> {
+    // Closures can read the variable whenever they're called, which isn't tracked
+    fn is_captured(&self, variable_id: Id<Variable>) -> bool {
+        let variable = &self.scope_manager.variables[variable_id];
+
+        let Some(&(definition, _)) = variable.identifiers.first() else {
+            return true;
+        };
+
+        variable.references.iter().any(|reference_id| {
+            let position = self.scope_manager.references[*reference_id].identifier.0;
+
+            self.function_ranges.iter().any(|&(start, end)| {
+                start <= position && position <= end && !(start <= definition && definition <= end)
+            })
+        })
+    }
+
+    fn variable_at(&self, name: &full_moon::tokenizer::TokenReference) -> Option<Id<Variable>> {
+        self.scope_manager
+            .reference_at_byte(range(name).0)?
+            .resolved
+    }
+
+    fn is_tracked(&self, variable_id: Id<Variable>, value: &ast::Expression) -> bool {
+        let is_nil = matches!(
+            value,
+            ast::Expression::Symbol(token)
+                if *token.token_type() == TokenType::Symbol { symbol: Symbol::Nil }
+        );
+
+        !is_nil
+            && !self.scope_manager.variables[variable_id]
+                .name
+                .starts_with('_')
+            && !self.is_captured(variable_id)
+    }
+
+    fn overwrite(
+        &mut self,
+        stores: &mut HashMap<Id<Variable>, Store>,
+        variable_id: Id<Variable>,
+        name: &full_moon::tokenizer::TokenReference,
+    ) {
+        let Some(store) = stores.remove(&variable_id) else {
+            return;
+        };
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "dead_store",
+            format!("the value assigned to `{}` is never read", store.name),
+            Label::new(store.range),
+            Vec::new(),
+            vec![Label::new_with_message(
+                range::<_, usize>(name),
+                "overwritten here".to_owned(),
+            )],
+        ));
+    }
+}
+
+impl Visitor for DeadStoreVisitor<'_> {
+    fn visit_block(&mut self, block: &ast::Block) {
+        // The latest value assigned to each variable in this block, if it hasn't been read yet
+        let mut stores: HashMap<Id<Variable>, Store> = HashMap::new();
+
+        for stmt in block.stmts() {
+            let (start, end) = range(stmt);
+
+            let first_read = self
+                .reads
+                .partition_point(|(position, _)| *position < start);
+            for (_, variable_id) in self.reads[first_read..]
+                .iter()
+                .take_while(|(position, _)| *position < end)
+            {
+                stores.remove(variable_id);
+            }
+
+            let mut jump_visitor = JumpVisitor::default();
+            stmt.visit(&mut jump_visitor);
+            if jump_visitor.found {
+                stores.clear();
+                continue;
+            }
+
+            match stmt {
+                ast::Stmt::Assignment(assignment) => {
+                    let mut expressions = assignment.expressions().iter();
+
+                    for var in assignment.variables() {
+                        let expression = expressions.next();
+
+                        let ast::Var::Name(name) = var else {
+                            continue;
+                        };
+
+                        let Some(variable_id) = self.variable_at(name) else {
+                            continue;
+                        };
+
+                        self.overwrite(&mut stores, variable_id, name);
+
+                        if let Some(expression) = expression {
+                            if self.is_tracked(variable_id, expression) {
+                                stores.insert(
+                                    variable_id,
+                                    Store {
+                                        range: range(expression),
+                                        name: name.token().to_string(),
+                                    },
+                                );
+                            }
+                        }
+                    }
+                }
+
+                ast::Stmt::LocalAssignment(local_assignment) => {
+                    for (name, expression) in local_assignment
+                        .names()
+                        .iter()
+                        .zip(local_assignment.expressions().iter())
+                    {
+                        let Some(variable_id) = self.variable_at(name) else {
+                            continue;
+                        };
+
+                        if self.is_tracked(variable_id, expression) {
+                            stores.insert(
+                                variable_id,
+                                Store {
+                                    range: range(expression),
+                                    name: name.token().to_string(),
+                                },
+                            );
+                        }
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_dead_store() {
+        test_lint(DeadStoreLint::new(()).unwrap(), "dead_store", "dead_store");
+    }
+}
@@ -3,7 +3,7 @@ use std::convert::Infallible;
 use full_moon::{ast, visitors::Visitor};
 use serde::Deserialize;
 
-use crate::ast_util::{name_paths::*, range, scopes::ScopeManager};
+use crate::ast_util::{name_paths::*, range, std_types::StdTypes};
 
 use super::{super::standard_library::*, *};
 
@@ -31,8 +31,7 @@ impl Lint for DeprecatedLint {
     fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
         let mut visitor = DeprecatedVisitor::new(
             &self.config,
-            &ast_context.scope_manager,
-            &context.standard_library,
+            StdTypes::new(&ast_context.scope_manager, &context.standard_library),
         );
 
         visitor.visit_ast(ast);
@@ -44,8 +43,7 @@ impl Lint for DeprecatedLint {
 struct DeprecatedVisitor<'a> {
     allow: Vec<Vec<String>>,
     diagnostics: Vec<Diagnostic>,
-    scope_manager: &'a ScopeManager,
-    standard_library: &'a StandardLibrary,
+    std_types: StdTypes<'a>,
 }
 
 struct Argument {
@@ -54,15 +52,10 @@ struct Argument {
 }
 
 impl<'a> DeprecatedVisitor<'a> {
-    fn new(
-        config: &DeprecatedLintConfig,
-        scope_manager: &'a ScopeManager,
-        standard_library: &'a StandardLibrary,
-    ) -> Self {
+    fn new(config: &DeprecatedLintConfig, std_types: StdTypes<'a>) -> Self {
         Self {
             diagnostics: Vec::new(),
-            scope_manager,
-            standard_library,
+            std_types,
 
             allow: config
                 .allow
@@ -103,13 +96,21 @@ impl<'a> DeprecatedVisitor<'a> {
     ) {
         assert!(!name_path.is_empty());
 
-        if self.allowed(name_path) {
+        // Locals holding structs, like `local part = Instance.new("Part")`, are checked from the struct
+        let Some(root) = self
+            .std_types
+            .name_path_root(node.start_position().unwrap().bytes(), name_path)
+        else {
+            return;
+        };
+
+        if root.struct_name.is_none() && self.allowed(name_path) {
             return;
         }
 
-        for bound in 1..=name_path.len() {
+        for bound in root.length + 1..=name_path.len() {
             profiling::scope!("DeprecatedVisitor::check_name_path check in bound");
-            let deprecated = match self.standard_library.find_global(&name_path[0..bound]) {
+            let deprecated = match self.std_types.find_field(&root, &name_path[0..bound]) {
                 Some(Field {
                     deprecated: Some(deprecated),
                     ..
@@ -144,7 +145,7 @@ impl<'a> DeprecatedVisitor<'a> {
         if let Some(Field {
             field_kind: FieldKind::Function(function),
             ..
-        }) = self.standard_library.find_global(name_path)
+        }) = self.std_types.find_field(&root, name_path)
         {
             for (arg, arg_std) in arguments
                 .iter()
@@ -167,15 +168,6 @@ impl<'a> DeprecatedVisitor<'a> {
 
 impl Visitor for DeprecatedVisitor<'_> {
     fn visit_expression(&mut self, expression: &ast::Expression) {
-        if let Some(reference) = self
-            .scope_manager
-            .reference_at_byte(expression.start_position().unwrap().bytes())
-        {
-            if reference.resolved.is_some() {
-                return;
-            }
-        }
-
         let name_path = match name_path(expression) {
             Some(name_path) => name_path,
             None => return,
@@ -185,15 +177,6 @@ impl Visitor for DeprecatedVisitor<'_> {
     }
 
     fn visit_function_call(&mut self, call: &ast::FunctionCall) {
-        if let Some(reference) = self
-            .scope_manager
-            .reference_at_byte(call.start_position().unwrap().bytes())
-        {
-            if reference.resolved.is_some() {
-                return;
-            }
-        }
-
         let mut keep_going = true;
         let mut suffixes: Vec<&ast::Suffix> = call
             .suffixes()
@@ -289,6 +272,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_local_method_calls() {
+        test_lint(
+            DeprecatedLint::new(DeprecatedLintConfig::default()).unwrap(),
+            "deprecated",
+            "local_method_calls",
+        );
+    }
+
     #[test]
     fn test_specific_allow() {
         test_lint(
@@ -1,4 +1,5 @@
 use super::*;
+use crate::ast_util::constant::Constant;
 use std::convert::Infallible;
 
 use full_moon::{
@@ -46,11 +47,7 @@ struct DivideByZeroVisitor {
 }
 
 fn value_is_zero(value: &ast::Expression) -> bool {
-    if let ast::Expression::Number(token) = value {
-        token.token().to_string() == "0"
-    } else {
-        false
-    }
+    Constant::evaluate(value) == Some(Constant::Number(0.0))
 }
 
 impl Visitor for DivideByZeroVisitor {
@@ -0,0 +1,289 @@
+use super::*;
+use crate::ast_util::{
+    range,
+    scopes::{ReferenceWrite, ScopeManager, Variable},
+};
+use std::{collections::HashMap, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+use id_arena::Id;
+
+pub struct DuplicateDefinitionLint;
+
+impl Lint for DuplicateDefinitionLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(DuplicateDefinitionLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut function_bodies_visitor = FunctionBodiesVisitor::default();
+        function_bodies_visitor.visit_ast(ast);
+
+        let mut visitor = DuplicateDefinitionVisitor {
+            scope_manager: &ast_context.scope_manager,
+            function_bodies: function_bodies_visitor.function_bodies,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum Root {
+    Local(Id<Variable>),
+    Global(String),
+}
+
+// What a statement defines, like `M.foo` in `function M.foo() end`
+#[derive(Debug)]
+struct Definition {
+    root: Root,
+    path: Vec<String>,
+    name: String,
+    range: (usize, usize),
+}
+
+#[derive(Default)]
+struct FunctionBodiesVisitor {
+    function_bodies: Vec<(usize, usize)>,
+}
+
+impl Visitor for FunctionBodiesVisitor {
+    fn visit_function_body(&mut self, body: &ast::FunctionBody) {
+        self.function_bodies.push(range(body));
+    }
+}
+
+struct DuplicateDefinitionVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    function_bodies: Vec<(usize, usize)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn dot_path(var_expression: &ast::VarExpression) -> Option<(&TokenReference, Vec<String>)> {
+    let ast::Prefix::Name(root) = var_expression.prefix() else {
+        return None;
+    };
+
+    let path = var_expression
+        .suffixes()
+        .map(|suffix| match suffix {
+            ast::Suffix::Index(ast::Index::Dot { name, .. }) => Some(name.token().to_string()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some((root, path))
+}
+
+impl DuplicateDefinitionVisitor<'_> {
+    fn root(&self, name: &TokenReference) -> Root {
+        match self
+            .scope_manager
+            .reference_at_byte(range(name).0)
+            .and_then(|reference| reference.resolved)
+        {
+            Some(variable) => Root::Local(variable),
+            None => Root::Global(name.token().to_string()),
+        }
+    }
+
+    fn declared_local(&self, name: &TokenReference) -> Option<Root> {
+        let identifier = range(name);
+
+        self.scope_manager
+            .variables
+            .iter()
+            .find(|(_, variable)| variable.identifiers.contains(&identifier))
+            .map(|(id, _)| Root::Local(id))
+    }
+
+    fn definition(
+        &self,
+        root_token: &TokenReference,
+        root: Root,
+        path: Vec<String>,
+        range: (usize, usize),
+    ) -> Definition {
+        let name = std::iter::once(root_token.token().to_string())
+            .chain(path.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        Definition {
+            root,
+            path,
+            name,
+            range,
+        }
+    }
+
+    fn definitions(&self, stmt: &ast::Stmt) -> Vec<Definition> {
+        match stmt {
+            ast::Stmt::FunctionDeclaration(declaration) => {
+                let function_name = declaration.name();
+                let mut names = function_name.names().iter();
+                let root_token = names.next().unwrap();
+
+                let path = names
+                    .chain(function_name.method_name())
+                    .map(|name| name.token().to_string())
+                    .collect();
+
+                vec![self.definition(
+                    root_token,
+                    self.root(root_token),
+                    path,
+                    range(function_name),
+                )]
+            }
+
+            ast::Stmt::LocalFunction(local_function) => self
+                .declared_local(local_function.name())
+                .map(|root| {
+                    self.definition(
+                        local_function.name(),
+                        root,
+                        Vec::new(),
+                        range(local_function.name()),
+                    )
+                })
+                .into_iter()
+                .collect(),
+
+            ast::Stmt::LocalAssignment(local_assignment) => local_assignment
+                .names()
+                .iter()
+                .zip(local_assignment.expressions())
+                .filter(|(_, expression)| matches!(expression, ast::Expression::Function(_)))
+                .filter_map(|(name, _)| {
+                    let root = self.declared_local(name)?;
+                    Some(self.definition(name, root, Vec::new(), range(name)))
+                })
+                .collect(),
+
+            ast::Stmt::Assignment(assignment) => assignment
+                .variables()
+                .iter()
+                .zip(assignment.expressions())
+                .filter(|(_, expression)| matches!(expression, ast::Expression::Function(_)))
+                .filter_map(|(var, _)| {
+                    let (root_token, path) = match var {
+                        ast::Var::Name(name) => (name, Vec::new()),
+                        ast::Var::Expression(var_expression) => dot_path(var_expression)?,
+                        _ => return None,
+                    };
+
+                    Some(self.definition(root_token, self.root(root_token), path, range(var)))
+                })
+                .collect(),
+
+            _ => Vec::new(),
+        }
+    }
+
+    // Whether the root is touched between the two definitions, like in
+    // `local old = M.foo` before wrapping it with a new `function M.foo()`
+    fn used_between(&self, first: &Definition, second: &Definition) -> bool {
+        let (start, end) = (first.range.1, second.range.0);
+
+        self.scope_manager.references.iter().any(|(_, reference)| {
+            let position = reference.identifier.0;
+
+            if position < start || position >= end {
+                return false;
+            }
+
+            let same_root = match &first.root {
+                Root::Local(variable) => reference.resolved == Some(*variable),
+                Root::Global(name) => reference.resolved.is_none() && reference.name == *name,
+            };
+
+            if !same_root {
+                return false;
+            }
+
+            // Defining other functions, like `function M.bar()`, doesn't touch it
+            if reference.write == Some(ReferenceWrite::Extend) {
+                return false;
+            }
+
+            // Functions defined in between aren't called yet
+            if self.function_bodies.iter().any(|&(body_start, body_end)| {
+                body_start >= start && body_start <= position && position < body_end
+            }) {
+                return false;
+            }
+
+            // `M.bar` doesn't touch `M.foo`
+            let diverges = match (&reference.indexing, first.path.first()) {
+                (Some(indexing), Some(first_name)) => indexing
+                    .first()
+                    .and_then(|index| index.static_name.as_ref())
+                    .is_some_and(|static_name| static_name.token().to_string() != *first_name),
+                _ => false,
+            };
+
+            !diverges
+        })
+    }
+}
+
+impl Visitor for DuplicateDefinitionVisitor<'_> {
+    fn visit_block(&mut self, block: &ast::Block) {
+        let mut defined: HashMap<(&Root, &[String]), &Definition> = HashMap::new();
+
+        let definitions = block
+            .stmts()
+            .flat_map(|stmt| self.definitions(stmt))
+            .collect::<Vec<_>>();
+
+        for definition in &definitions {
+            let key = (&definition.root, definition.path.as_slice());
+
+            if let Some(previous) = defined.insert(key, definition) {
+                if self.used_between(previous, definition) {
+                    continue;
+                }
+
+                self.diagnostics.push(Diagnostic::new_complete(
+                    "duplicate_definition",
+                    format!("`{}` is already defined", definition.name),
+                    Label::new(definition.range),
+                    vec!["only the last definition will be used".to_owned()],
+                    vec![Label::new_with_message(
+                        previous.range,
+                        format!("`{}` previously defined here", previous.name),
+                    )],
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_duplicate_definition() {
+        test_lint(
+            DuplicateDefinitionLint::new(()).unwrap(),
+            "duplicate_definition",
+            "duplicate_definition",
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use super::*;
+use std::convert::Infallible;
+
+use full_moon::{ast::Ast, node::Node};
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct FileLengthConfig {
+    maximum_lines: usize,
+}
+
+impl Default for FileLengthConfig {
+    fn default() -> Self {
+        Self {
+            maximum_lines: 1000,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct FileLengthLint {
+    config: FileLengthConfig,
+}
+
+impl Lint for FileLengthLint {
+    type Config = FileLengthConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(FileLengthLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let eof = ast.eof().token().start_position();
+
+        // A newline at the end of the file doesn't start another line
+        let lines = if eof.character() == 1 && eof.line() > 1 {
+            eof.line() - 1
+        } else {
+            eof.line()
+        };
+
+        if lines <= self.config.maximum_lines {
+            return Vec::new();
+        }
+
+        // Point at the first code past the limit, or the end of the file if there's only comments
+        let past_limit = ast
+            .nodes()
+            .tokens()
+            .find(|token| token.token().start_position().line() > self.config.maximum_lines)
+            .unwrap_or_else(|| ast.eof());
+
+        vec![Diagnostic::new(
+            "file_length",
+            format!(
+                "file is too long ({lines} lines > {})",
+                self.config.maximum_lines
+            ),
+            Label::new_with_message(
+                (
+                    past_limit.token().start_position().bytes(),
+                    past_limit.token().end_position().bytes(),
+                ),
+                format!("goes past {} lines here", self.config.maximum_lines),
+            ),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_file_length() {
+        test_lint(
+            FileLengthLint::new(FileLengthConfig { maximum_lines: 5 }).unwrap(),
+            "file_length",
+            "file_length",
+        );
+    }
+
+    #[test]
+    fn test_short_file() {
+        test_lint(
+            FileLengthLint::new(FileLengthConfig { maximum_lines: 5 }).unwrap(),
+            "file_length",
+            "short_file",
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use super::*;
+use crate::ast_util::loop_tracker::LoopKind;
+use std::convert::Infallible;
+
+use full_moon::ast::Ast;
+
+pub struct ForVariableAssignmentLint;
+
+impl Lint for ForVariableAssignmentLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(ForVariableAssignmentLint)
+    }
+
+    fn pass(
+        &self,
+        _: &Ast,
+        _: &Context,
+        AstContext {
+            loop_tracker,
+            scope_manager,
+        }: &AstContext,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for numeric_for in loop_tracker
+            .loops()
+            .iter()
+            .filter(|tracked| tracked.kind == LoopKind::NumericFor)
+        {
+            let Some(&declaration) = numeric_for.variables.first() else {
+                continue;
+            };
+
+            let Some((_, variable)) = scope_manager
+                .variables
+                .iter()
+                .find(|(_, variable)| variable.identifiers.contains(&declaration))
+            else {
+                continue;
+            };
+
+            for reference_id in &variable.references {
+                let reference = &scope_manager.references[*reference_id];
+
+                // `i.x = 1` writes to a field of `i`, not `i` itself
+                if reference.identifier == declaration
+                    || reference.write.is_none()
+                    || reference.indexing.is_some()
+                {
+                    continue;
+                }
+
+                diagnostics.push(Diagnostic::new_complete(
+                    "for_variable_assignment",
+                    format!(
+                        "assigning to `{}` doesn't change how many times the loop runs",
+                        variable.name
+                    ),
+                    Label::new(reference.identifier),
+                    vec![format!(
+                        "the next iteration still starts from the next value of `{}`, try a `while` loop instead",
+                        variable.name
+                    )],
+                    vec![Label::new_with_message(
+                        declaration,
+                        "loop variable declared here".to_owned(),
+                    )],
+                ));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_for_variable_assignment() {
+        test_lint(
+            ForVariableAssignmentLint::new(()).unwrap(),
+            "for_variable_assignment",
+            "for_variable_assignment",
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use super::*;
+use crate::ast_util::functions::visit_functions;
+use std::convert::Infallible;
+
+use full_moon::{ast::Ast, node::Node};
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct FunctionLengthConfig {
+    maximum_lines: usize,
+}
+
+impl Default for FunctionLengthConfig {
+    fn default() -> Self {
+        Self { maximum_lines: 100 }
+    }
+}
+
+#[derive(Default)]
+pub struct FunctionLengthLint {
+    config: FunctionLengthConfig,
+}
+
+impl Lint for FunctionLengthLint {
+    type Config = FunctionLengthConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(FunctionLengthLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        visit_functions(ast, |signature, body| {
+            let (Some(start), Some(end)) = (body.start_position(), body.end_position()) else {
+                return;
+            };
+
+            let lines = end.line() - start.line() + 1;
+
+            if lines > self.config.maximum_lines {
+                diagnostics.push(Diagnostic::new(
+                    "function_length",
+                    format!(
+                        "function is too long ({lines} lines > {})",
+                        self.config.maximum_lines
+                    ),
+                    Label::new(signature),
+                ));
+            }
+        });
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_function_length() {
+        test_lint(
+            FunctionLengthLint::new(FunctionLengthConfig { maximum_lines: 3 }).unwrap(),
+            "function_length",
+            "function_length",
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use super::*;
+use crate::{
+    ast_util::{loop_tracker::LoopTracker, range, scopes::ScopeManager},
+    standard_library::{LuaVersion, StandardLibrary},
+};
+use std::{collections::HashSet, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct GlobalLookupInLoopLint;
+
+impl Lint for GlobalLookupInLoopLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const LINT_TYPE: LintType = LintType::Performance;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(GlobalLookupInLoopLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        // Luau resolves builtins like `math.floor` ahead of time, so localizing them doesn't help
+        if context
+            .standard_library
+            .lua_versions
+            .contains(&LuaVersion::Luau)
+        {
+            return Vec::new();
+        }
+
+        let mut visitor = GlobalLookupInLoopVisitor {
+            loop_tracker: &ast_context.loop_tracker,
+            scope_manager: &ast_context.scope_manager,
+            standard_library: &context.standard_library,
+            reported: HashSet::new(),
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct GlobalLookupInLoopVisitor<'a> {
+    loop_tracker: &'a LoopTracker,
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+    // Each lookup is only reported once per loop, as `(loop block, name)`
+    reported: HashSet<((usize, usize), String)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl GlobalLookupInLoopVisitor<'_> {
+    fn check<'b>(&mut self, prefix: &ast::Prefix, suffixes: impl Iterator<Item = &'b ast::Suffix>) {
+        let ast::Prefix::Name(root) = prefix else {
+            return;
+        };
+
+        let mut path = vec![root.token().to_string()];
+        let mut end = range(root).1;
+
+        for suffix in suffixes {
+            let ast::Suffix::Index(ast::Index::Dot { name, .. }) = suffix else {
+                break;
+            };
+
+            path.push(name.token().to_string());
+            end = range(name).1;
+        }
+
+        if path.len() < 2 || self.standard_library.find_global(&path).is_none() {
+            return;
+        }
+
+        let start = range(root).0;
+
+        if self
+            .scope_manager
+            .reference_at_byte(start)
+            .is_some_and(|reference| reference.resolved.is_some())
+        {
+            return;
+        }
+
+        let Some(tracked_loop) = self.loop_tracker.innermost_loop_at_byte(start) else {
+            return;
+        };
+
+        let name = path.join(".");
+
+        if !self.reported.insert((tracked_loop.block, name.clone())) {
+            return;
+        }
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "global_lookup_in_loop",
+            format!("`{name}` is looked up on every iteration of the loop"),
+            Label::new((start, end)),
+            vec![format!(
+                "try: `local {} = {name}` outside of the loop",
+                path.last().unwrap()
+            )],
+            Vec::new(),
+        ));
+    }
+}
+
+impl Visitor for GlobalLookupInLoopVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        self.check(call.prefix(), call.suffixes());
+    }
+
+    fn visit_var_expression(&mut self, var_expression: &ast::VarExpression) {
+        self.check(var_expression.prefix(), var_expression.suffixes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::test_util::{test_lint, test_lint_config, TestUtilConfig},
+        *,
+    };
+
+    #[test]
+    fn test_global_lookup_in_loop() {
+        test_lint(
+            GlobalLookupInLoopLint::new(()).unwrap(),
+            "global_lookup_in_loop",
+            "global_lookup_in_loop",
+        );
+    }
+
+    #[test]
+    fn test_luau() {
+        test_lint_config(
+            GlobalLookupInLoopLint::new(()).unwrap(),
+            "global_lookup_in_loop",
+            "luau",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("luau").unwrap(),
+                ..Default::default()
+            },
+        );
+    }
+}
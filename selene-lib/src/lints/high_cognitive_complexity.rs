@@ -0,0 +1,224 @@
+use super::*;
+use crate::ast_util::{functions::visit_functions, range};
+use std::{collections::HashSet, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::{Visit, Visitor},
+};
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct HighCognitiveComplexityConfig {
+    maximum_complexity: u16,
+}
+
+impl Default for HighCognitiveComplexityConfig {
+    fn default() -> Self {
+        Self {
+            maximum_complexity: 25,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct HighCognitiveComplexityLint {
+    config: HighCognitiveComplexityConfig,
+}
+
+impl Lint for HighCognitiveComplexityLint {
+    type Config = HighCognitiveComplexityConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(HighCognitiveComplexityLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        visit_functions(ast, |signature, body| {
+            let mut boolean_operators = BooleanOperatorVisitor::default();
+            body.block().visit(&mut boolean_operators);
+
+            let complexity = count_block_complexity(body.block(), 0) + boolean_operators.complexity;
+
+            if complexity > self.config.maximum_complexity {
+                diagnostics.push(Diagnostic::new(
+                    "high_cognitive_complexity",
+                    format!(
+                        "cognitive complexity is too high ({complexity} > {})",
+                        self.config.maximum_complexity
+                    ),
+                    Label::new(signature),
+                ));
+            }
+        });
+
+        diagnostics
+    }
+}
+
+// Branches and loops cost more the deeper they're nested, since more has to be kept in mind to follow them
+fn count_block_complexity(block: &ast::Block, nesting: u16) -> u16 {
+    let mut complexity = 0;
+
+    for stmt in block.stmts() {
+        #[cfg_attr(
+            feature = "force_exhaustive_checks",
+            deny(non_exhaustive_omitted_patterns)
+        )]
+        match stmt {
+            ast::Stmt::Do(do_) => {
+                complexity += count_block_complexity(do_.block(), nesting);
+            }
+
+            ast::Stmt::If(if_block) => {
+                complexity += 1 + nesting;
+                complexity += count_block_complexity(if_block.block(), nesting + 1);
+
+                for else_if in if_block.else_if().into_iter().flatten() {
+                    complexity += 1 + count_block_complexity(else_if.block(), nesting + 1);
+                }
+
+                if let Some(else_block) = if_block.else_block() {
+                    complexity += 1 + count_block_complexity(else_block, nesting + 1);
+                }
+            }
+
+            ast::Stmt::GenericFor(generic_for) => {
+                complexity +=
+                    1 + nesting + count_block_complexity(generic_for.block(), nesting + 1);
+            }
+
+            ast::Stmt::NumericFor(numeric_for) => {
+                complexity +=
+                    1 + nesting + count_block_complexity(numeric_for.block(), nesting + 1);
+            }
+
+            ast::Stmt::Repeat(repeat) => {
+                complexity += 1 + nesting + count_block_complexity(repeat.block(), nesting + 1);
+            }
+
+            ast::Stmt::While(while_loop) => {
+                complexity += 1 + nesting + count_block_complexity(while_loop.block(), nesting + 1);
+            }
+
+            #[cfg(any(feature = "lua52", feature = "luajit"))]
+            ast::Stmt::Goto(_) => {
+                // Jumping breaks the flow of reading top to bottom
+                complexity += 1;
+            }
+
+            // Functions are measured on their own
+            ast::Stmt::FunctionDeclaration(_) | ast::Stmt::LocalFunction(_) => {}
+
+            ast::Stmt::Assignment(_)
+            | ast::Stmt::FunctionCall(_)
+            | ast::Stmt::LocalAssignment(_) => {}
+
+            #[cfg(any(feature = "lua52", feature = "luajit"))]
+            ast::Stmt::Label(_) => {}
+
+            #[cfg(feature = "roblox")]
+            ast::Stmt::CompoundAssignment(_)
+            | ast::Stmt::ExportedTypeDeclaration(_)
+            | ast::Stmt::ExportedTypeFunction(_)
+            | ast::Stmt::TypeDeclaration(_)
+            | ast::Stmt::TypeFunction(_) => {}
+
+            _ => {}
+        }
+    }
+
+    complexity
+}
+
+// Each run of the same boolean operator costs 1, so `a and b and c` is 1, but `a and b or c` is 2.
+// Functions inside are measured on their own.
+#[derive(Default)]
+struct BooleanOperatorVisitor {
+    complexity: u16,
+    function_depth: usize,
+    // Operators that continue the run of their parent, like the `b and c` in `a and b and c`
+    continuations: HashSet<(usize, usize)>,
+}
+
+fn boolean_operator(expression: &ast::Expression) -> Option<bool> {
+    match expression {
+        ast::Expression::BinaryOperator {
+            binop: ast::BinOp::And(_),
+            ..
+        } => Some(true),
+        ast::Expression::BinaryOperator {
+            binop: ast::BinOp::Or(_),
+            ..
+        } => Some(false),
+        _ => None,
+    }
+}
+
+impl Visitor for BooleanOperatorVisitor {
+    fn visit_function_body(&mut self, _: &ast::FunctionBody) {
+        self.function_depth += 1;
+    }
+
+    fn visit_function_body_end(&mut self, _: &ast::FunctionBody) {
+        self.function_depth -= 1;
+    }
+
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        if self.function_depth > 0 {
+            return;
+        }
+
+        #[cfg(feature = "roblox")]
+        if let ast::Expression::IfExpression(if_expression) = expression {
+            self.complexity += 1 + if_expression
+                .else_if_expressions()
+                .into_iter()
+                .flatten()
+                .count() as u16;
+            return;
+        }
+
+        let Some(operator) = boolean_operator(expression) else {
+            return;
+        };
+
+        let ast::Expression::BinaryOperator { lhs, rhs, .. } = expression else {
+            unreachable!();
+        };
+
+        for side in [lhs, rhs] {
+            if boolean_operator(side) == Some(operator) {
+                self.continuations.insert(range(side.as_ref()));
+            }
+        }
+
+        if !self.continuations.contains(&range(expression)) {
+            self.complexity += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_high_cognitive_complexity() {
+        test_lint(
+            HighCognitiveComplexityLint::new(HighCognitiveComplexityConfig {
+                maximum_complexity: 3,
+            })
+            .unwrap(),
+            "high_cognitive_complexity",
+            "high_cognitive_complexity",
+        );
+    }
+}
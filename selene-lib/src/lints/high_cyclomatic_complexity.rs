@@ -1,11 +1,8 @@
 use super::*;
-use crate::ast_util::range;
+use crate::ast_util::functions::visit_functions;
 use std::convert::Infallible;
 
-use full_moon::{
-    ast::{self, Ast, TableConstructor},
-    visitors::Visitor,
-};
+use full_moon::ast::{self, Ast, TableConstructor};
 
 use serde::Deserialize;
 
@@ -40,33 +37,25 @@ impl Lint for HighCyclomaticComplexityLint {
     }
 
     fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
-        let mut visitor = HighCyclomaticComplexityVisitor {
-            positions: Vec::new(),
-            config: self.config,
-        };
-
-        visitor.visit_ast(ast);
-
-        visitor
-            .positions
-            .into_iter()
-            .map(|(position, complexity)| {
-                Diagnostic::new(
+        let mut diagnostics = Vec::new();
+
+        visit_functions(ast, |signature, body| {
+            let complexity = count_block_complexity(body.block(), 1);
+
+            if complexity > self.config.maximum_complexity {
+                diagnostics.push(Diagnostic::new(
                     "high_cyclomatic_complexity",
                     format!(
                         "cyclomatic complexity is too high ({complexity} > {})",
                         self.config.maximum_complexity
                     ),
-                    Label::new(position),
-                )
-            })
-            .collect()
-    }
-}
+                    Label::new(signature),
+                ));
+            }
+        });
 
-struct HighCyclomaticComplexityVisitor {
-    positions: Vec<((u32, u32), u16)>,
-    config: HighCyclomaticComplexityConfig,
+        diagnostics
+    }
 }
 
 fn count_table_complexity(table: &TableConstructor, starting_complexity: u16) -> u16 {
@@ -182,7 +171,7 @@ fn count_expression_complexity(expression: &ast::Expression, starting_complexity
             count_expression_complexity(expression, complexity)
         }
 
-        // visit_expression already tracks this
+        // visit_functions already tracks this
         ast::Expression::Function(_) => complexity,
 
         ast::Expression::FunctionCall(call) => {
@@ -278,7 +267,7 @@ fn count_block_complexity(block: &ast::Block, starting_complexity: u16) -> u16 {
                 }
             }
 
-            // visit_function_declaration already tracks this
+            // visit_functions already tracks this
             ast::Stmt::FunctionDeclaration(_) => {}
 
             ast::Stmt::GenericFor(generic_for) => {
@@ -309,7 +298,7 @@ fn count_block_complexity(block: &ast::Block, starting_complexity: u16) -> u16 {
                 }
             }
 
-            // visit_local_function tracks this
+            // visit_functions already tracks this
             ast::Stmt::LocalFunction(_) => {}
 
             ast::Stmt::NumericFor(numeric_for) => {
@@ -382,50 +371,6 @@ fn count_block_complexity(block: &ast::Block, starting_complexity: u16) -> u16 {
     complexity
 }
 
-impl Visitor for HighCyclomaticComplexityVisitor {
-    fn visit_local_function(&mut self, local_function: &ast::LocalFunction) {
-        let complexity = count_block_complexity(local_function.body().block(), 1);
-        if complexity > self.config.maximum_complexity {
-            self.positions.push((
-                (
-                    range(local_function.function_token()).0,
-                    range(local_function.body().parameters_parentheses()).1,
-                ),
-                complexity,
-            ));
-        }
-    }
-
-    fn visit_function_declaration(&mut self, function_declaration: &ast::FunctionDeclaration) {
-        let complexity = count_block_complexity(function_declaration.body().block(), 1);
-        if complexity > self.config.maximum_complexity {
-            self.positions.push((
-                (
-                    range(function_declaration.function_token()).0,
-                    range(function_declaration.body().parameters_parentheses()).1,
-                ),
-                complexity,
-            ));
-        }
-    }
-
-    fn visit_expression(&mut self, expression: &ast::Expression) {
-        if let ast::Expression::Function(function_box) = expression {
-            let function_body = function_box.body();
-            let complexity = count_block_complexity(function_body.block(), 1);
-            if complexity > self.config.maximum_complexity {
-                self.positions.push((
-                    (
-                        expression.start_position().unwrap().bytes() as u32,
-                        range(function_body.parameters_parentheses()).1,
-                    ),
-                    complexity,
-                ));
-            }
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::{super::test_util::test_lint, *};
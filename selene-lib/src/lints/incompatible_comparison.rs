@@ -0,0 +1,404 @@
+use super::*;
+use crate::{
+    ast_util::{
+        is_type_function,
+        loop_tracker::LoopKind,
+        range,
+        scopes::{AssignedValue, LiteralType, ScopeManager, Variable},
+        strip_parentheses,
+    },
+    standard_library::LuaVersion,
+};
+use std::{collections::BTreeSet, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::{TokenReference, TokenType},
+    visitors::Visitor,
+};
+use id_arena::Id;
+
+// Everything `type` can return
+const TYPE_NAMES: &[&str] = &[
+    "boolean", "function", "nil", "number", "string", "table", "thread", "userdata",
+];
+
+const LUAU_TYPE_NAMES: &[&str] = &["buffer", "vector"];
+
+// The data types `typeof` can return on Roblox, on top of what `type` can.
+// Globals that start with an uppercase letter, like `Vector3`, are accepted as well.
+const ROBLOX_TYPE_NAMES: &[&str] = &[
+    "Axes",
+    "BrickColor",
+    "CFrame",
+    "CatalogSearchParams",
+    "Color3",
+    "ColorSequence",
+    "ColorSequenceKeypoint",
+    "Content",
+    "DateTime",
+    "DockWidgetPluginGuiInfo",
+    "Enum",
+    "EnumItem",
+    "Enums",
+    "Faces",
+    "FloatCurveKey",
+    "Font",
+    "Instance",
+    "NumberRange",
+    "NumberSequence",
+    "NumberSequenceKeypoint",
+    "OverlapParams",
+    "Path2DControlPoint",
+    "PathWaypoint",
+    "PhysicalProperties",
+    "RBXScriptConnection",
+    "RBXScriptSignal",
+    "Random",
+    "Ray",
+    "RaycastParams",
+    "RaycastResult",
+    "Rect",
+    "Region3",
+    "Region3int16",
+    "RotationCurveKey",
+    "SecurityCapabilities",
+    "SharedTable",
+    "TweenInfo",
+    "UDim",
+    "UDim2",
+    "ValueCurveKey",
+    "Vector2",
+    "Vector2int16",
+    "Vector3",
+    "Vector3int16",
+];
+
+pub struct IncompatibleComparisonLint;
+
+impl Lint for IncompatibleComparisonLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(IncompatibleComparisonLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let roblox = context.is_roblox();
+
+        let mut type_names = TYPE_NAMES.iter().copied().collect::<BTreeSet<_>>();
+
+        if context
+            .standard_library
+            .lua_versions
+            .contains(&LuaVersion::Luau)
+        {
+            type_names.extend(LUAU_TYPE_NAMES);
+        }
+
+        let mut typeof_names = type_names.clone();
+        if roblox {
+            typeof_names.extend(ROBLOX_TYPE_NAMES);
+            typeof_names.extend(
+                context
+                    .standard_library
+                    .globals
+                    .keys()
+                    .map(|name| name.split('.').next().unwrap_or(name))
+                    .filter(|name| name.starts_with(|first: char| first.is_ascii_uppercase())),
+            );
+        }
+
+        let numeric_for_variables = ast_context
+            .loop_tracker
+            .loops()
+            .iter()
+            .filter(|tracked| tracked.kind == LoopKind::NumericFor)
+            .flat_map(|tracked| tracked.variables.iter().copied())
+            .collect::<Vec<_>>();
+
+        let mut visitor = IncompatibleComparisonVisitor {
+            scope_manager: &ast_context.scope_manager,
+            roblox,
+            type_names,
+            typeof_names,
+            numeric_for_variables,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct IncompatibleComparisonVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    roblox: bool,
+    type_names: BTreeSet<&'a str>,
+    typeof_names: BTreeSet<&'a str>,
+    numeric_for_variables: Vec<(usize, usize)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn string_literal(expression: &ast::Expression) -> Option<(&TokenReference, String)> {
+    let ast::Expression::String(token) = strip_parentheses(expression) else {
+        return None;
+    };
+
+    let TokenType::StringLiteral { literal, .. } = token.token_type() else {
+        return None;
+    };
+
+    Some((token, literal.to_string()))
+}
+
+// https://en.wikipedia.org/wiki/Levenshtein_distance
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+impl IncompatibleComparisonVisitor<'_> {
+    fn resolve(&self, name: &TokenReference) -> Option<Id<Variable>> {
+        self.scope_manager
+            .reference_at_byte(range(name).0)?
+            .resolved
+    }
+
+    fn is_global_type_function(&self, name: &TokenReference) -> bool {
+        is_type_function(&name.token().to_string(), self.roblox) && self.resolve(name).is_none()
+    }
+
+    // The type function an expression is the result of, either directly like `type(x)`,
+    // or through a local like `local kind = type(x)`
+    fn type_function(&self, expression: &ast::Expression) -> Option<String> {
+        match strip_parentheses(expression) {
+            ast::Expression::FunctionCall(call) => {
+                let ast::Prefix::Name(name) = call.prefix() else {
+                    return None;
+                };
+
+                if call.suffixes().count() != 1 || !self.is_global_type_function(name) {
+                    return None;
+                }
+
+                Some(name.token().to_string())
+            }
+
+            ast::Expression::Var(ast::Var::Name(name)) => {
+                let variable = &self.scope_manager.variables[self.resolve(name)?];
+
+                if self.is_reassigned(variable) {
+                    return None;
+                }
+
+                let Some(AssignedValue::FunctionCall {
+                    call_name_path,
+                    initial_reference,
+                    return_index: 0,
+                    ..
+                }) = &variable.value
+                else {
+                    return None;
+                };
+
+                let [function_name] = call_name_path.as_slice() else {
+                    return None;
+                };
+
+                (is_type_function(function_name, self.roblox)
+                    && self.scope_manager.references[*initial_reference]
+                        .resolved
+                        .is_none())
+                .then(|| function_name.to_owned())
+            }
+
+            _ => None,
+        }
+    }
+
+    fn is_reassigned(&self, variable: &Variable) -> bool {
+        variable.references.iter().any(|reference_id| {
+            let reference = &self.scope_manager.references[*reference_id];
+            reference.write.is_some() && !variable.identifiers.contains(&reference.identifier)
+        })
+    }
+
+    fn is_known_number(&self, expression: &ast::Expression) -> bool {
+        match strip_parentheses(expression) {
+            ast::Expression::Number(_) => true,
+
+            ast::Expression::UnaryOperator {
+                unop: ast::UnOp::Minus(_) | ast::UnOp::Hash(_),
+                ..
+            } => true,
+
+            ast::Expression::BinaryOperator { binop, .. } => matches!(
+                binop,
+                ast::BinOp::Plus(_)
+                    | ast::BinOp::Minus(_)
+                    | ast::BinOp::Star(_)
+                    | ast::BinOp::Slash(_)
+                    | ast::BinOp::Percent(_)
+                    | ast::BinOp::Caret(_)
+            ),
+
+            ast::Expression::Var(ast::Var::Name(name)) => {
+                let Some(variable_id) = self.resolve(name) else {
+                    return false;
+                };
+
+                let variable = &self.scope_manager.variables[variable_id];
+
+                if variable
+                    .identifiers
+                    .iter()
+                    .any(|identifier| self.numeric_for_variables.contains(identifier))
+                {
+                    return !self.is_reassigned(variable);
+                }
+
+                variable.value == Some(AssignedValue::Literal(LiteralType::Number))
+                    && !self.is_reassigned(variable)
+            }
+
+            _ => false,
+        }
+    }
+
+    fn check_type_name(&mut self, function_name: &str, string: &TokenReference, name: &str) {
+        let valid_names = if function_name == "typeof" {
+            &self.typeof_names
+        } else {
+            &self.type_names
+        };
+
+        if valid_names.contains(name) {
+            return;
+        }
+
+        let mut notes = Vec::new();
+
+        if let Some(suggestion) = valid_names
+            .iter()
+            .map(|valid_name| {
+                (
+                    edit_distance(&name.to_lowercase(), &valid_name.to_lowercase()),
+                    valid_name,
+                )
+            })
+            .filter(|(distance, _)| *distance <= 2)
+            .min()
+            .map(|(_, valid_name)| valid_name)
+        {
+            notes.push(format!("did you mean `{suggestion}`?"));
+        } else if function_name == "type" && self.typeof_names.contains(name) {
+            notes.push(format!(
+                "`type` returns `userdata` for `{name}`, try `typeof` instead"
+            ));
+        }
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "incompatible_comparison",
+            format!("`{function_name}` never returns \"{name}\""),
+            Label::new(range::<_, usize>(string)),
+            notes,
+            Vec::new(),
+        ));
+    }
+}
+
+impl Visitor for IncompatibleComparisonVisitor<'_> {
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        let ast::Expression::BinaryOperator { lhs, binop, rhs } = expression else {
+            return;
+        };
+
+        let equal = match binop {
+            ast::BinOp::TwoEqual(_) => true,
+            ast::BinOp::TildeEqual(_) => false,
+            _ => return,
+        };
+
+        for (side, other_side) in [(lhs, rhs), (rhs, lhs)] {
+            let Some((string, literal)) = string_literal(other_side) else {
+                continue;
+            };
+
+            if let Some(function_name) = self.type_function(side) {
+                self.check_type_name(&function_name, string, &literal);
+                return;
+            }
+
+            // Comparing two literals is left to `constant_condition`
+            if matches!(strip_parentheses(side), ast::Expression::Number(_)) {
+                return;
+            }
+
+            if self.is_known_number(side) {
+                let side_text = side.to_string();
+                let side_text = side_text.trim();
+
+                let note = match literal.parse::<f64>() {
+                    Ok(_) => format!(
+                        "try: `{side_text} {} {literal}` instead",
+                        if equal { "==" } else { "~=" }
+                    ),
+                    Err(_) => format!("`{side_text}` is always a number"),
+                };
+
+                self.diagnostics.push(Diagnostic::new_complete(
+                    "incompatible_comparison",
+                    format!("comparing a number to a string is always {}", !equal),
+                    Label::new(range::<_, usize>(expression)),
+                    vec![note],
+                    Vec::new(),
+                ));
+
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_incompatible_comparison() {
+        test_lint(
+            IncompatibleComparisonLint::new(()).unwrap(),
+            "incompatible_comparison",
+            "incompatible_comparison",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "roblox")]
+    fn test_roblox() {
+        test_lint(
+            IncompatibleComparisonLint::new(()).unwrap(),
+            "incompatible_comparison",
+            "roblox",
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use super::*;
+use crate::{
+    ast_util::{name_paths::*, range, scopes::ScopeManager},
+    possible_std::possible_standard_library_notes,
+    standard_library::{FieldVersions, LuaVersion, StandardLibrary},
+};
+use std::{collections::HashMap, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+use once_cell::sync::OnceCell;
+
+pub struct IncompatibleLuaVersionLint;
+
+impl Lint for IncompatibleLuaVersionLint {
+    type Config = ();
+    type Error = Infallible;
+
+    // Reported instead of `undefined_variable` and `incorrect_standard_library_use` for fields
+    // missing from the standard library, so is just as severe
+    const SEVERITY: Severity = Severity::Error;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(IncompatibleLuaVersionLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let standard_library = &context.standard_library;
+
+        // Luau and unknown versions aren't part of the 5.x line, so they're left to their own libraries
+        let mut targets = standard_library
+            .lua_versions
+            .iter()
+            .filter(|version| release_order(version).is_some())
+            .collect::<Vec<_>>();
+
+        if standard_library.lua_versions.is_empty() {
+            targets.push(&LuaVersion::Lua51);
+        }
+
+        if targets.is_empty() {
+            return Vec::new();
+        }
+
+        let mut visitor = IncompatibleLuaVersionVisitor {
+            scope_manager: &ast_context.scope_manager,
+            standard_library,
+            user_set_standard_library: &context.user_set_standard_library,
+            targets,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+// The 5.x versions in release order. LuaJIT is treated as 5.1, which its standard library follows.
+fn release_order(version: &LuaVersion) -> Option<u8> {
+    match version {
+        LuaVersion::Lua51 | LuaVersion::LuaJIT => Some(1),
+        LuaVersion::Lua52 => Some(2),
+        LuaVersion::Lua53 => Some(3),
+        LuaVersion::Lua54 => Some(4),
+        LuaVersion::Luau | LuaVersion::Unknown(_) => None,
+    }
+}
+
+fn display_version(version: &LuaVersion) -> &str {
+    match version {
+        LuaVersion::Lua51 => "Lua 5.1",
+        LuaVersion::Lua52 => "Lua 5.2",
+        LuaVersion::Lua53 => "Lua 5.3",
+        LuaVersion::Lua54 => "Lua 5.4",
+        LuaVersion::Luau => "Luau",
+        LuaVersion::LuaJIT => "LuaJIT",
+        LuaVersion::Unknown(version) => version,
+    }
+}
+
+// Fields only some versions have are missing from the other versions' libraries entirely,
+// so their annotations are collected from every built-in library.
+fn builtin_field_versions() -> &'static HashMap<String, FieldVersions> {
+    static BUILTIN_FIELD_VERSIONS: OnceCell<HashMap<String, FieldVersions>> = OnceCell::new();
+
+    BUILTIN_FIELD_VERSIONS.get_or_init(|| {
+        let standard_libraries = StandardLibrary::all_default_standard_libraries();
+
+        let mut names = standard_libraries.keys().collect::<Vec<_>>();
+        names.sort();
+
+        let mut field_versions = HashMap::new();
+
+        for name in names {
+            for (field_name, field) in &standard_libraries[name].globals {
+                if let Some(versions) = &field.versions {
+                    field_versions
+                        .entry(field_name.to_owned())
+                        .or_insert_with(|| versions.clone());
+                }
+            }
+        }
+
+        field_versions
+    })
+}
+
+struct IncompatibleLuaVersionVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+    user_set_standard_library: &'a Option<Vec<String>>,
+    targets: Vec<&'a LuaVersion>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl IncompatibleLuaVersionVisitor<'_> {
+    fn field_versions(&self, name_path: &[String]) -> Option<&FieldVersions> {
+        match self.standard_library.find_global(name_path) {
+            Some(field) => field.versions.as_ref(),
+            None => builtin_field_versions().get(&name_path.join(".")),
+        }
+    }
+
+    // `name_ends` is where each name in the path ends, so only the name with the problem is underlined
+    fn check_name_path(&mut self, start: usize, name_ends: &[usize], name_path: &[String]) {
+        if self
+            .scope_manager
+            .reference_at_byte(start)
+            .is_some_and(|reference| reference.resolved.is_some())
+        {
+            return;
+        }
+
+        for bound in 1..=name_path.len() {
+            let name_path = &name_path[0..bound];
+
+            let Some(versions) = self.field_versions(name_path) else {
+                continue;
+            };
+
+            let problem = self.targets.iter().find_map(|target| {
+                let target_order = release_order(target)?;
+
+                if let Some(added) = &versions.added {
+                    if release_order(added).is_some_and(|added| target_order < added) {
+                        return Some((
+                            format!(
+                                "`{}` was added in {}, but this project supports {}",
+                                name_path.join("."),
+                                display_version(added),
+                                display_version(target),
+                            ),
+                            &versions.added_replacement,
+                        ));
+                    }
+                }
+
+                if let Some(removed) = &versions.removed {
+                    if release_order(removed).is_some_and(|removed| target_order >= removed) {
+                        return Some((
+                            format!(
+                                "`{}` was removed in {}",
+                                name_path.join("."),
+                                display_version(removed),
+                            ),
+                            &versions.removed_replacement,
+                        ));
+                    }
+                }
+
+                None
+            });
+
+            let Some((message, replacement)) = problem else {
+                continue;
+            };
+
+            let mut notes = replacement
+                .iter()
+                .map(|replacement| format!("try {replacement} instead"))
+                .collect::<Vec<_>>();
+
+            // The same notes `undefined_variable` and `incorrect_standard_library_use` would've given
+            if self.standard_library.find_global(name_path).is_none() {
+                notes.extend(possible_standard_library_notes(
+                    name_path,
+                    self.user_set_standard_library,
+                ));
+            }
+
+            self.diagnostics.push(Diagnostic::new_complete(
+                "incompatible_lua_version",
+                message,
+                Label::new((start, name_ends[bound - 1])),
+                notes,
+                Vec::new(),
+            ));
+
+            return;
+        }
+    }
+}
+
+// Where each name of `name_path_from_prefix_suffix` ends
+fn name_ends<'a>(
+    prefix: &ast::Prefix,
+    suffixes: impl Iterator<Item = &'a ast::Suffix>,
+) -> Vec<usize> {
+    let mut ends = vec![range(prefix).1];
+    let mut keep_going = true;
+
+    for suffix in suffixes.take_while(|suffix| take_while_keep_going(suffix, &mut keep_going)) {
+        match suffix {
+            ast::Suffix::Call(ast::Call::MethodCall(method_call)) => {
+                ends.push(range(method_call.name()).1);
+            }
+
+            ast::Suffix::Index(_) => ends.push(range(suffix).1),
+
+            _ => {}
+        }
+    }
+
+    ends
+}
+
+impl Visitor for IncompatibleLuaVersionVisitor<'_> {
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        let Some(name_path) = name_path(expression) else {
+            return;
+        };
+
+        let name_ends = match expression {
+            ast::Expression::Var(ast::Var::Expression(var_expression)) => {
+                name_ends(var_expression.prefix(), var_expression.suffixes())
+            }
+
+            _ => vec![range(expression).1],
+        };
+
+        self.check_name_path(range(expression).0, &name_ends, &name_path);
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let Some(name_path) = name_path_from_prefix_suffix(call.prefix(), call.suffixes()) else {
+            return;
+        };
+
+        let name_ends = name_ends(call.prefix(), call.suffixes());
+
+        self.check_name_path(range(call.prefix()).0, &name_ends, &name_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::test_util::{test_lint_config_with_output, TestUtilConfig},
+        *,
+    };
+
+    // The notes list standard libraries with the field, which only includes roblox with its feature
+    fn output() -> &'static str {
+        if cfg!(feature = "roblox") {
+            "stderr"
+        } else {
+            "noroblox.stderr"
+        }
+    }
+
+    #[test]
+    fn test_lua51() {
+        test_lint_config_with_output(
+            IncompatibleLuaVersionLint::new(()).unwrap(),
+            "incompatible_lua_version",
+            "lua51",
+            TestUtilConfig::default(),
+            output(),
+        );
+    }
+
+    #[test]
+    fn test_lua53() {
+        test_lint_config_with_output(
+            IncompatibleLuaVersionLint::new(()).unwrap(),
+            "incompatible_lua_version",
+            "lua53",
+            TestUtilConfig {
+                standard_library: StandardLibrary::from_name("lua53").unwrap(),
+                ..Default::default()
+            },
+            output(),
+        );
+    }
+
+    #[test]
+    fn test_multiple_versions() {
+        let mut standard_library = StandardLibrary::from_name("lua51").unwrap();
+        standard_library.lua_versions = vec![LuaVersion::LuaJIT, LuaVersion::Lua54];
+
+        test_lint_config_with_output(
+            IncompatibleLuaVersionLint::new(()).unwrap(),
+            "incompatible_lua_version",
+            "multiple_versions",
+            TestUtilConfig {
+                standard_library,
+                ..Default::default()
+            },
+            output(),
+        );
+    }
+}
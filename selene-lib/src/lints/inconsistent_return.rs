@@ -0,0 +1,261 @@
+use super::*;
+use crate::{
+    ast_util::{
+        control_flow::{ControlFlowGraph, NodeKind},
+        range,
+        scopes::ScopeManager,
+    },
+    standard_library::StandardLibrary,
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::{Symbol, TokenType},
+    visitors::Visitor,
+};
+
+pub struct InconsistentReturnLint;
+
+impl Lint for InconsistentReturnLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(InconsistentReturnLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = InconsistentReturnVisitor {
+            scope_manager: &ast_context.scope_manager,
+            standard_library: &context.standard_library,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReturnCount {
+    Exact(usize),
+
+    /// Ends with a function call or `...`, so could return any number of values after the first few.
+    AtLeast(usize),
+}
+
+struct FunctionExit {
+    range: (usize, usize),
+    count: ReturnCount,
+
+    // `return nil, "message"` is the usual way to return an error, so can return a different amount
+    is_error_tuple: bool,
+
+    falls_off_end: bool,
+}
+
+struct InconsistentReturnVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl InconsistentReturnVisitor<'_> {
+    fn check_function(&mut self, signature: (usize, usize), body: &ast::FunctionBody) {
+        let graph = ControlFlowGraph::new(body.block(), self.scope_manager, self.standard_library);
+
+        let mut exits = Vec::new();
+
+        for node in graph.exits() {
+            match node.kind {
+                NodeKind::LastStmt(ast::LastStmt::Return(return_stmt)) => {
+                    exits.push(FunctionExit {
+                        range: range(return_stmt),
+                        count: return_count(return_stmt),
+                        is_error_tuple: is_error_tuple(return_stmt),
+                        falls_off_end: false,
+                    });
+                }
+
+                _ => {
+                    if !exits.iter().any(|exit| exit.falls_off_end) {
+                        exits.push(FunctionExit {
+                            range: range(body.end_token()),
+                            count: ReturnCount::Exact(0),
+                            is_error_tuple: false,
+                            falls_off_end: true,
+                        });
+                    }
+                }
+            }
+        }
+
+        let returns_value = exits.iter().any(|exit| match exit.count {
+            ReturnCount::Exact(count) | ReturnCount::AtLeast(count) => count > 0,
+        });
+
+        let returns_nothing = exits.iter().any(|exit| exit.count == ReturnCount::Exact(0));
+
+        if returns_value && returns_nothing {
+            self.diagnostics.push(Diagnostic::new_complete(
+                "inconsistent_return",
+                "this function returns a value on some paths but not others".to_owned(),
+                Label::new(signature),
+                Vec::new(),
+                exits
+                    .iter()
+                    .filter_map(|exit| {
+                        let message = match exit.count {
+                            ReturnCount::Exact(0) if exit.falls_off_end => {
+                                "returns nothing when reaching the end of the function"
+                            }
+                            ReturnCount::Exact(0) => "returns nothing",
+                            ReturnCount::AtLeast(0) => return None,
+                            _ => "returns a value",
+                        };
+
+                        Some(Label::new_with_message(exit.range, message.to_owned()))
+                    })
+                    .collect(),
+            ));
+
+            return;
+        }
+
+        let exact_counts = exits
+            .iter()
+            .filter_map(|exit| match exit.count {
+                ReturnCount::Exact(count) if !exit.is_error_tuple => Some((exit, count)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if exact_counts
+            .iter()
+            .all(|(_, count)| *count == exact_counts[0].1)
+        {
+            return;
+        }
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "inconsistent_return",
+            "this function returns a different number of values on different paths".to_owned(),
+            Label::new(signature),
+            Vec::new(),
+            exact_counts
+                .iter()
+                .map(|(exit, count)| {
+                    Label::new_with_message(
+                        exit.range,
+                        format!(
+                            "returns {count} value{}",
+                            if *count == 1 { "" } else { "s" }
+                        ),
+                    )
+                })
+                .collect(),
+        ));
+    }
+}
+
+fn return_count(return_stmt: &ast::Return) -> ReturnCount {
+    let count = return_stmt.returns().len();
+
+    // `return (f())` is a parenthesized expression, and so only ever returns one value
+    match return_stmt.returns().iter().last() {
+        Some(ast::Expression::FunctionCall(_)) => ReturnCount::AtLeast(count - 1),
+
+        Some(ast::Expression::Symbol(symbol))
+            if matches!(
+                symbol.token_type(),
+                TokenType::Symbol {
+                    symbol: Symbol::Ellipsis
+                }
+            ) =>
+        {
+            ReturnCount::AtLeast(count - 1)
+        }
+
+        _ => ReturnCount::Exact(count),
+    }
+}
+
+fn is_error_tuple(return_stmt: &ast::Return) -> bool {
+    if return_stmt.returns().len() < 2 {
+        return false;
+    }
+
+    matches!(
+        return_stmt.returns().iter().next(),
+        Some(ast::Expression::Symbol(symbol))
+            if matches!(
+                symbol.token_type(),
+                TokenType::Symbol {
+                    symbol: Symbol::Nil | Symbol::False
+                }
+            )
+    )
+}
+
+impl Visitor for InconsistentReturnVisitor<'_> {
+    fn visit_local_function(&mut self, local_function: &ast::LocalFunction) {
+        self.check_function(
+            (
+                range(local_function.function_token()).0,
+                range(local_function.body().parameters_parentheses()).1,
+            ),
+            local_function.body(),
+        );
+    }
+
+    fn visit_function_declaration(&mut self, function_declaration: &ast::FunctionDeclaration) {
+        self.check_function(
+            (
+                range(function_declaration.function_token()).0,
+                range(function_declaration.body().parameters_parentheses()).1,
+            ),
+            function_declaration.body(),
+        );
+    }
+
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        if let ast::Expression::Function(function_box) = expression {
+            self.check_function(
+                (
+                    expression.start_position().unwrap().bytes(),
+                    range(function_box.body().parameters_parentheses()).1,
+                ),
+                function_box.body(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_inconsistent_return() {
+        test_lint(
+            InconsistentReturnLint::new(()).unwrap(),
+            "inconsistent_return",
+            "inconsistent_return",
+        );
+    }
+
+    #[test]
+    fn test_return_counts() {
+        test_lint(
+            InconsistentReturnLint::new(()).unwrap(),
+            "inconsistent_return",
+            "return_counts",
+        );
+    }
+}
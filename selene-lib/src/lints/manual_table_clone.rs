@@ -339,7 +339,10 @@ impl Visitor for ManualTableCloneVisitor<'_> {
 
                 if !matches!(
                     variable.value,
-                    Some(AssignedValue::StaticTable { has_fields: false })
+                    Some(AssignedValue::StaticTable {
+                        has_fields: false,
+                        ..
+                    })
                 ) {
                     return;
                 }
@@ -0,0 +1,145 @@
+use super::*;
+use crate::ast_util::{functions::visit_functions, range};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::TokenReference,
+};
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct MaxNestingDepthConfig {
+    maximum_depth: usize,
+}
+
+impl Default for MaxNestingDepthConfig {
+    fn default() -> Self {
+        Self { maximum_depth: 5 }
+    }
+}
+
+#[derive(Default)]
+pub struct MaxNestingDepthLint {
+    config: MaxNestingDepthConfig,
+}
+
+impl Lint for MaxNestingDepthLint {
+    type Config = MaxNestingDepthConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(MaxNestingDepthLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        // Functions start over from a depth of 0
+        self.check_block(ast.nodes(), 0, &mut diagnostics);
+        visit_functions(ast, |_, body| {
+            self.check_block(body.block(), 0, &mut diagnostics);
+        });
+
+        diagnostics
+    }
+}
+
+impl MaxNestingDepthLint {
+    fn check_block(&self, block: &ast::Block, depth: usize, diagnostics: &mut Vec<Diagnostic>) {
+        for stmt in block.stmts() {
+            #[cfg_attr(
+                feature = "force_exhaustive_checks",
+                deny(non_exhaustive_omitted_patterns)
+            )]
+            let (token, blocks): (&TokenReference, Vec<&ast::Block>) = match stmt {
+                ast::Stmt::Do(do_) => (do_.do_token(), vec![do_.block()]),
+
+                ast::Stmt::GenericFor(generic_for) => {
+                    (generic_for.for_token(), vec![generic_for.block()])
+                }
+
+                ast::Stmt::If(if_block) => (
+                    if_block.if_token(),
+                    std::iter::once(if_block.block())
+                        .chain(
+                            if_block
+                                .else_if()
+                                .into_iter()
+                                .flatten()
+                                .map(|else_if| else_if.block()),
+                        )
+                        .chain(if_block.else_block())
+                        .collect(),
+                ),
+
+                ast::Stmt::NumericFor(numeric_for) => {
+                    (numeric_for.for_token(), vec![numeric_for.block()])
+                }
+
+                ast::Stmt::Repeat(repeat) => (repeat.repeat_token(), vec![repeat.block()]),
+
+                ast::Stmt::While(while_loop) => {
+                    (while_loop.while_token(), vec![while_loop.block()])
+                }
+
+                // Functions start over, and are checked on their own
+                ast::Stmt::FunctionDeclaration(_) | ast::Stmt::LocalFunction(_) => continue,
+
+                ast::Stmt::Assignment(_)
+                | ast::Stmt::FunctionCall(_)
+                | ast::Stmt::LocalAssignment(_) => continue,
+
+                #[cfg(any(feature = "lua52", feature = "luajit"))]
+                ast::Stmt::Goto(_) | ast::Stmt::Label(_) => continue,
+
+                #[cfg(feature = "roblox")]
+                ast::Stmt::CompoundAssignment(_)
+                | ast::Stmt::ExportedTypeDeclaration(_)
+                | ast::Stmt::ExportedTypeFunction(_)
+                | ast::Stmt::TypeDeclaration(_)
+                | ast::Stmt::TypeFunction(_) => continue,
+
+                _ => continue,
+            };
+
+            let depth = depth + 1;
+
+            // Only the outermost block that's too deep is reported
+            if depth > self.config.maximum_depth {
+                diagnostics.push(Diagnostic::new(
+                    "max_nesting_depth",
+                    format!(
+                        "blocks are nested too deeply ({depth} > {})",
+                        self.config.maximum_depth
+                    ),
+                    Label::new(range::<_, usize>(token)),
+                ));
+
+                continue;
+            }
+
+            for block in blocks {
+                self.check_block(block, depth, diagnostics);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_max_nesting_depth() {
+        test_lint(
+            MaxNestingDepthLint::new(MaxNestingDepthConfig { maximum_depth: 2 }).unwrap(),
+            "max_nesting_depth",
+            "max_nesting_depth",
+        );
+    }
+}
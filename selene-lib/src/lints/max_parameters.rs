@@ -0,0 +1,82 @@
+use super::*;
+use crate::ast_util::functions::visit_functions;
+use std::convert::Infallible;
+
+use full_moon::ast::{self, Ast};
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct MaxParametersConfig {
+    maximum_parameters: usize,
+}
+
+impl Default for MaxParametersConfig {
+    fn default() -> Self {
+        Self {
+            maximum_parameters: 7,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MaxParametersLint {
+    config: MaxParametersConfig,
+}
+
+impl Lint for MaxParametersLint {
+    type Config = MaxParametersConfig;
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Allow;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(MaxParametersLint { config })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        visit_functions(ast, |signature, body| {
+            // `...` can take any number of arguments, so it isn't counted
+            let parameters = body
+                .parameters()
+                .iter()
+                .filter(|parameter| matches!(parameter, ast::Parameter::Name(_)))
+                .count();
+
+            if parameters > self.config.maximum_parameters {
+                diagnostics.push(Diagnostic::new_complete(
+                    "max_parameters",
+                    format!(
+                        "function has too many parameters ({parameters} > {})",
+                        self.config.maximum_parameters
+                    ),
+                    Label::new(signature),
+                    vec!["try: passing a table with named fields instead".to_owned()],
+                    Vec::new(),
+                ));
+            }
+        });
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_max_parameters() {
+        test_lint(
+            MaxParametersLint::new(MaxParametersConfig {
+                maximum_parameters: 2,
+            })
+            .unwrap(),
+            "max_parameters",
+            "max_parameters",
+        );
+    }
+}
@@ -2,12 +2,12 @@ use super::*;
 use crate::{
     ast_util::{
         is_vararg, range,
-        scopes::{Reference, ScopeManager, Variable},
+        scopes::{AssignedValue, Reference, ScopeManager, Variable},
     },
     text::plural,
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     convert::Infallible,
     fmt::{self, Display},
 };
@@ -34,16 +34,23 @@ impl Lint for MismatchedArgCountLint {
     fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
         // Firstly visit the AST so we can map the variables to their required parameter counts
         let mut definitions = HashMap::new();
+        let mut field_definitions = HashMap::new();
         let mut definitions_visitor = MapFunctionDefinitionVisitor {
             scope_manager: &ast_context.scope_manager,
             definitions: &mut definitions,
+            field_definitions: &mut field_definitions,
+            dynamic_tables: HashSet::new(),
         };
         definitions_visitor.visit_ast(ast);
 
+        let dynamic_tables = definitions_visitor.dynamic_tables;
+        field_definitions.retain(|(variable, _), _| !dynamic_tables.contains(variable));
+
         let mut visitor = MismatchedArgCountVisitor {
             mismatched_arg_counts: Vec::new(),
             scope_manager: &ast_context.scope_manager,
             definitions,
+            field_definitions,
         };
 
         visitor.visit_ast(ast);
@@ -160,6 +167,28 @@ impl ParameterCount {
         }
     }
 
+    /// The parameter count when `self` is passed explicitly, for functions defined with `function t:f()`
+    fn with_self(self) -> Self {
+        match self {
+            ParameterCount::Fixed(required) => ParameterCount::Fixed(required + 1),
+            ParameterCount::Minimum(required) => ParameterCount::Minimum(required + 1),
+            ParameterCount::Variable => ParameterCount::Variable,
+        }
+    }
+
+    /// The parameter count when `self` is passed implicitly, for calls like `t:f()`
+    fn without_self(self) -> Option<Self> {
+        match self {
+            ParameterCount::Fixed(required) => {
+                Some(ParameterCount::Fixed(required.checked_sub(1)?))
+            }
+            ParameterCount::Minimum(required) => {
+                Some(ParameterCount::Minimum(required.checked_sub(1)?))
+            }
+            ParameterCount::Variable => Some(ParameterCount::Variable),
+        }
+    }
+
     fn overlap_with_other_parameter_count(self, other: ParameterCount) -> ParameterCount {
         match (self, other) {
             // If something takes `...`, then it'll always be correct no matter what.
@@ -253,8 +282,19 @@ impl PassedArgumentCount {
             _ => Self::Fixed(0),
         }
     }
+
+    /// The passed argument count when `self` is passed implicitly, for calls like `t:f()`
+    fn with_self(self) -> Self {
+        match self {
+            PassedArgumentCount::Fixed(amount) => PassedArgumentCount::Fixed(amount + 1),
+            PassedArgumentCount::Variable(amount) => PassedArgumentCount::Variable(amount + 1),
+        }
+    }
 }
 
+/// A function stored in a field of a local table, such as `M.f` in `local M = {}; function M.f() end`
+type FieldDefinitions = HashMap<(Id<Variable>, String), (ParameterCount, Vec<(usize, usize)>)>;
+
 impl Display for PassedArgumentCount {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -268,6 +308,9 @@ impl Display for PassedArgumentCount {
 struct MapFunctionDefinitionVisitor<'a> {
     scope_manager: &'a ScopeManager,
     definitions: &'a mut HashMap<Id<Variable>, ParameterCount>,
+    field_definitions: &'a mut FieldDefinitions,
+    // Tables that have fields set with `t[key] = value`, which could be any field
+    dynamic_tables: HashSet<Id<Variable>>,
 }
 
 impl MapFunctionDefinitionVisitor<'_> {
@@ -302,6 +345,36 @@ impl MapFunctionDefinitionVisitor<'_> {
             })
             .or_insert(parameter_count);
     }
+
+    /// Finds the local a field is being defined on, if it's a table whose fields are all known.
+    fn find_table_variable(&self, identifier: (usize, usize)) -> Option<Id<Variable>> {
+        let variable = self.find_reference(identifier)?.resolved?;
+
+        match self.scope_manager.variable_value(variable) {
+            Some(AssignedValue::StaticTable { .. }) => Some(variable),
+            _ => None,
+        }
+    }
+
+    /// Stores a function defined on a field, overlapping it with any other definitions the same way as `verify_assignment`.
+    /// A `parameter_count` of `None` means the field was set to something that isn't a function, so nothing is known.
+    fn verify_field_assignment(
+        &mut self,
+        variable: Id<Variable>,
+        field: String,
+        parameter_count: Option<ParameterCount>,
+        definition_range: (usize, usize),
+    ) {
+        let parameter_count = parameter_count.unwrap_or(ParameterCount::Variable);
+
+        self.field_definitions
+            .entry((variable, field))
+            .and_modify(|(older_count, ranges)| {
+                *older_count = parameter_count.overlap_with_other_parameter_count(*older_count);
+                ranges.push(definition_range);
+            })
+            .or_insert((parameter_count, vec![definition_range]));
+    }
 }
 
 impl Visitor for MapFunctionDefinitionVisitor<'_> {
@@ -322,6 +395,29 @@ impl Visitor for MapFunctionDefinitionVisitor<'_> {
                 self.verify_assignment(variable, function.body())
             }
         }
+
+        // `function M.f()` and `function M:f()`
+        let names = function.name().names();
+        let (field, is_method) = match (names.len(), function.name().method_name()) {
+            (2, None) => (names.iter().nth(1).unwrap(), false),
+            (1, Some(method_name)) => (method_name, true),
+            _ => return,
+        };
+
+        if let Some(variable) = self.find_table_variable(range(names.iter().next().unwrap())) {
+            let parameter_count = ParameterCount::from_function_body(function.body());
+
+            self.verify_field_assignment(
+                variable,
+                field.token().to_string(),
+                Some(if is_method {
+                    parameter_count.with_self()
+                } else {
+                    parameter_count
+                }),
+                range(function.name()),
+            );
+        }
     }
 
     fn visit_local_assignment(&mut self, local_assignment: &ast::LocalAssignment) {
@@ -340,6 +436,36 @@ impl Visitor for MapFunctionDefinitionVisitor<'_> {
                         .insert(id, ParameterCount::from_function_body(function_body));
                 }
             }
+
+            // `local M = { f = function() end }`
+            if let ast::Expression::TableConstructor(table_constructor) = expression {
+                let Some(id) = self.find_variable(range(name_token)) else {
+                    continue;
+                };
+
+                if !matches!(
+                    self.scope_manager.variable_value(id),
+                    Some(AssignedValue::StaticTable { .. })
+                ) {
+                    continue;
+                }
+
+                for field in table_constructor.fields() {
+                    if let ast::Field::NameKey { key, value, .. } = field {
+                        self.verify_field_assignment(
+                            id,
+                            key.token().to_string(),
+                            match value {
+                                ast::Expression::Function(function_box) => {
+                                    Some(ParameterCount::from_function_body(function_box.body()))
+                                }
+                                _ => None,
+                            },
+                            range(key),
+                        );
+                    }
+                }
+            }
         }
     }
 
@@ -357,6 +483,43 @@ impl Visitor for MapFunctionDefinitionVisitor<'_> {
                     }
                 }
             }
+
+            // `M.f = function() end`
+            if_chain::if_chain! {
+                if let ast::Var::Expression(var_expression) = var;
+                if let ast::Prefix::Name(name) = var_expression.prefix();
+                if let Some(variable) = self.find_table_variable(range(name));
+                then {
+                    let mut suffixes = var_expression.suffixes();
+
+                    let field = match (suffixes.next(), suffixes.next()) {
+                        (Some(ast::Suffix::Index(ast::Index::Dot { name, .. })), None) => {
+                            name.token().to_string()
+                        }
+
+                        // `M.a.b = ...` doesn't change `M`'s fields
+                        (Some(ast::Suffix::Index(ast::Index::Dot { .. })), Some(_)) => continue,
+
+                        // `M[key] = ...` could be any field, so nothing can be known about `M`
+                        _ => {
+                            self.dynamic_tables.insert(variable);
+                            continue;
+                        }
+                    };
+
+                    self.verify_field_assignment(
+                        variable,
+                        field,
+                        match expression {
+                            ast::Expression::Function(function_box) => {
+                                Some(ParameterCount::from_function_body(function_box.body()))
+                            }
+                            _ => None,
+                        },
+                        range(var),
+                    );
+                }
+            }
         }
     }
 }
@@ -365,6 +528,7 @@ struct MismatchedArgCountVisitor<'a> {
     mismatched_arg_counts: Vec<MismatchedArgCount>,
     scope_manager: &'a ScopeManager,
     definitions: HashMap<Id<Variable>, ParameterCount>,
+    field_definitions: FieldDefinitions,
 }
 
 impl MismatchedArgCountVisitor<'_> {
@@ -388,8 +552,77 @@ impl MismatchedArgCountVisitor<'_> {
     }
 }
 
+impl MismatchedArgCountVisitor<'_> {
+    /// Checks calls to functions stored in fields of local tables, like `M.f()` or `M:f()`
+    fn check_field_call(&mut self, call: &ast::FunctionCall) {
+        let ast::Prefix::Name(name) = call.prefix() else {
+            return;
+        };
+
+        let mut suffixes = call.suffixes();
+
+        let (field, args, is_method) = match (suffixes.next(), suffixes.next()) {
+            (
+                Some(ast::Suffix::Index(ast::Index::Dot { name, .. })),
+                Some(ast::Suffix::Call(ast::Call::AnonymousCall(args))),
+            ) => (name, args, false),
+
+            (Some(ast::Suffix::Call(ast::Call::MethodCall(method_call))), _) => {
+                (method_call.name(), method_call.args(), true)
+            }
+
+            _ => return,
+        };
+
+        let identifier = range(name);
+        let Some(variable) = self
+            .scope_manager
+            .references
+            .iter()
+            .find(|reference| reference.1.identifier == identifier)
+            .and_then(|(_, reference)| reference.resolved)
+        else {
+            return;
+        };
+
+        let Some((parameter_count, definition_ranges)) = self
+            .field_definitions
+            .get(&(variable, field.token().to_string()))
+        else {
+            return;
+        };
+
+        let num_args_provided = PassedArgumentCount::from_function_args(args);
+
+        let correct = if is_method {
+            parameter_count.correct_num_args_provided(num_args_provided.with_self())
+        } else {
+            parameter_count.correct_num_args_provided(num_args_provided)
+        };
+
+        if !correct {
+            // Report method calls without the implicit `self` when possible, since that's what was written
+            let (num_provided, parameter_count) = match (is_method, parameter_count.without_self())
+            {
+                (true, Some(parameter_count)) => (num_args_provided, parameter_count),
+                (true, None) => (num_args_provided.with_self(), *parameter_count),
+                (false, _) => (num_args_provided, *parameter_count),
+            };
+
+            self.mismatched_arg_counts.push(MismatchedArgCount {
+                num_provided,
+                parameter_count,
+                call_range: range(call),
+                function_definition_ranges: definition_ranges.clone(),
+            });
+        }
+    }
+}
+
 impl Visitor for MismatchedArgCountVisitor<'_> {
     fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        self.check_field_call(call);
+
         if_chain::if_chain! {
             // Check that we're using a named function call, with an anonymous call suffix
             if let ast::Prefix::Name(name) = call.prefix();
@@ -501,4 +734,13 @@ mod tests {
             "multiple_definition_locations",
         );
     }
+
+    #[test]
+    fn test_table_fields() {
+        test_lint(
+            MismatchedArgCountLint::new(()).unwrap(),
+            "mismatched_arg_count",
+            "table_fields",
+        );
+    }
 }
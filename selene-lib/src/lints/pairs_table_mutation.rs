@@ -0,0 +1,292 @@
+use super::*;
+use crate::ast_util::{
+    range,
+    scopes::{ScopeManager, Variable},
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::{Symbol, TokenReference, TokenType},
+    visitors::Visitor,
+};
+use id_arena::Id;
+
+pub struct PairsTableMutationLint;
+
+impl Lint for PairsTableMutationLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(PairsTableMutationLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = PairsTableMutationVisitor {
+            scope_manager: &ast_context.scope_manager,
+            iterating: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+enum Table {
+    Local(Id<Variable>),
+    Global(String),
+}
+
+struct IteratedTable {
+    table: Table,
+    name: String,
+    // The key variable, `k` in `for k, v in pairs(t)`, which can always be assigned to
+    key: Option<Id<Variable>>,
+    pairs_range: (usize, usize),
+}
+
+struct PairsTableMutationVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    // One entry per generic for loop being visited, None if it doesn't iterate with `pairs` or `next`
+    iterating: Vec<Option<IteratedTable>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn is_nil(expression: &ast::Expression) -> bool {
+    matches!(
+        expression,
+        ast::Expression::Symbol(token)
+            if *token.token_type() == TokenType::Symbol { symbol: Symbol::Nil }
+    )
+}
+
+fn expression_name(expression: &ast::Expression) -> Option<&TokenReference> {
+    match expression {
+        ast::Expression::Var(ast::Var::Name(name)) => Some(name),
+        _ => None,
+    }
+}
+
+impl PairsTableMutationVisitor<'_> {
+    fn is_global(&self, name: &TokenReference, global_name: &str) -> bool {
+        name.token().to_string() == global_name
+            && self
+                .scope_manager
+                .reference_at_byte(range(name).0)
+                .is_some_and(|reference| reference.resolved.is_none())
+    }
+
+    fn table(&self, name: &TokenReference) -> Option<Table> {
+        let reference = self.scope_manager.reference_at_byte(range(name).0)?;
+
+        Some(match reference.resolved {
+            Some(variable) => Table::Local(variable),
+            None => Table::Global(reference.name.clone()),
+        })
+    }
+
+    // Finds `t` in `pairs(t)` or `next, t`
+    fn iterated_table<'b>(&self, generic_for: &'b ast::GenericFor) -> Option<&'b TokenReference> {
+        let mut expressions = generic_for.expressions().iter();
+
+        match expressions.next()? {
+            ast::Expression::FunctionCall(call) => {
+                let ast::Prefix::Name(pairs) = call.prefix() else {
+                    return None;
+                };
+
+                if !self.is_global(pairs, "pairs") {
+                    return None;
+                }
+
+                let mut suffixes = call.suffixes();
+
+                let Some(ast::Suffix::Call(ast::Call::AnonymousCall(
+                    ast::FunctionArgs::Parentheses { arguments, .. },
+                ))) = suffixes.next()
+                else {
+                    return None;
+                };
+
+                if suffixes.next().is_some() || arguments.len() != 1 {
+                    return None;
+                }
+
+                expression_name(arguments.iter().next()?)
+            }
+
+            expression => {
+                if !expression_name(expression).is_some_and(|next| self.is_global(next, "next")) {
+                    return None;
+                }
+
+                expression_name(expressions.next()?)
+            }
+        }
+    }
+
+    fn iterated_by(&self, name: &TokenReference) -> Option<&IteratedTable> {
+        let table = self.table(name)?;
+
+        self.iterating
+            .iter()
+            .flatten()
+            .find(|iterated| iterated.table == table)
+    }
+
+    fn report(&mut self, name: &TokenReference, range: (usize, usize)) {
+        let Some(iterated) = self.iterated_by(name) else {
+            return;
+        };
+
+        let diagnostic = Diagnostic::new_complete(
+            "pairs_table_mutation",
+            format!(
+                "adding keys to `{}` while iterating over it is undefined behavior",
+                iterated.name
+            ),
+            Label::new(range),
+            vec![
+                "assigning to existing keys or setting them to nil is fine, but new keys can make the loop skip or repeat keys".to_owned(),
+            ],
+            vec![Label::new_with_message(
+                iterated.pairs_range,
+                "iterated over here".to_owned(),
+            )],
+        );
+
+        self.diagnostics.push(diagnostic);
+    }
+}
+
+impl Visitor for PairsTableMutationVisitor<'_> {
+    fn visit_generic_for(&mut self, generic_for: &ast::GenericFor) {
+        let iterated = self.iterated_table(generic_for).and_then(|name| {
+            let key_range = range(generic_for.names().iter().next()?);
+
+            Some(IteratedTable {
+                table: self.table(name)?,
+                name: name.token().to_string(),
+                key: self
+                    .scope_manager
+                    .variables
+                    .iter()
+                    .find(|(_, variable)| variable.identifiers.contains(&key_range))
+                    .map(|(id, _)| id),
+                pairs_range: (
+                    range::<_, usize>(generic_for.in_token()).1 + 1,
+                    range::<_, usize>(generic_for.expressions()).1,
+                ),
+            })
+        });
+
+        self.iterating.push(iterated);
+    }
+
+    fn visit_generic_for_end(&mut self, _: &ast::GenericFor) {
+        self.iterating.pop();
+    }
+
+    fn visit_assignment(&mut self, assignment: &ast::Assignment) {
+        let mut expressions = assignment.expressions().iter();
+
+        for var in assignment.variables() {
+            // Extra variables without a value are set to nil
+            let Some(expression) = expressions.next() else {
+                break;
+            };
+
+            let ast::Var::Expression(var_expression) = var else {
+                continue;
+            };
+
+            let ast::Prefix::Name(name) = var_expression.prefix() else {
+                continue;
+            };
+
+            let mut suffixes = var_expression.suffixes();
+
+            let Some(ast::Suffix::Index(index)) = suffixes.next() else {
+                continue;
+            };
+
+            if suffixes.next().is_some() || is_nil(expression) {
+                continue;
+            }
+
+            if let ast::Index::Brackets {
+                expression: key, ..
+            } = index
+            {
+                let key_variable = expression_name(key)
+                    .and_then(|key| self.scope_manager.reference_at_byte(range(key).0))
+                    .and_then(|reference| reference.resolved);
+
+                if key_variable.is_some()
+                    && self
+                        .iterated_by(name)
+                        .is_some_and(|iterated| iterated.key == key_variable)
+                {
+                    continue;
+                }
+            }
+
+            self.report(name, (range(var).0, range(expression).1));
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let ast::Prefix::Name(table_global) = call.prefix() else {
+            return;
+        };
+
+        if !self.is_global(table_global, "table") {
+            return;
+        }
+
+        let mut suffixes = call.suffixes();
+
+        let Some(ast::Suffix::Index(ast::Index::Dot { name: insert, .. })) = suffixes.next() else {
+            return;
+        };
+
+        if insert.token().to_string() != "insert" {
+            return;
+        }
+
+        let Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+            arguments,
+            ..
+        }))) = suffixes.next()
+        else {
+            return;
+        };
+
+        let Some(name) = arguments.iter().next().and_then(expression_name) else {
+            return;
+        };
+
+        self.report(name, range(call));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_pairs_table_mutation() {
+        test_lint(
+            PairsTableMutationLint::new(()).unwrap(),
+            "pairs_table_mutation",
+            "pairs_table_mutation",
+        );
+    }
+}
@@ -0,0 +1,545 @@
+use super::*;
+use crate::{
+    ast_util::{
+        control_flow::{guarded_variables, ControlFlowGraph, ControlFlowNode, EdgeKind, NodeKind},
+        range,
+        scopes::{AssignedValue, ScopeManager, Variable},
+        std_types::StdTypes,
+    },
+    standard_library::StandardLibrary,
+};
+use std::{collections::HashSet, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+use id_arena::Id;
+
+pub struct PossiblyNilIndexLint;
+
+impl Lint for PossiblyNilIndexLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(PossiblyNilIndexLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = PossiblyNilIndexVisitor {
+            scope_manager: &ast_context.scope_manager,
+            standard_library: &context.standard_library,
+            std_types: StdTypes::new(&ast_context.scope_manager, &context.standard_library),
+            diagnostics: Vec::new(),
+        };
+
+        visitor.check_function_body(ast.nodes());
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+/// The locals that could be nil at some point in the function.
+type NilVariables = HashSet<Id<Variable>>;
+
+struct PossiblyNilIndexVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+    std_types: StdTypes<'a>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl PossiblyNilIndexVisitor<'_> {
+    fn check_function_body(&mut self, block: &ast::Block) {
+        let graph = ControlFlowGraph::new(block, self.scope_manager, self.standard_library);
+
+        let states = graph.forward_dataflow(
+            NilVariables::new(),
+            |node, edge_kind, state| {
+                let mut state = state.clone();
+                self.visit_node(node, &mut state, &mut None);
+
+                if let Some(condition) = node.condition() {
+                    let truthy = match edge_kind {
+                        EdgeKind::IfTrue => true,
+                        EdgeKind::IfFalse => false,
+                        EdgeKind::Always => return state,
+                    };
+
+                    for variable in guarded_variables(self.scope_manager, condition, truthy) {
+                        state.remove(&variable);
+                    }
+                }
+
+                state
+            },
+            |state, other_state| state.extend(other_state),
+        );
+
+        let mut diagnostics = Some(Vec::new());
+
+        for (id, mut state) in states {
+            self.visit_node(graph.node(id), &mut state, &mut diagnostics);
+        }
+
+        self.diagnostics.extend(diagnostics.unwrap());
+    }
+
+    // Checks for indexing locals that could be nil, and updates which locals could be nil after the node.
+    // Diagnostics are only made on the last pass, once the analysis has settled.
+    fn visit_node(
+        &self,
+        node: &ControlFlowNode,
+        state: &mut NilVariables,
+        diagnostics: &mut Option<Vec<Diagnostic>>,
+    ) {
+        match node.kind {
+            NodeKind::Stmt(stmt) => self.visit_stmt(stmt, state, diagnostics),
+
+            NodeKind::LastStmt(ast::LastStmt::Return(return_stmt)) => {
+                for expression in return_stmt.returns() {
+                    self.check_expression(expression, state, diagnostics);
+                }
+            }
+
+            NodeKind::ElseIf(else_if) => {
+                self.check_expression(else_if.condition(), state, diagnostics);
+            }
+
+            NodeKind::Until(repeat) => self.check_expression(repeat.until(), state, diagnostics),
+
+            _ => {}
+        }
+    }
+
+    fn visit_stmt(
+        &self,
+        stmt: &ast::Stmt,
+        state: &mut NilVariables,
+        diagnostics: &mut Option<Vec<Diagnostic>>,
+    ) {
+        match stmt {
+            ast::Stmt::LocalAssignment(local_assignment) => {
+                for expression in local_assignment.expressions() {
+                    self.check_expression(expression, state, diagnostics);
+                }
+
+                for name in local_assignment.names() {
+                    let Some(variable) = self.resolve(name) else {
+                        continue;
+                    };
+
+                    if self.nilable_function_name(variable).is_some() {
+                        state.insert(variable);
+                    } else {
+                        state.remove(&variable);
+                    }
+                }
+            }
+
+            ast::Stmt::Assignment(assignment) => {
+                for var in assignment.variables() {
+                    self.check_var(var, state, diagnostics);
+                }
+
+                for expression in assignment.expressions() {
+                    self.check_expression(expression, state, diagnostics);
+                }
+
+                // `x = x or default` and anything else that gives it a new value
+                for var in assignment.variables() {
+                    if let ast::Var::Name(name) = var {
+                        if let Some(variable) = self.resolve(name) {
+                            state.remove(&variable);
+                        }
+                    }
+                }
+            }
+
+            ast::Stmt::FunctionCall(call) => {
+                self.check_call(call, state, diagnostics);
+
+                if let Some(condition) = self.assert_condition(call) {
+                    for variable in guarded_variables(self.scope_manager, condition, true) {
+                        state.remove(&variable);
+                    }
+                }
+            }
+
+            ast::Stmt::If(if_block) => {
+                self.check_expression(if_block.condition(), state, diagnostics)
+            }
+
+            ast::Stmt::While(while_loop) => {
+                self.check_expression(while_loop.condition(), state, diagnostics)
+            }
+
+            ast::Stmt::NumericFor(numeric_for) => {
+                self.check_expression(numeric_for.start(), state, diagnostics);
+                self.check_expression(numeric_for.end(), state, diagnostics);
+
+                if let Some(step) = numeric_for.step() {
+                    self.check_expression(step, state, diagnostics);
+                }
+            }
+
+            ast::Stmt::GenericFor(generic_for) => {
+                for expression in generic_for.expressions() {
+                    self.check_expression(expression, state, diagnostics);
+                }
+            }
+
+            #[cfg(feature = "roblox")]
+            ast::Stmt::CompoundAssignment(compound_assignment) => {
+                self.check_var(compound_assignment.lhs(), state, diagnostics);
+                self.check_expression(compound_assignment.rhs(), state, diagnostics);
+
+                if let ast::Var::Name(name) = compound_assignment.lhs() {
+                    if let Some(variable) = self.resolve(name) {
+                        state.remove(&variable);
+                    }
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    fn check_expression(
+        &self,
+        expression: &ast::Expression,
+        state: &mut NilVariables,
+        diagnostics: &mut Option<Vec<Diagnostic>>,
+    ) {
+        match expression {
+            ast::Expression::BinaryOperator { lhs, binop, rhs } => {
+                self.check_expression(lhs, state, diagnostics);
+
+                // `x and x.y` only indexes `x` when it isn't nil
+                let guard = match binop {
+                    ast::BinOp::And(_) => Some(true),
+                    ast::BinOp::Or(_) => Some(false),
+                    _ => None,
+                };
+
+                match guard {
+                    Some(truthy) => {
+                        let mut rhs_state = state.clone();
+
+                        for variable in guarded_variables(self.scope_manager, lhs, truthy) {
+                            rhs_state.remove(&variable);
+                        }
+
+                        self.check_expression(rhs, &mut rhs_state, diagnostics);
+                    }
+
+                    None => self.check_expression(rhs, state, diagnostics),
+                }
+            }
+
+            ast::Expression::FunctionCall(call) => self.check_call(call, state, diagnostics),
+
+            ast::Expression::Parentheses { expression, .. }
+            | ast::Expression::UnaryOperator { expression, .. } => {
+                self.check_expression(expression, state, diagnostics)
+            }
+
+            ast::Expression::TableConstructor(table_constructor) => {
+                self.check_table_constructor(table_constructor, state, diagnostics)
+            }
+
+            ast::Expression::Var(var) => self.check_var(var, state, diagnostics),
+
+            #[cfg(feature = "roblox")]
+            ast::Expression::IfExpression(if_expression) => {
+                self.check_expression(if_expression.condition(), state, diagnostics);
+
+                let mut then_state = state.clone();
+                for variable in
+                    guarded_variables(self.scope_manager, if_expression.condition(), true)
+                {
+                    then_state.remove(&variable);
+                }
+                self.check_expression(if_expression.if_expression(), &mut then_state, diagnostics);
+
+                // Which `elseif`s ran isn't tracked, so they only get what's known to be nil before them
+                for else_if in if_expression.else_if_expressions().into_iter().flatten() {
+                    let mut else_if_state = state.clone();
+                    self.check_expression(else_if.condition(), &mut else_if_state, diagnostics);
+                    self.check_expression(else_if.expression(), &mut else_if_state, diagnostics);
+                }
+
+                let mut else_state = state.clone();
+                for variable in
+                    guarded_variables(self.scope_manager, if_expression.condition(), false)
+                {
+                    else_state.remove(&variable);
+                }
+                self.check_expression(
+                    if_expression.else_expression(),
+                    &mut else_state,
+                    diagnostics,
+                );
+            }
+
+            #[cfg(feature = "roblox")]
+            ast::Expression::InterpolatedString(interpolated_string) => {
+                for expression in interpolated_string.expressions() {
+                    self.check_expression(expression, state, diagnostics);
+                }
+            }
+
+            #[cfg(feature = "roblox")]
+            ast::Expression::TypeAssertion { expression, .. } => {
+                self.check_expression(expression, state, diagnostics)
+            }
+
+            // Functions get their own graph
+            _ => {}
+        }
+    }
+
+    fn check_table_constructor(
+        &self,
+        table_constructor: &ast::TableConstructor,
+        state: &mut NilVariables,
+        diagnostics: &mut Option<Vec<Diagnostic>>,
+    ) {
+        for field in table_constructor.fields() {
+            match field {
+                ast::Field::ExpressionKey { key, value, .. } => {
+                    self.check_expression(key, state, diagnostics);
+                    self.check_expression(value, state, diagnostics);
+                }
+
+                ast::Field::NameKey { value, .. } | ast::Field::NoKey(value) => {
+                    self.check_expression(value, state, diagnostics)
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    fn check_var(
+        &self,
+        var: &ast::Var,
+        state: &mut NilVariables,
+        diagnostics: &mut Option<Vec<Diagnostic>>,
+    ) {
+        if let ast::Var::Expression(var_expression) = var {
+            self.check_suffixed(
+                var_expression.prefix(),
+                var_expression.suffixes(),
+                state,
+                diagnostics,
+            );
+        }
+    }
+
+    fn check_call(
+        &self,
+        call: &ast::FunctionCall,
+        state: &mut NilVariables,
+        diagnostics: &mut Option<Vec<Diagnostic>>,
+    ) {
+        self.check_suffixed(call.prefix(), call.suffixes(), state, diagnostics);
+    }
+
+    fn check_suffixed<'b>(
+        &self,
+        prefix: &ast::Prefix,
+        suffixes: impl Iterator<Item = &'b ast::Suffix>,
+        state: &mut NilVariables,
+        diagnostics: &mut Option<Vec<Diagnostic>>,
+    ) {
+        let mut suffixes = suffixes.peekable();
+
+        match prefix {
+            ast::Prefix::Name(name) => {
+                let first_suffix = suffixes.peek();
+
+                let is_index = matches!(
+                    first_suffix,
+                    Some(ast::Suffix::Index(_) | ast::Suffix::Call(ast::Call::MethodCall(_)))
+                );
+
+                if let (true, Some(variable)) = (is_index, self.resolve(name)) {
+                    // If it was nil, it would've errored here, so it's not nil after this
+                    if state.remove(&variable) {
+                        if let Some(diagnostics) = diagnostics {
+                            let end = range(first_suffix.unwrap()).1;
+                            diagnostics.push(self.diagnostic(variable, (range(name).0, end)));
+                        }
+                    }
+                }
+            }
+
+            ast::Prefix::Expression(expression) => {
+                self.check_expression(expression, state, diagnostics)
+            }
+
+            _ => {}
+        }
+
+        for suffix in suffixes {
+            match suffix {
+                ast::Suffix::Index(ast::Index::Brackets { expression, .. }) => {
+                    self.check_expression(expression, state, diagnostics)
+                }
+
+                ast::Suffix::Call(ast::Call::AnonymousCall(arguments)) => {
+                    self.check_arguments(arguments, state, diagnostics)
+                }
+
+                ast::Suffix::Call(ast::Call::MethodCall(method_call)) => {
+                    self.check_arguments(method_call.args(), state, diagnostics)
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    fn check_arguments(
+        &self,
+        arguments: &ast::FunctionArgs,
+        state: &mut NilVariables,
+        diagnostics: &mut Option<Vec<Diagnostic>>,
+    ) {
+        match arguments {
+            ast::FunctionArgs::Parentheses { arguments, .. } => {
+                for argument in arguments {
+                    self.check_expression(argument, state, diagnostics);
+                }
+            }
+
+            ast::FunctionArgs::TableConstructor(table_constructor) => {
+                self.check_table_constructor(table_constructor, state, diagnostics)
+            }
+
+            _ => {}
+        }
+    }
+
+    fn diagnostic(&self, variable_id: Id<Variable>, range: (usize, usize)) -> Diagnostic {
+        let variable = &self.scope_manager.variables[variable_id];
+        let function_name = self.nilable_function_name(variable_id).unwrap_or_default();
+
+        Diagnostic::new_complete(
+            "possibly_nil_index",
+            format!(
+                "`{}` could be nil here, as `{function_name}` can return nil",
+                variable.name
+            ),
+            Label::new(range),
+            vec![format!(
+                "try checking `if {name} then` first, or using `assert({name})`",
+                name = variable.name
+            )],
+            variable
+                .identifiers
+                .first()
+                .map(|identifier| Label::new_with_message(*identifier, "assigned here".to_owned()))
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    fn resolve(&self, name: &TokenReference) -> Option<Id<Variable>> {
+        self.scope_manager
+            .reference_at_byte(name.token().start_position().bytes())?
+            .resolved
+    }
+
+    // The name of the function the local was assigned from, if it can return nil
+    fn nilable_function_name(&self, variable: Id<Variable>) -> Option<String> {
+        let value = self.scope_manager.variables[variable].value.as_ref()?;
+
+        let AssignedValue::FunctionCall {
+            call_name_path,
+            return_index,
+            ..
+        } = value
+        else {
+            return None;
+        };
+
+        let function = self.std_types.called_function(value)?;
+
+        function
+            .returns
+            .get(*return_index)?
+            .is_nilable()
+            .then(|| call_name_path.join("."))
+    }
+
+    // `assert(x)`, as long as `assert` isn't a local
+    fn assert_condition<'b>(&self, call: &'b ast::FunctionCall) -> Option<&'b ast::Expression> {
+        let ast::Prefix::Name(name) = call.prefix() else {
+            return None;
+        };
+
+        if name.token().to_string() != "assert" || self.resolve(name).is_some() {
+            return None;
+        }
+
+        let mut suffixes = call.suffixes();
+
+        match (suffixes.next(), suffixes.next()) {
+            (
+                Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+                    arguments,
+                    ..
+                }))),
+                None,
+            ) => arguments.iter().next(),
+
+            _ => None,
+        }
+    }
+}
+
+impl Visitor for PossiblyNilIndexVisitor<'_> {
+    fn visit_function_body(&mut self, function_body: &ast::FunctionBody) {
+        self.check_function_body(function_body.block());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_possibly_nil_index() {
+        test_lint(
+            PossiblyNilIndexLint::new(()).unwrap(),
+            "possibly_nil_index",
+            "possibly_nil_index",
+        );
+    }
+
+    #[test]
+    fn test_method_calls() {
+        test_lint(
+            PossiblyNilIndexLint::new(()).unwrap(),
+            "possibly_nil_index",
+            "method_calls",
+        );
+    }
+
+    #[test]
+    fn test_guards() {
+        test_lint(
+            PossiblyNilIndexLint::new(()).unwrap(),
+            "possibly_nil_index",
+            "guards",
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use super::*;
+use crate::ast_util::{purge_trivia, range, HasSideEffects};
+use std::{collections::HashSet, convert::Infallible};
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct SelfAssignmentLint;
+
+impl Lint for SelfAssignmentLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(SelfAssignmentLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = SelfAssignmentVisitor {
+            parameters: HashSet::new(),
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct SelfAssignmentVisitor {
+    // The parameters of the function whose body is about to be visited
+    parameters: HashSet<String>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+// `has_side_effects` doesn't look inside brackets, but `a[f()] = a[f()]` can index different fields
+fn indexes_have_side_effects(var: &ast::Var) -> bool {
+    let ast::Var::Expression(var_expression) = var else {
+        return false;
+    };
+
+    var_expression.suffixes().any(|suffix| {
+        matches!(
+            suffix,
+            ast::Suffix::Index(ast::Index::Brackets { expression, .. })
+                if expression.has_side_effects()
+        )
+    })
+}
+
+impl Visitor for SelfAssignmentVisitor {
+    fn visit_block(&mut self, block: &ast::Block) {
+        // `local x = x` is only useless when the `x` it copies is a local of this same block,
+        // since copying a global or an outer local is a common way to cache or capture it.
+        let mut locals = std::mem::take(&mut self.parameters);
+
+        for stmt in block.stmts() {
+            match stmt {
+                ast::Stmt::Assignment(assignment) => {
+                    for (var, expression) in assignment
+                        .variables()
+                        .iter()
+                        .zip(assignment.expressions().iter())
+                    {
+                        let ast::Expression::Var(value) = expression else {
+                            continue;
+                        };
+
+                        if var.has_side_effects()
+                            || value.has_side_effects()
+                            || indexes_have_side_effects(var)
+                        {
+                            continue;
+                        }
+
+                        let var_text = purge_trivia(var).to_string();
+
+                        if var_text.trim() == purge_trivia(value).to_string().trim() {
+                            self.diagnostics.push(Diagnostic::new(
+                                "self_assignment",
+                                format!("`{}` is assigned to itself", var_text.trim()),
+                                Label::new((
+                                    range::<_, usize>(var).0,
+                                    range::<_, usize>(expression).1,
+                                )),
+                            ));
+                        }
+                    }
+                }
+
+                ast::Stmt::LocalAssignment(local_assignment) => {
+                    for (name, expression) in local_assignment
+                        .names()
+                        .iter()
+                        .zip(local_assignment.expressions().iter())
+                    {
+                        let ast::Expression::Var(ast::Var::Name(value)) = expression else {
+                            continue;
+                        };
+
+                        let name_text = name.token().to_string();
+
+                        if value.token().to_string() == name_text && locals.contains(&name_text) {
+                            self.diagnostics.push(Diagnostic::new_complete(
+                                "self_assignment",
+                                format!("`{name_text}` is redeclared with its own value"),
+                                Label::new((
+                                    range::<_, usize>(name).0,
+                                    range::<_, usize>(expression).1,
+                                )),
+                                vec![format!(
+                                    "`{name_text}` is already a local here, so this does nothing"
+                                )],
+                                Vec::new(),
+                            ));
+                        }
+                    }
+
+                    locals.extend(
+                        local_assignment
+                            .names()
+                            .iter()
+                            .map(|name| name.token().to_string()),
+                    );
+                }
+
+                ast::Stmt::LocalFunction(local_function) => {
+                    locals.insert(local_function.name().token().to_string());
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    fn visit_function_body(&mut self, body: &ast::FunctionBody) {
+        self.parameters = body
+            .parameters()
+            .iter()
+            .filter_map(|parameter| match parameter {
+                ast::Parameter::Name(name) => Some(name.token().to_string()),
+                _ => None,
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_self_assignment() {
+        test_lint(
+            SelfAssignmentLint::new(()).unwrap(),
+            "self_assignment",
+            "self_assignment",
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use super::{super::standard_library::*, *};
 use crate::{
-    ast_util::{name_paths::*, scopes::ScopeManager},
+    ast_util::{
+        name_paths::*,
+        std_types::{NamePathRoot, StdTypes},
+    },
     possible_std::possible_standard_library_notes,
 };
 use std::convert::Infallible;
@@ -29,6 +32,7 @@ impl Lint for StandardLibraryLint {
         let mut visitor = StandardLibraryVisitor {
             diagnostics: Vec::new(),
             scope_manager: &ast_context.scope_manager,
+            std_types: StdTypes::new(&ast_context.scope_manager, &context.standard_library),
             standard_library: &context.standard_library,
             user_set_standard_library: &context.user_set_standard_library,
         };
@@ -74,7 +78,9 @@ fn get_argument_type(expression: &ast::Expression) -> Option<PassedArgumentType>
             }
         }
 
-        ast::Expression::Function(_) => Some(ArgumentType::Function.into()),
+        ast::Expression::Function(function) => {
+            Some(PassedArgumentType::from_function_body(function.body()))
+        }
         ast::Expression::FunctionCall(_) => None,
         ast::Expression::Number(_) => Some(ArgumentType::Number.into()),
         ast::Expression::String(token) => {
@@ -100,7 +106,9 @@ fn get_argument_type(expression: &ast::Expression) -> Option<PassedArgumentType>
                 other
             ),
         },
-        ast::Expression::TableConstructor(_) => Some(ArgumentType::Table.into()),
+        ast::Expression::TableConstructor(table) => {
+            Some(PassedArgumentType::from_table_constructor(table))
+        }
         ast::Expression::Var(_) => None,
 
         #[cfg(feature = "roblox")]
@@ -205,6 +213,7 @@ fn get_argument_type(expression: &ast::Expression) -> Option<PassedArgumentType>
 pub struct StandardLibraryVisitor<'std> {
     diagnostics: Vec<Diagnostic>,
     scope_manager: &'std ScopeManager,
+    std_types: StdTypes<'std>,
     standard_library: &'std StandardLibrary,
     user_set_standard_library: &'std Option<Vec<String>>,
 }
@@ -212,20 +221,24 @@ pub struct StandardLibraryVisitor<'std> {
 impl StandardLibraryVisitor<'_> {
     fn lint_invalid_field_access(
         &mut self,
+        root: &NamePathRoot,
         mut name_path: Vec<String>,
         range: (Position, Position),
     ) {
         // Make sure it's not just `bad()`, and that it's not a field access from a global outside of standard library
-        if self.standard_library.find_global(&name_path).is_none()
-            && self.standard_library.global_has_fields(&name_path[0])
-        {
+        let outside_standard_library = match root.struct_name {
+            Some(_) => name_path.len() <= root.length,
+            None => !self.standard_library.global_has_fields(&name_path[0]),
+        };
+
+        if self.std_types.find_field(root, &name_path).is_none() && !outside_standard_library {
             let field = name_path.pop().unwrap();
             assert!(!name_path.is_empty(), "name_path is empty");
 
             // check if it's writable
-            for bound in 1..=name_path.len() {
+            for bound in root.length + 1..=name_path.len() {
                 let path = &name_path[0..bound];
-                match self.standard_library.find_global(path) {
+                match self.std_types.find_field(root, path) {
                     Some(field) => {
                         match field.field_kind {
                             FieldKind::Any => return,
@@ -252,32 +265,73 @@ impl StandardLibraryVisitor<'_> {
             self.diagnostics.push(Diagnostic::new_complete(
                 "incorrect_standard_library_use",
                 format!(
-                    "standard library global `{}` does not contain the field `{}`",
-                    name_path.join("."),
+                    "{} does not contain the field `{}`{}",
+                    describe_name_path(root, &name_path),
                     field,
+                    describe_root(root, &name_path),
                 ),
                 Label::new((range.0.bytes(), range.1.bytes())),
-                possible_standard_library_notes(
-                    &name_path_with_field,
-                    self.user_set_standard_library,
-                ),
+                match root.struct_name {
+                    Some(_) => Vec::new(),
+                    None => possible_standard_library_notes(
+                        &name_path_with_field,
+                        self.user_set_standard_library,
+                    ),
+                },
                 Vec::new(),
             ));
         }
     }
 }
 
+impl StandardLibraryVisitor<'_> {
+    // Struct fields named in their struct, like `Parent`, are properties holding an instance, and can be set.
+    // Ones only matched by `*`, like the children of an instance, can't be.
+    fn is_named_struct_field(&self, root: &NamePathRoot, name_path: &[String]) -> bool {
+        let (field_name, parent_path) = name_path.split_last().unwrap();
+
+        let parent_struct = if parent_path.len() == root.length {
+            root.struct_name.as_deref()
+        } else {
+            match self.std_types.find_field(root, parent_path) {
+                Some(Field {
+                    field_kind: FieldKind::Struct(struct_name),
+                    ..
+                }) => Some(struct_name.as_str()),
+                _ => None,
+            }
+        };
+
+        parent_struct
+            .and_then(|struct_name| self.standard_library.structs.get(struct_name))
+            .is_some_and(|strukt| strukt.contains_key(field_name))
+    }
+}
+
+fn describe_name_path(root: &NamePathRoot, name_path: &[String]) -> String {
+    match root.struct_name {
+        Some(_) => format!("`{}`", name_path.join(".")),
+        None => format!("standard library global `{}`", name_path.join(".")),
+    }
+}
+
+fn describe_root(root: &NamePathRoot, name_path: &[String]) -> String {
+    match &root.struct_name {
+        Some(struct_name) => format!(
+            ", `{}` is a standard library `{struct_name}`",
+            name_path[..root.length].join(".")
+        ),
+        None => String::new(),
+    }
+}
+
 impl Visitor for StandardLibraryVisitor<'_> {
     fn visit_assignment(&mut self, assignment: &ast::Assignment) {
         for var in assignment.variables() {
-            if let Some(reference) = self
+            let is_local = self
                 .scope_manager
                 .reference_at_byte(var.start_position().unwrap().bytes())
-            {
-                if reference.resolved.is_some() {
-                    return;
-                }
-            }
+                .is_some_and(|reference| reference.resolved.is_some());
 
             match var {
                 ast::Var::Expression(var_expr) => {
@@ -295,7 +349,14 @@ impl Visitor for StandardLibraryVisitor<'_> {
                     if let Some(name_path) =
                         name_path_from_prefix_suffix(var_expr.prefix(), var_expr.suffixes())
                     {
-                        match self.standard_library.find_global(&name_path) {
+                        let Some(root) = self
+                            .std_types
+                            .name_path_root(var_expr.start_position().unwrap().bytes(), &name_path)
+                        else {
+                            return;
+                        };
+
+                        match self.std_types.find_field(&root, &name_path) {
                             Some(field) => {
                                 match field.field_kind {
                                     FieldKind::Property(writability) => {
@@ -306,6 +367,11 @@ impl Visitor for StandardLibraryVisitor<'_> {
                                         }
                                     }
                                     FieldKind::Any => continue,
+                                    FieldKind::Struct(_)
+                                        if self.is_named_struct_field(&root, &name_path) =>
+                                    {
+                                        continue
+                                    }
                                     _ => {}
                                 };
 
@@ -314,8 +380,9 @@ impl Visitor for StandardLibraryVisitor<'_> {
                                 self.diagnostics.push(Diagnostic::new_complete(
                                     "incorrect_standard_library_use",
                                     format!(
-                                        "standard library global `{}` is not writable",
-                                        name_path.join("."),
+                                        "{} is not writable{}",
+                                        describe_name_path(&root, &name_path),
+                                        describe_root(&root, &name_path),
                                     ),
                                     Label::new((range.0.bytes(), range.1.bytes())),
                                     Vec::new(),
@@ -325,6 +392,7 @@ impl Visitor for StandardLibraryVisitor<'_> {
 
                             None => {
                                 self.lint_invalid_field_access(
+                                    &root,
                                     name_path,
                                     var_expr.range().unwrap(),
                                 );
@@ -333,6 +401,8 @@ impl Visitor for StandardLibraryVisitor<'_> {
                     }
                 }
 
+                ast::Var::Name(_) if is_local => return,
+
                 ast::Var::Name(name_token) => {
                     let name = name_token.token().to_string();
 
@@ -367,30 +437,19 @@ impl Visitor for StandardLibraryVisitor<'_> {
     }
 
     fn visit_expression(&mut self, expression: &ast::Expression) {
-        if let Some(reference) = self
-            .scope_manager
-            .reference_at_byte(expression.start_position().unwrap().bytes())
-        {
-            if reference.resolved.is_some() {
-                return;
-            }
-        }
+        let Some(name_path) = name_path(expression) else {
+            return;
+        };
 
-        if let Some(name_path) = name_path(expression) {
-            self.lint_invalid_field_access(name_path, expression.range().unwrap());
+        if let Some(root) = self
+            .std_types
+            .name_path_root(expression.start_position().unwrap().bytes(), &name_path)
+        {
+            self.lint_invalid_field_access(&root, name_path, expression.range().unwrap());
         }
     }
 
     fn visit_function_call(&mut self, call: &ast::FunctionCall) {
-        if let Some(reference) = self
-            .scope_manager
-            .reference_at_byte(call.start_position().unwrap().bytes())
-        {
-            if reference.resolved.is_some() {
-                return;
-            }
-        }
-
         let mut keep_going = true;
         let mut suffixes: Vec<&ast::Suffix> = call
             .suffixes()
@@ -405,10 +464,19 @@ impl Visitor for StandardLibraryVisitor<'_> {
 
         let call_suffix = suffixes.pop().unwrap();
 
-        let field = match self.standard_library.find_global(&name_path) {
+        // Method calls on locals, like `local ws = workspace; ws:FindFirstChild("Part")`, are checked too
+        let Some(root) = self
+            .std_types
+            .name_path_root(call.start_position().unwrap().bytes(), &name_path)
+        else {
+            return;
+        };
+
+        let field = match self.std_types.find_field(&root, &name_path) {
             Some(field) => field,
             None => {
                 self.lint_invalid_field_access(
+                    &root,
                     name_path,
                     (
                         call.prefix().start_position().unwrap(),
@@ -508,7 +576,10 @@ impl Visitor for StandardLibraryVisitor<'_> {
             }
 
             ast::FunctionArgs::TableConstructor(table) => {
-                argument_types.push((table.range().unwrap(), Some(ArgumentType::Table.into())));
+                argument_types.push((
+                    table.range().unwrap(),
+                    Some(PassedArgumentType::from_table_constructor(table)),
+                ));
             }
 
             _ => {}
@@ -546,7 +617,7 @@ impl Visitor for StandardLibraryVisitor<'_> {
         };
 
         if let Some(last) = function.arguments.last() {
-            if last.argument_type == ArgumentType::Vararg {
+            if last.argument_type.is_vararg() {
                 if let Required::Required(message) = &last.required {
                     // Functions like math.ceil where not using the vararg is wrong
                     if function.arguments.len() > argument_types.len() && !maybe_more_arguments {
@@ -600,11 +671,21 @@ impl Visitor for StandardLibraryVisitor<'_> {
             ));
         }
 
-        for ((range, passed_type), expected) in argument_types.iter().zip(function.arguments.iter())
-        {
-            if expected.argument_type == ArgumentType::Vararg {
-                continue;
-            }
+        let vararg_argument = function
+            .arguments
+            .last()
+            .filter(|argument| argument.argument_type.is_vararg());
+
+        for (index, (range, passed_type)) in argument_types.iter().enumerate() {
+            let Some(expected) = function.arguments.get(index).or(vararg_argument) else {
+                break;
+            };
+
+            let expected_type = match &expected.argument_type {
+                ArgumentType::Vararg => continue,
+                ArgumentType::VarargOf(argument_type) => argument_type,
+                argument_type => argument_type,
+            };
 
             if let Some(passed_type) = passed_type {
                 // Allow nil for unrequired arguments
@@ -614,7 +695,7 @@ impl Visitor for StandardLibraryVisitor<'_> {
                     continue;
                 }
 
-                let matches = passed_type.matches(&expected.argument_type);
+                let matches = passed_type.matches(expected_type, self.standard_library);
 
                 if !matches {
                     self.diagnostics.push(Diagnostic::new(
@@ -627,7 +708,7 @@ impl Visitor for StandardLibraryVisitor<'_> {
                             (range.0.bytes() as u32, range.1.bytes() as u32),
                             format!(
                                 "expected `{}`, received `{}`",
-                                expected.argument_type,
+                                expected_type,
                                 passed_type.type_name()
                             ),
                         ),
@@ -640,8 +721,16 @@ impl Visitor for StandardLibraryVisitor<'_> {
 
 #[derive(Debug, PartialEq, Eq)]
 enum PassedArgumentType {
+    Function { parameters: usize, vararg: bool },
     Primitive(ArgumentType),
     String(String),
+    Table(Vec<PassedTableField>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct PassedTableField {
+    key: Option<PassedArgumentType>,
+    value: Option<PassedArgumentType>,
 }
 
 impl PassedArgumentType {
@@ -650,23 +739,131 @@ impl PassedArgumentType {
         PassedArgumentType::String(string.chars().skip(1).collect())
     }
 
-    fn matches(&self, argument_type: &ArgumentType) -> bool {
-        if argument_type == &ArgumentType::Any {
-            return true;
+    fn from_function_body(body: &ast::FunctionBody) -> PassedArgumentType {
+        let mut parameters = 0;
+        let mut vararg = false;
+
+        for parameter in body.parameters() {
+            match parameter {
+                ast::Parameter::Ellipsis(_) => vararg = true,
+                _ => parameters += 1,
+            }
+        }
+
+        PassedArgumentType::Function { parameters, vararg }
+    }
+
+    fn from_table_constructor(table: &ast::TableConstructor) -> PassedArgumentType {
+        PassedArgumentType::Table(
+            table
+                .fields()
+                .iter()
+                .map(|field| {
+                    #[cfg_attr(
+                        feature = "force_exhaustive_checks",
+                        deny(non_exhaustive_omitted_patterns)
+                    )]
+                    match field {
+                        ast::Field::ExpressionKey { key, value, .. } => PassedTableField {
+                            key: get_argument_type(key),
+                            value: get_argument_type(value),
+                        },
+
+                        ast::Field::NameKey { key, value, .. } => PassedTableField {
+                            key: Some(PassedArgumentType::String(key.token().to_string())),
+                            value: get_argument_type(value),
+                        },
+
+                        ast::Field::NoKey(value) => PassedTableField {
+                            key: Some(ArgumentType::Number.into()),
+                            value: get_argument_type(value),
+                        },
+
+                        _ => PassedTableField {
+                            key: None,
+                            value: None,
+                        },
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    fn matches(&self, argument_type: &ArgumentType, standard_library: &StandardLibrary) -> bool {
+        match argument_type {
+            ArgumentType::Any => return true,
+
+            ArgumentType::Nilable(argument_type) => {
+                return self == &PassedArgumentType::Primitive(ArgumentType::Nil)
+                    || self.matches(argument_type, standard_library);
+            }
+
+            ArgumentType::Union(argument_types) => {
+                return argument_types
+                    .iter()
+                    .any(|argument_type| self.matches(argument_type, standard_library));
+            }
+
+            _ => {}
         }
 
         match self {
+            PassedArgumentType::Function { parameters, vararg } => match argument_type {
+                ArgumentType::Function => true,
+
+                // Parameters past the ones the function is called with would always be nil
+                ArgumentType::FunctionSignature(expected_parameters) => {
+                    *vararg
+                        || expected_parameters.last().map(String::as_str) == Some("...")
+                        || *parameters <= expected_parameters.len()
+                }
+
+                _ => false,
+            },
+
             PassedArgumentType::Primitive(us) => {
                 us == &ArgumentType::Vararg
                     || us == argument_type
                     || (us == &ArgumentType::String
                         && matches!(argument_type, ArgumentType::Constant(_)))
             }
+
             PassedArgumentType::String(text) => match argument_type {
                 ArgumentType::Constant(constants) => constants.contains(text),
                 ArgumentType::String => true,
                 _ => false,
             },
+
+            PassedArgumentType::Table(fields) => match argument_type {
+                ArgumentType::Table => true,
+
+                ArgumentType::TableOf(key_type, value_type) => fields.iter().all(|field| {
+                    field
+                        .key
+                        .as_ref()
+                        .is_none_or(|key| key.matches(key_type, standard_library))
+                        && field
+                            .value
+                            .as_ref()
+                            .is_none_or(|value| value.matches(value_type, standard_library))
+                }),
+
+                // Tables can only have the fields the struct has
+                ArgumentType::Struct(name) => match standard_library.structs.get(name) {
+                    Some(struct_fields) if !struct_fields.contains_key("*") => {
+                        fields.iter().all(|field| match &field.key {
+                            Some(PassedArgumentType::String(key)) => {
+                                struct_fields.contains_key(key)
+                            }
+                            _ => true,
+                        })
+                    }
+
+                    _ => true,
+                },
+
+                _ => false,
+            },
         }
     }
 
@@ -674,16 +871,24 @@ impl PassedArgumentType {
     #[allow(dead_code)]
     fn same_type(&self, other: &PassedArgumentType) -> bool {
         match (self, other) {
+            (PassedArgumentType::Function { .. }, PassedArgumentType::Function { .. }) => true,
             (PassedArgumentType::Primitive(a), PassedArgumentType::Primitive(b)) => a == b,
             (PassedArgumentType::String(_), PassedArgumentType::String(_)) => true,
+            (PassedArgumentType::Table(_), PassedArgumentType::Table(_)) => true,
             _ => false,
         }
     }
 
     fn type_name(&self) -> String {
         match self {
+            PassedArgumentType::Function {
+                parameters,
+                vararg: false,
+            } => format!("function with {parameters} parameters"),
+            PassedArgumentType::Function { vararg: true, .. } => ArgumentType::Function.to_string(),
             PassedArgumentType::Primitive(argument_type) => argument_type.to_string(),
             PassedArgumentType::String(_) => ArgumentType::String.to_string(),
+            PassedArgumentType::Table(_) => ArgumentType::Table.to_string(),
         }
     }
 }
@@ -822,6 +1027,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_local_method_calls() {
+        test_lint(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "local_method_calls",
+        );
+    }
+
+    #[test]
+    fn test_returns() {
+        test_lint(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "returns",
+        );
+    }
+
     #[test]
     fn test_shadowing() {
         test_lint(
@@ -840,6 +1063,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_typed_arguments() {
+        test_lint(
+            StandardLibraryLint::new(()).unwrap(),
+            "standard_library",
+            "typed_arguments",
+        );
+    }
+
     #[test]
     fn test_unknown_property() {
         test_lint(
@@ -0,0 +1,157 @@
+use super::*;
+use crate::ast_util::{loop_tracker::LoopTracker, purge_trivia, range, scopes::ScopeManager};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct StringConcatInLoopLint;
+
+impl Lint for StringConcatInLoopLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Performance;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(StringConcatInLoopLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = StringConcatInLoopVisitor {
+            loop_tracker: &ast_context.loop_tracker,
+            scope_manager: &ast_context.scope_manager,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct StringConcatInLoopVisitor<'a> {
+    loop_tracker: &'a LoopTracker,
+    scope_manager: &'a ScopeManager,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl StringConcatInLoopVisitor<'_> {
+    // Whether the variable keeps its value across iterations of the loop the statement is in
+    fn accumulates<N: Node>(&self, statement: &N, var: &ast::Var) -> bool {
+        let Some(tracked_loop) = self.loop_tracker.innermost_loop_at_byte(range(statement).0)
+        else {
+            return false;
+        };
+
+        // Fields, like `self.buffer`, always outlive the loop
+        let ast::Var::Name(name) = var else {
+            return true;
+        };
+
+        let Some(variable) = self
+            .scope_manager
+            .reference_at_byte(range(name).0)
+            .and_then(|reference| reference.resolved)
+        else {
+            return true;
+        };
+
+        let (declared, _) = self.scope_manager.variables[variable].identifiers[0];
+
+        declared < tracked_loop.block.0 || declared > tracked_loop.block.1
+    }
+
+    fn report<N: Node>(&mut self, statement: &N, var: &ast::Var) {
+        let var_text = var.to_string();
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "string_concat_in_loop",
+            format!(
+                "`{}` is built up with `..` inside of a loop",
+                var_text.trim()
+            ),
+            Label::new(range::<_, usize>(statement)),
+            vec![
+                "every `..` copies the whole string, so this gets slower the longer it gets"
+                    .to_owned(),
+                "try: collecting the pieces in a table, then joining them with `table.concat`"
+                    .to_owned(),
+            ],
+            Vec::new(),
+        ));
+    }
+}
+
+impl Visitor for StringConcatInLoopVisitor<'_> {
+    fn visit_assignment(&mut self, assignment: &ast::Assignment) {
+        for (var, expression) in assignment
+            .variables()
+            .iter()
+            .zip(assignment.expressions().iter())
+        {
+            // `..` is right associative, so `s .. a .. b` is `s .. (a .. b)`
+            let ast::Expression::BinaryOperator {
+                lhs,
+                binop: ast::BinOp::TwoDots(_),
+                ..
+            } = expression
+            else {
+                continue;
+            };
+
+            let ast::Expression::Var(concatenated) = lhs.as_ref() else {
+                continue;
+            };
+
+            if purge_trivia(concatenated).to_string() != purge_trivia(var).to_string() {
+                continue;
+            }
+
+            if self.accumulates(assignment, var) {
+                self.report(assignment, var);
+            }
+        }
+    }
+
+    #[cfg(feature = "roblox")]
+    fn visit_compound_assignment(&mut self, compound_assignment: &ast::CompoundAssignment) {
+        if !matches!(
+            compound_assignment.compound_operator(),
+            ast::CompoundOp::TwoDotsEqual(_)
+        ) {
+            return;
+        }
+
+        if self.accumulates(compound_assignment, compound_assignment.lhs()) {
+            self.report(compound_assignment, compound_assignment.lhs());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_string_concat_in_loop() {
+        test_lint(
+            StringConcatInLoopLint::new(()).unwrap(),
+            "string_concat_in_loop",
+            "string_concat_in_loop",
+        );
+    }
+
+    #[cfg(feature = "roblox")]
+    #[test]
+    fn test_compound_assignment() {
+        test_lint(
+            StringConcatInLoopLint::new(()).unwrap(),
+            "string_concat_in_loop",
+            "compound_assignment",
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use super::*;
+use crate::ast_util::range;
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct SuspiciousPrecedenceLint;
+
+impl Lint for SuspiciousPrecedenceLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(SuspiciousPrecedenceLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = SuspiciousPrecedenceVisitor {
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct SuspiciousPrecedenceVisitor {
+    diagnostics: Vec<Diagnostic>,
+}
+
+fn text<N: std::fmt::Display>(node: &N) -> String {
+    node.to_string().trim().to_owned()
+}
+
+fn is_comparison(binop: &ast::BinOp) -> bool {
+    matches!(
+        binop,
+        ast::BinOp::TwoEqual(_)
+            | ast::BinOp::TildeEqual(_)
+            | ast::BinOp::GreaterThan(_)
+            | ast::BinOp::GreaterThanEqual(_)
+            | ast::BinOp::LessThan(_)
+            | ast::BinOp::LessThanEqual(_)
+    )
+}
+
+// `+`, `-`, `*`, `/`, `//`, `%`, and `^`, which are all evaluated before `..`
+fn is_arithmetic(expression: &ast::Expression) -> bool {
+    let ast::Expression::BinaryOperator { binop, .. } = expression else {
+        return false;
+    };
+
+    #[cfg(any(feature = "lua53", feature = "roblox"))]
+    if let ast::BinOp::DoubleSlash(_) = binop {
+        return true;
+    }
+
+    matches!(
+        binop,
+        ast::BinOp::Plus(_)
+            | ast::BinOp::Minus(_)
+            | ast::BinOp::Star(_)
+            | ast::BinOp::Slash(_)
+            | ast::BinOp::Percent(_)
+            | ast::BinOp::Caret(_)
+    )
+}
+
+fn parenthesize_arithmetic(expression: &ast::Expression) -> String {
+    if is_arithmetic(expression) {
+        format!("({})", text(expression))
+    } else {
+        text(expression)
+    }
+}
+
+impl SuspiciousPrecedenceVisitor {
+    fn push(&mut self, expression: &ast::Expression, parsed: String, notes: Vec<String>) {
+        self.diagnostics.push(Diagnostic::new_complete(
+            "suspicious_precedence",
+            format!("`{}` is evaluated as `{parsed}`", text(expression)),
+            Label::new(range::<_, usize>(expression)),
+            notes,
+            Vec::new(),
+        ));
+    }
+}
+
+impl Visitor for SuspiciousPrecedenceVisitor {
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        match expression {
+            // `not x == y`
+            ast::Expression::BinaryOperator { lhs, binop, rhs } if is_comparison(binop) => {
+                let ast::Expression::UnaryOperator {
+                    unop: ast::UnOp::Not(_),
+                    expression: operand,
+                } = lhs.as_ref()
+                else {
+                    return;
+                };
+
+                let (operand, binop, rhs) = (text(operand), text(binop), text(rhs));
+
+                self.push(
+                    expression,
+                    format!("(not {operand}) {binop} {rhs}"),
+                    vec![format!("try: `not ({operand} {binop} {rhs})` instead")],
+                );
+            }
+
+            // `x .. y + z`
+            ast::Expression::BinaryOperator {
+                lhs,
+                binop: ast::BinOp::TwoDots(_),
+                rhs,
+            } if is_arithmetic(lhs) || is_arithmetic(rhs) => {
+                let parsed = format!(
+                    "{} .. {}",
+                    parenthesize_arithmetic(lhs),
+                    parenthesize_arithmetic(rhs)
+                );
+
+                self.push(
+                    expression,
+                    parsed.clone(),
+                    vec![format!(
+                        "try: `{parsed}` instead, to make the order explicit"
+                    )],
+                );
+            }
+
+            // `-x ^ 2`
+            ast::Expression::UnaryOperator {
+                unop: ast::UnOp::Minus(_),
+                expression: operand,
+            } => {
+                let ast::Expression::BinaryOperator {
+                    lhs,
+                    binop: ast::BinOp::Caret(_),
+                    rhs,
+                } = operand.as_ref()
+                else {
+                    return;
+                };
+
+                let (lhs, rhs) = (text(lhs), text(rhs));
+
+                self.push(
+                    expression,
+                    format!("-({lhs} ^ {rhs})"),
+                    vec![format!(
+                        "try: `(-{lhs}) ^ {rhs}` if you meant to raise a negative number, or `-({lhs} ^ {rhs})` to make the order explicit"
+                    )],
+                );
+            }
+
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_suspicious_precedence() {
+        test_lint(
+            SuspiciousPrecedenceLint::new(()).unwrap(),
+            "suspicious_precedence",
+            "suspicious_precedence",
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use super::*;
+use crate::ast_util::{constant::Constant, loop_tracker::LoopTracker, range, scopes::ScopeManager};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct TableInsertFrontInLoopLint;
+
+impl Lint for TableInsertFrontInLoopLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Performance;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(TableInsertFrontInLoopLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = TableInsertFrontInLoopVisitor {
+            loop_tracker: &ast_context.loop_tracker,
+            scope_manager: &ast_context.scope_manager,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct TableInsertFrontInLoopVisitor<'a> {
+    loop_tracker: &'a LoopTracker,
+    scope_manager: &'a ScopeManager,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Visitor for TableInsertFrontInLoopVisitor<'_> {
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        let ast::Prefix::Name(table) = call.prefix() else {
+            return;
+        };
+
+        if table.token().to_string() != "table"
+            || self
+                .scope_manager
+                .reference_at_byte(range(table).0)
+                .is_some_and(|reference| reference.resolved.is_some())
+        {
+            return;
+        }
+
+        let mut suffixes = call.suffixes();
+
+        let (
+            Some(ast::Suffix::Index(ast::Index::Dot { name, .. })),
+            Some(ast::Suffix::Call(ast::Call::AnonymousCall(ast::FunctionArgs::Parentheses {
+                arguments,
+                ..
+            }))),
+            None,
+        ) = (suffixes.next(), suffixes.next(), suffixes.next())
+        else {
+            return;
+        };
+
+        if name.token().to_string() != "insert" || arguments.len() != 3 {
+            return;
+        }
+
+        let position = arguments.iter().nth(1).unwrap();
+
+        if Constant::evaluate(position) != Some(Constant::Number(1.0)) {
+            return;
+        }
+
+        let call_range = range(call);
+
+        if self
+            .loop_tracker
+            .innermost_loop_at_byte(call_range.0)
+            .is_none()
+        {
+            return;
+        }
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "table_insert_front_in_loop",
+            "inserting at the front of a table inside of a loop moves every element each time"
+                .to_owned(),
+            Label::new(call_range),
+            vec!["try: inserting at the end, then reversing the table after the loop".to_owned()],
+            Vec::new(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_table_insert_front_in_loop() {
+        test_lint(
+            TableInsertFrontInLoopLint::new(()).unwrap(),
+            "table_insert_front_in_loop",
+            "table_insert_front_in_loop",
+        );
+    }
+}
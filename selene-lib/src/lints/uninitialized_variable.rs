@@ -0,0 +1,448 @@
+use super::*;
+use crate::{
+    ast_util::{
+        control_flow::{guarded_variables, ControlFlowGraph, EdgeKind, NodeKind},
+        range,
+        scopes::{Reference, ReferenceWrite, ScopeManager, Variable},
+        strip_parentheses,
+    },
+    standard_library::StandardLibrary,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+};
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+use id_arena::Id;
+
+pub struct UninitializedVariableLint;
+
+impl Lint for UninitializedVariableLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(UninitializedVariableLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let scope_manager = &ast_context.scope_manager;
+
+        let mut read_positions = ReadPositionsVisitor::default();
+        read_positions.visit_ast(ast);
+
+        let owner_of = |byte: usize| {
+            read_positions
+                .function_bodies
+                .iter()
+                .rev()
+                .find(|(start, end)| *start <= byte && byte < *end)
+                .copied()
+        };
+
+        let mut variables_by_identifier = HashMap::new();
+        for (id, variable) in scope_manager.variables.iter() {
+            if let Some(identifier) = variable.identifiers.first() {
+                variables_by_identifier.insert(identifier.0, id);
+            }
+        }
+
+        // Locals given a value by a closure could be assigned at any time, so can't be tracked
+        let mut written_by_closures = HashSet::new();
+        let mut references_by_owner: HashMap<_, Vec<_>> = HashMap::new();
+
+        for (id, reference) in scope_manager.references.iter() {
+            let owner = owner_of(reference.identifier.0);
+
+            if let Some(variable_id) = reference.resolved {
+                let variable = &scope_manager.variables[variable_id];
+
+                if reference.write == Some(ReferenceWrite::Assign)
+                    && variable
+                        .identifiers
+                        .first()
+                        .is_some_and(|identifier| owner_of(identifier.0) != owner)
+                {
+                    written_by_closures.insert(variable_id);
+                }
+            }
+
+            references_by_owner.entry(owner).or_default().push(id);
+        }
+
+        let mut visitor = UninitializedVariableVisitor {
+            scope_manager,
+            standard_library: &context.standard_library,
+            nil_erroring_reads: &read_positions.nil_erroring_reads,
+            function_declaration_names: &read_positions.function_declaration_names,
+            variables_by_identifier: &variables_by_identifier,
+            written_by_closures: &written_by_closures,
+            references_by_owner: &references_by_owner,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.check_function_body(None, ast.nodes());
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+/// The locals declared without a value that haven't been given one yet.
+#[derive(Clone, Default, PartialEq)]
+struct Uninitialized {
+    // On at least one path
+    maybe: HashSet<Id<Variable>>,
+
+    // On every path
+    definitely: HashSet<Id<Variable>>,
+}
+
+#[derive(Default)]
+struct NodeEvents {
+    reads: Vec<(Id<Reference>, Id<Variable>)>,
+    assigns: Vec<Id<Variable>>,
+    declares: Vec<Id<Variable>>,
+}
+
+struct UninitializedVariableVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+    nil_erroring_reads: &'a HashSet<usize>,
+    function_declaration_names: &'a HashSet<usize>,
+    variables_by_identifier: &'a HashMap<usize, Id<Variable>>,
+    written_by_closures: &'a HashSet<Id<Variable>>,
+    references_by_owner: &'a HashMap<Option<(usize, usize)>, Vec<Id<Reference>>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl UninitializedVariableVisitor<'_> {
+    fn check_function_body(&mut self, owner: Option<(usize, usize)>, block: &ast::Block) {
+        let graph = ControlFlowGraph::new(block, self.scope_manager, self.standard_library);
+
+        let events = self.node_events(&graph, owner);
+        if events.values().all(|events| events.declares.is_empty()) {
+            return;
+        }
+
+        let states = graph.forward_dataflow(
+            Uninitialized::default(),
+            |node, edge_kind, state| {
+                let mut state = state.clone();
+
+                if let Some(events) = node.kind.range().and_then(|range| events.get(&range)) {
+                    self.visit_events(events, &mut state, false);
+                }
+
+                // `if x then`, `while x == nil do`, and so on
+                if let Some(condition) = node.condition() {
+                    let truthy = match edge_kind {
+                        EdgeKind::IfTrue => true,
+                        EdgeKind::IfFalse => false,
+                        EdgeKind::Always => return state,
+                    };
+
+                    for variable in guarded_variables(self.scope_manager, condition, truthy) {
+                        state.maybe.remove(&variable);
+                        state.definitely.remove(&variable);
+                    }
+                }
+
+                state
+            },
+            |state, other_state| {
+                state.maybe.extend(other_state.maybe);
+                state
+                    .definitely
+                    .retain(|variable| other_state.definitely.contains(variable));
+            },
+        );
+
+        let mut nodes = states.into_iter().collect::<Vec<_>>();
+        nodes.sort_by_key(|(id, _)| graph.node(*id).kind.range());
+
+        for (id, mut state) in nodes {
+            if let Some(events) = graph
+                .node(id)
+                .kind
+                .range()
+                .and_then(|range| events.get(&range))
+            {
+                self.visit_events(events, &mut state, true);
+            }
+        }
+    }
+
+    // Finds what happens to the locals declared without a value at every node.
+    fn node_events(
+        &self,
+        graph: &ControlFlowGraph,
+        owner: Option<(usize, usize)>,
+    ) -> HashMap<(usize, usize), NodeEvents> {
+        let mut node_ranges = graph
+            .nodes()
+            .filter_map(|(_, node)| node.kind.range())
+            .collect::<Vec<_>>();
+
+        node_ranges.sort();
+
+        let mut events: HashMap<(usize, usize), NodeEvents> = HashMap::new();
+
+        for (_, node) in graph.nodes() {
+            if let NodeKind::Stmt(ast::Stmt::LocalAssignment(local_assignment)) = node.kind {
+                if local_assignment.expressions().is_empty() {
+                    let declares = local_assignment
+                        .names()
+                        .iter()
+                        .filter_map(|name| self.variables_by_identifier.get(&range(name).0))
+                        .filter(|variable| !self.written_by_closures.contains(variable))
+                        .copied()
+                        .collect::<Vec<_>>();
+
+                    events
+                        .entry(node.kind.range().unwrap())
+                        .or_default()
+                        .declares
+                        .extend(declares);
+                }
+            }
+        }
+
+        for &reference_id in self.references_by_owner.get(&owner).into_iter().flatten() {
+            let reference = &self.scope_manager.references[reference_id];
+
+            let Some(variable) = reference.resolved else {
+                continue;
+            };
+
+            let byte = reference.identifier.0;
+            let index = node_ranges.partition_point(|(start, _)| *start <= byte);
+            let Some(&node_range) = index.checked_sub(1).map(|index| &node_ranges[index]) else {
+                continue;
+            };
+
+            if byte >= node_range.1 {
+                continue;
+            }
+
+            let node_events = events.entry(node_range).or_default();
+
+            // `function helper()` only gives `helper` a value, even though it's also counted as a read
+            let is_function_declaration_name = self.function_declaration_names.contains(&byte);
+
+            if (reference.read && !is_function_declaration_name) || reference.indexing.is_some() {
+                node_events.reads.push((reference_id, variable));
+            }
+
+            if reference.write == Some(ReferenceWrite::Assign) && reference.indexing.is_none() {
+                node_events.assigns.push(variable);
+            }
+        }
+
+        events
+    }
+
+    // Reads happen before any assignments in the same statement, such as with `x = x + 1`.
+    // Diagnostics are only made on the last pass, once the analysis has settled.
+    fn visit_events(&mut self, events: &NodeEvents, state: &mut Uninitialized, report: bool) {
+        for &(reference_id, variable_id) in &events.reads {
+            let reference = &self.scope_manager.references[reference_id];
+            let nil_erroring = reference.indexing.is_some()
+                || self.nil_erroring_reads.contains(&reference.identifier.0);
+
+            if report {
+                if state.definitely.contains(&variable_id) {
+                    self.report(
+                        reference,
+                        variable_id,
+                        format!("`{}` is used before it's given a value", reference.name),
+                    );
+                } else if nil_erroring && state.maybe.contains(&variable_id) {
+                    self.report(
+                        reference,
+                        variable_id,
+                        format!("`{}` might not have been given a value yet", reference.name),
+                    );
+                }
+            }
+
+            // It would have errored if it was nil, so it's not worth reporting again
+            if nil_erroring {
+                state.maybe.remove(&variable_id);
+                state.definitely.remove(&variable_id);
+            }
+        }
+
+        for variable_id in &events.assigns {
+            state.maybe.remove(variable_id);
+            state.definitely.remove(variable_id);
+        }
+
+        for &variable_id in &events.declares {
+            state.maybe.insert(variable_id);
+            state.definitely.insert(variable_id);
+        }
+    }
+
+    fn report(&mut self, reference: &Reference, variable_id: Id<Variable>, message: String) {
+        let variable = &self.scope_manager.variables[variable_id];
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "uninitialized_variable",
+            message,
+            Label::new(reference.identifier),
+            Vec::new(),
+            variable
+                .identifiers
+                .first()
+                .map(|identifier| {
+                    Label::new_with_message(*identifier, "declared here without a value".to_owned())
+                })
+                .into_iter()
+                .collect(),
+        ));
+    }
+}
+
+impl Visitor for UninitializedVariableVisitor<'_> {
+    fn visit_function_body(&mut self, function_body: &ast::FunctionBody) {
+        self.check_function_body(Some(range(function_body)), function_body.block());
+    }
+}
+
+/// Finds the function bodies, and which names are used somewhere that would error if they were nil.
+#[derive(Default)]
+struct ReadPositionsVisitor {
+    function_bodies: Vec<(usize, usize)>,
+    nil_erroring_reads: HashSet<usize>,
+
+    // The names of `function x()`, but not `function x.y()` or `function x:y()`
+    function_declaration_names: HashSet<usize>,
+}
+
+impl ReadPositionsVisitor {
+    fn add_name(&mut self, token: &TokenReference) {
+        self.nil_erroring_reads.insert(range(token).0);
+    }
+
+    fn add_expression(&mut self, expression: &ast::Expression) {
+        if let ast::Expression::Var(ast::Var::Name(name)) = strip_parentheses(expression) {
+            self.add_name(name);
+        }
+    }
+}
+
+impl Visitor for ReadPositionsVisitor {
+    fn visit_function_body(&mut self, function_body: &ast::FunctionBody) {
+        self.function_bodies.push(range(function_body));
+    }
+
+    fn visit_expression(&mut self, expression: &ast::Expression) {
+        match expression {
+            ast::Expression::BinaryOperator { lhs, binop, rhs } => {
+                if matches!(
+                    binop,
+                    ast::BinOp::Plus(_)
+                        | ast::BinOp::Minus(_)
+                        | ast::BinOp::Star(_)
+                        | ast::BinOp::Slash(_)
+                        | ast::BinOp::Percent(_)
+                        | ast::BinOp::Caret(_)
+                        | ast::BinOp::TwoDots(_)
+                        | ast::BinOp::LessThan(_)
+                        | ast::BinOp::LessThanEqual(_)
+                        | ast::BinOp::GreaterThan(_)
+                        | ast::BinOp::GreaterThanEqual(_)
+                ) {
+                    self.add_expression(lhs);
+                    self.add_expression(rhs);
+                }
+            }
+
+            ast::Expression::UnaryOperator { unop, expression } => {
+                if matches!(unop, ast::UnOp::Minus(_) | ast::UnOp::Hash(_)) {
+                    self.add_expression(expression);
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    fn visit_function_call(&mut self, call: &ast::FunctionCall) {
+        if let ast::Prefix::Name(name) = call.prefix() {
+            self.add_name(name);
+        }
+    }
+
+    fn visit_function_declaration(&mut self, declaration: &ast::FunctionDeclaration) {
+        let name = declaration.name();
+
+        if name.names().len() == 1 && name.method_colon().is_none() {
+            let base = name.names().iter().next().unwrap();
+            self.function_declaration_names.insert(range(base).0);
+        }
+    }
+
+    fn visit_var_expression(&mut self, var_expression: &ast::VarExpression) {
+        if let ast::Prefix::Name(name) = var_expression.prefix() {
+            self.add_name(name);
+        }
+    }
+
+    fn visit_numeric_for(&mut self, numeric_for: &ast::NumericFor) {
+        self.add_expression(numeric_for.start());
+        self.add_expression(numeric_for.end());
+
+        if let Some(step) = numeric_for.step() {
+            self.add_expression(step);
+        }
+    }
+
+    #[cfg(feature = "roblox")]
+    fn visit_compound_assignment(&mut self, compound_assignment: &ast::CompoundAssignment) {
+        if let ast::Var::Name(name) = compound_assignment.lhs() {
+            self.add_name(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_uninitialized_variable() {
+        test_lint(
+            UninitializedVariableLint::new(()).unwrap(),
+            "uninitialized_variable",
+            "uninitialized_variable",
+        );
+    }
+
+    #[test]
+    fn test_forward_declaration() {
+        test_lint(
+            UninitializedVariableLint::new(()).unwrap(),
+            "uninitialized_variable",
+            "forward_declaration",
+        );
+    }
+
+    #[test]
+    fn test_loops() {
+        test_lint(
+            UninitializedVariableLint::new(()).unwrap(),
+            "uninitialized_variable",
+            "loops",
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use super::*;
+use crate::{
+    ast_util::{control_flow::ControlFlowGraph, range, scopes::ScopeManager},
+    standard_library::StandardLibrary,
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct UnreachableCodeLint;
+
+impl Lint for UnreachableCodeLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(UnreachableCodeLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = UnreachableCodeVisitor {
+            scope_manager: &ast_context.scope_manager,
+            standard_library: &context.standard_library,
+            unreachable: Vec::new(),
+        };
+
+        visitor.check_function_body(ast.nodes());
+        visitor.visit_ast(ast);
+
+        let unreachable = visitor.unreachable;
+
+        // Functions inside unreachable code have their own graph, but are already covered
+        unreachable
+            .iter()
+            .filter(|code| {
+                !unreachable.iter().any(|other| {
+                    other.range != code.range
+                        && other.range.0 <= code.range.0
+                        && other.range.1 >= code.range.1
+                })
+            })
+            .map(|code| {
+                Diagnostic::new_complete(
+                    "unreachable_code",
+                    "unreachable code".to_owned(),
+                    Label::new(code.range),
+                    Vec::new(),
+                    code.cause
+                        .map(|cause| {
+                            Label::new_with_message(
+                                cause,
+                                "any code following this is unreachable".to_owned(),
+                            )
+                        })
+                        .into_iter()
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+}
+
+struct UnreachableCode {
+    range: (usize, usize),
+    cause: Option<(usize, usize)>,
+}
+
+struct UnreachableCodeVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+    unreachable: Vec<UnreachableCode>,
+}
+
+impl UnreachableCodeVisitor<'_> {
+    fn check_function_body(&mut self, block: &ast::Block) {
+        let graph = ControlFlowGraph::new(block, self.scope_manager, self.standard_library);
+        self.check_block(&graph, block);
+    }
+
+    fn check_block(&mut self, graph: &ControlFlowGraph, block: &ast::Block) {
+        let mut unreachable_range: Option<(usize, usize)> = None;
+        let mut cause = None;
+
+        for stmt in block.stmts() {
+            let reachable = graph
+                .stmt_node(stmt)
+                .is_none_or(|id| graph.is_reachable(id));
+
+            if !reachable {
+                if is_runnable(stmt) {
+                    let (start, end) = range(stmt);
+                    unreachable_range =
+                        Some((unreachable_range.map_or(start, |(start, _)| start), end));
+                }
+
+                continue;
+            }
+
+            self.flush(&mut unreachable_range, cause);
+            cause = Some(range(stmt));
+
+            match stmt {
+                ast::Stmt::Do(do_) => self.check_block(graph, do_.block()),
+                ast::Stmt::GenericFor(generic_for) => self.check_block(graph, generic_for.block()),
+                ast::Stmt::NumericFor(numeric_for) => self.check_block(graph, numeric_for.block()),
+                ast::Stmt::Repeat(repeat) => self.check_block(graph, repeat.block()),
+                ast::Stmt::While(while_loop) => self.check_block(graph, while_loop.block()),
+
+                ast::Stmt::If(if_block) => {
+                    self.check_block(graph, if_block.block());
+
+                    for else_if in if_block.else_if().into_iter().flatten() {
+                        self.check_block(graph, else_if.block());
+                    }
+
+                    if let Some(else_block) = if_block.else_block() {
+                        self.check_block(graph, else_block);
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        if let Some(last_stmt) = block.last_stmt() {
+            if let Some(id) = graph.last_stmt_node(last_stmt) {
+                if !graph.is_reachable(id) {
+                    let (start, end) = range(last_stmt);
+                    unreachable_range =
+                        Some((unreachable_range.map_or(start, |(start, _)| start), end));
+                }
+            }
+        }
+
+        self.flush(&mut unreachable_range, cause);
+    }
+
+    fn flush(
+        &mut self,
+        unreachable_range: &mut Option<(usize, usize)>,
+        cause: Option<(usize, usize)>,
+    ) {
+        if let Some(range) = unreachable_range.take() {
+            self.unreachable.push(UnreachableCode { range, cause });
+        }
+    }
+}
+
+// Type declarations don't run, so they can come after anything
+fn is_runnable(stmt: &ast::Stmt) -> bool {
+    match stmt {
+        #[cfg(feature = "roblox")]
+        ast::Stmt::ExportedTypeDeclaration(_)
+        | ast::Stmt::TypeDeclaration(_)
+        | ast::Stmt::ExportedTypeFunction(_)
+        | ast::Stmt::TypeFunction(_) => false,
+
+        _ => true,
+    }
+}
+
+impl Visitor for UnreachableCodeVisitor<'_> {
+    fn visit_function_body(&mut self, function_body: &ast::FunctionBody) {
+        self.check_function_body(function_body.block());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_unreachable_code() {
+        test_lint(
+            UnreachableCodeLint::new(()).unwrap(),
+            "unreachable_code",
+            "unreachable_code",
+        );
+    }
+
+    #[test]
+    fn test_loops() {
+        test_lint(
+            UnreachableCodeLint::new(()).unwrap(),
+            "unreachable_code",
+            "loops",
+        );
+    }
+
+    #[cfg(feature = "lua52")]
+    #[test]
+    fn test_goto() {
+        test_lint(
+            UnreachableCodeLint::new(()).unwrap(),
+            "unreachable_code",
+            "goto",
+        );
+    }
+
+    #[cfg(feature = "roblox")]
+    #[test]
+    fn test_continue() {
+        test_lint(
+            UnreachableCodeLint::new(()).unwrap(),
+            "unreachable_code",
+            "continue",
+        );
+    }
+}
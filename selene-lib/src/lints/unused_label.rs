@@ -0,0 +1,105 @@
+use super::*;
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+
+pub struct UnusedLabelLint;
+
+impl Lint for UnusedLabelLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(UnusedLabelLint)
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, _: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = UnusedLabelVisitor {
+            functions: vec![FunctionLabels::default()],
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        let main_chunk = visitor.functions.pop().unwrap();
+        visitor.report(main_chunk);
+
+        visitor.diagnostics
+    }
+}
+
+// `goto` can't jump out of a function, so labels are tracked per function
+#[derive(Default)]
+struct FunctionLabels {
+    labels: Vec<(String, (usize, usize))>,
+    gotos: Vec<String>,
+}
+
+struct UnusedLabelVisitor {
+    functions: Vec<FunctionLabels>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl UnusedLabelVisitor {
+    fn report(&mut self, function: FunctionLabels) {
+        for (name, range) in function.labels {
+            if function.gotos.contains(&name) {
+                continue;
+            }
+
+            self.diagnostics.push(Diagnostic::new(
+                "unused_label",
+                format!("label `{name}` is never jumped to"),
+                Label::new(range),
+            ));
+        }
+    }
+}
+
+impl Visitor for UnusedLabelVisitor {
+    fn visit_function_body(&mut self, _: &ast::FunctionBody) {
+        self.functions.push(FunctionLabels::default());
+    }
+
+    fn visit_function_body_end(&mut self, _: &ast::FunctionBody) {
+        let function = self.functions.pop().unwrap();
+        self.report(function);
+    }
+
+    #[cfg(any(feature = "lua52", feature = "luajit"))]
+    fn visit_goto(&mut self, goto: &ast::lua52::Goto) {
+        self.functions
+            .last_mut()
+            .unwrap()
+            .gotos
+            .push(goto.label_name().token().to_string());
+    }
+
+    #[cfg(any(feature = "lua52", feature = "luajit"))]
+    fn visit_label(&mut self, label: &ast::lua52::Label) {
+        self.functions.last_mut().unwrap().labels.push((
+            label.name().token().to_string(),
+            crate::ast_util::range(label),
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "lua52"))]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_unused_label() {
+        test_lint(
+            UnusedLabelLint::new(()).unwrap(),
+            "unused_label",
+            "unused_label",
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use super::*;
+use crate::ast_util::{
+    range,
+    scopes::{ScopeManager, Variable},
+};
+use std::collections::{HashMap, HashSet};
+
+use full_moon::{
+    ast::{self, Ast},
+    visitors::Visitor,
+};
+use id_arena::Id;
+use regex::Regex;
+use serde::Deserialize;
+
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct UnusedParameterConfig {
+    allow_unused_before_used: bool,
+    allow_unused_callback_parameters: bool,
+    ignore_pattern: String,
+}
+
+impl Default for UnusedParameterConfig {
+    fn default() -> Self {
+        Self {
+            allow_unused_before_used: true,
+            allow_unused_callback_parameters: false,
+            ignore_pattern: "^_".to_owned(),
+        }
+    }
+}
+
+pub struct UnusedParameterLint {
+    allow_unused_before_used: bool,
+    allow_unused_callback_parameters: bool,
+    ignore_pattern: Regex,
+}
+
+impl Lint for UnusedParameterLint {
+    type Config = UnusedParameterConfig;
+    type Error = regex::Error;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        Ok(Self {
+            allow_unused_before_used: config.allow_unused_before_used,
+            allow_unused_callback_parameters: config.allow_unused_callback_parameters,
+            ignore_pattern: Regex::new(&config.ignore_pattern)?,
+        })
+    }
+
+    fn pass(&self, ast: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let scope_manager = &ast_context.scope_manager;
+
+        let parameters = scope_manager
+            .variables
+            .iter()
+            .filter(|(_, variable)| variable.is_parameter)
+            .map(|(id, variable)| (variable.identifiers[0], id))
+            .collect();
+
+        let mut visitor = UnusedParameterVisitor {
+            lint: self,
+            scope_manager,
+            parameters,
+            callbacks: HashSet::new(),
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct UnusedParameterVisitor<'a> {
+    lint: &'a UnusedParameterLint,
+    scope_manager: &'a ScopeManager,
+    parameters: HashMap<(usize, usize), Id<Variable>>,
+    // Function bodies passed directly as an argument, like `signal:Connect(function(player) end)`
+    callbacks: HashSet<(usize, usize)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ParameterUsage {
+    Read,
+    WrittenOnly,
+    Unused,
+}
+
+impl UnusedParameterVisitor<'_> {
+    fn usage(&self, parameter: &ast::Parameter) -> Option<ParameterUsage> {
+        let ast::Parameter::Name(name) = parameter else {
+            return None;
+        };
+
+        let variable = &self.scope_manager.variables[*self.parameters.get(&range(name))?];
+
+        let references = variable
+            .references
+            .iter()
+            .map(|id| &self.scope_manager.references[*id]);
+
+        let mut usage = ParameterUsage::Unused;
+
+        for reference in references {
+            if reference.read || reference.write.is_none() {
+                return Some(ParameterUsage::Read);
+            }
+
+            usage = ParameterUsage::WrittenOnly;
+        }
+
+        Some(usage)
+    }
+}
+
+impl Visitor for UnusedParameterVisitor<'_> {
+    fn visit_function_args(&mut self, args: &ast::FunctionArgs) {
+        let ast::FunctionArgs::Parentheses { arguments, .. } = args else {
+            return;
+        };
+
+        for argument in arguments {
+            if let ast::Expression::Function(function) = argument {
+                self.callbacks.insert(range(function.body()));
+            }
+        }
+    }
+
+    fn visit_function_body(&mut self, body: &ast::FunctionBody) {
+        if self.lint.allow_unused_callback_parameters && self.callbacks.contains(&range(body)) {
+            return;
+        }
+
+        let usages = body
+            .parameters()
+            .iter()
+            .map(|parameter| (parameter, self.usage(parameter)))
+            .collect::<Vec<_>>();
+
+        // Parameters before one that's used can't be removed without changing the ones after them
+        let first_checked = if self.lint.allow_unused_before_used {
+            usages
+                .iter()
+                .rposition(|(_, usage)| *usage == Some(ParameterUsage::Read))
+                .map_or(0, |index| index + 1)
+        } else {
+            0
+        };
+
+        for (parameter, usage) in usages.into_iter().skip(first_checked) {
+            let (Some(usage), ast::Parameter::Name(name)) = (usage, parameter) else {
+                continue;
+            };
+
+            let name = name.token().to_string();
+
+            if usage == ParameterUsage::Read || self.lint.ignore_pattern.is_match(&name) {
+                continue;
+            }
+
+            self.diagnostics.push(Diagnostic::new(
+                "unused_parameter",
+                if usage == ParameterUsage::WrittenOnly {
+                    format!("{name} is assigned a value, but never used")
+                } else {
+                    format!("{name} is defined, but never used")
+                },
+                Label::new(range::<_, usize>(parameter)),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_unused_parameter() {
+        test_lint(
+            UnusedParameterLint::new(UnusedParameterConfig::default()).unwrap(),
+            "unused_parameter",
+            "unused_parameter",
+        );
+    }
+
+    #[test]
+    fn test_before_used() {
+        test_lint(
+            UnusedParameterLint::new(UnusedParameterConfig {
+                allow_unused_before_used: false,
+                ..UnusedParameterConfig::default()
+            })
+            .unwrap(),
+            "unused_parameter",
+            "before_used",
+        );
+    }
+
+    #[test]
+    fn test_callbacks() {
+        test_lint(
+            UnusedParameterLint::new(UnusedParameterConfig {
+                allow_unused_callback_parameters: true,
+                ..UnusedParameterConfig::default()
+            })
+            .unwrap(),
+            "unused_parameter",
+            "callbacks",
+        );
+    }
+
+    #[test]
+    fn test_explicit_self() {
+        test_lint(
+            UnusedParameterLint::new(UnusedParameterConfig::default()).unwrap(),
+            "unused_parameter",
+            "explicit_self",
+        );
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        assert!(UnusedParameterLint::new(UnusedParameterConfig {
+            ignore_pattern: "(".to_owned(),
+            ..UnusedParameterConfig::default()
+        })
+        .is_err());
+    }
+}
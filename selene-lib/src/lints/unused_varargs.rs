@@ -0,0 +1,56 @@
+use super::*;
+use std::convert::Infallible;
+
+use full_moon::ast::Ast;
+
+pub struct UnusedVarargsLint;
+
+impl Lint for UnusedVarargsLint {
+    type Config = ();
+    type Error = Infallible;
+
+    const SEVERITY: Severity = Severity::Warning;
+    const LINT_TYPE: LintType = LintType::Style;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(UnusedVarargsLint)
+    }
+
+    fn pass(&self, _: &Ast, _: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        ast_context
+            .scope_manager
+            .variables
+            .iter()
+            .filter(|(_, variable)| variable.is_parameter && variable.name == "...")
+            .filter(|(_, variable)| {
+                !variable
+                    .references
+                    .iter()
+                    .any(|id| ast_context.scope_manager.references[*id].read)
+            })
+            .map(|(_, variable)| {
+                Diagnostic::new_complete(
+                    "unused_varargs",
+                    "`...` is defined, but never used".to_owned(),
+                    Label::new(variable.identifiers[0]),
+                    vec!["if the function doesn't take extra arguments, remove `...`".to_owned()],
+                    Vec::new(),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_unused_varargs() {
+        test_lint(
+            UnusedVarargsLint::new(()).unwrap(),
+            "unused_varargs",
+            "unused_varargs",
+        );
+    }
+}
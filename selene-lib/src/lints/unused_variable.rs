@@ -60,6 +60,11 @@ impl Lint for UnusedVariableLint {
             .iter()
             .filter(|(_, variable)| !self.ignore_pattern.is_match(&variable.name))
         {
+            // Checked by `unused_parameter` and `unused_varargs` instead
+            if variable.is_parameter {
+                continue;
+            }
+
             if context.standard_library.global_has_fields(&variable.name) {
                 continue;
             }
@@ -227,15 +232,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_explicit_self() {
-        test_lint(
-            UnusedVariableLint::new(UnusedVariableConfig::default()).unwrap(),
-            "unused_variable",
-            "explicit_self",
-        );
-    }
-
     #[test]
     fn test_function_overriding() {
         test_lint(
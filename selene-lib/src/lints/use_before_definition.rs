@@ -0,0 +1,212 @@
+use super::*;
+use crate::{
+    ast_util::{range, scopes::ScopeManager},
+    standard_library::StandardLibrary,
+};
+use std::convert::Infallible;
+
+use full_moon::{
+    ast::{self, Ast},
+    tokenizer::TokenReference,
+    visitors::Visitor,
+};
+
+pub struct UseBeforeDefinitionLint;
+
+impl Lint for UseBeforeDefinitionLint {
+    type Config = ();
+    type Error = Infallible;
+
+    // Reported instead of `undefined_variable` for these globals, so is just as severe
+    const SEVERITY: Severity = Severity::Error;
+    const LINT_TYPE: LintType = LintType::Correctness;
+
+    fn new(_: Self::Config) -> Result<Self, Self::Error> {
+        Ok(UseBeforeDefinitionLint)
+    }
+
+    fn pass(&self, ast: &Ast, context: &Context, ast_context: &AstContext) -> Vec<Diagnostic> {
+        let mut visitor = UseBeforeDefinitionVisitor {
+            scope_manager: &ast_context.scope_manager,
+            standard_library: &context.standard_library,
+            blocks: Vec::new(),
+            function_depth: 0,
+            diagnostics: Vec::new(),
+        };
+
+        visitor.visit_ast(ast);
+
+        visitor.diagnostics
+    }
+}
+
+struct BlockLocals {
+    function_depth: usize,
+    locals: Vec<Local>,
+}
+
+struct Local {
+    name: String,
+    identifier: (usize, usize),
+
+    // Where the local starts existing. `local x = ...` only exists after the whole statement,
+    // while `local function x()` exists as soon as the name is given.
+    defined_at: usize,
+
+    // Set for `local x = function()`, which can't refer to itself
+    function_expression: Option<(usize, usize)>,
+}
+
+struct UseBeforeDefinitionVisitor<'a> {
+    scope_manager: &'a ScopeManager,
+    standard_library: &'a StandardLibrary,
+    blocks: Vec<BlockLocals>,
+    function_depth: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl UseBeforeDefinitionVisitor<'_> {
+    fn check_name(&mut self, token: &TokenReference) {
+        let (start, end) = range(token);
+
+        let Some(reference) = self.scope_manager.reference_at_byte(start) else {
+            return;
+        };
+
+        if !reference.read
+            || reference.resolved.is_some()
+            || self.standard_library.global_has_fields(&reference.name)
+        {
+            return;
+        }
+
+        // Only functions can run after a local defined later in the same block,
+        // anything else is just using a global.
+        let Some(local) = self
+            .blocks
+            .iter()
+            .rev()
+            .filter(|block| block.function_depth < self.function_depth)
+            .find_map(|block| {
+                block
+                    .locals
+                    .iter()
+                    .find(|local| local.name == reference.name && local.defined_at > start)
+            })
+        else {
+            return;
+        };
+
+        let is_self_reference =
+            local
+                .function_expression
+                .is_some_and(|(function_start, function_end)| {
+                    function_start <= start && start < function_end
+                });
+
+        self.diagnostics.push(Diagnostic::new_complete(
+            "use_before_definition",
+            format!(
+                "`{}` is used before the local is defined, so refers to a global instead",
+                reference.name
+            ),
+            Label::new((start, end)),
+            if is_self_reference {
+                vec![format!(
+                    "try `local function {}()` instead, which can refer to itself",
+                    reference.name
+                )]
+            } else {
+                Vec::new()
+            },
+            vec![Label::new_with_message(
+                local.identifier,
+                "defined here".to_owned(),
+            )],
+        ));
+    }
+}
+
+impl Visitor for UseBeforeDefinitionVisitor<'_> {
+    fn visit_block(&mut self, block: &ast::Block) {
+        let mut locals = Vec::new();
+
+        for stmt in block.stmts() {
+            match stmt {
+                ast::Stmt::LocalAssignment(local_assignment) => {
+                    let mut expressions = local_assignment.expressions().iter();
+
+                    for name in local_assignment.names() {
+                        let function_expression = match expressions.next() {
+                            Some(expression @ ast::Expression::Function(_)) => {
+                                Some(range(expression))
+                            }
+                            _ => None,
+                        };
+
+                        locals.push(Local {
+                            name: name.token().to_string(),
+                            identifier: range(name),
+                            defined_at: range(stmt).1,
+                            function_expression,
+                        });
+                    }
+                }
+
+                ast::Stmt::LocalFunction(local_function) => {
+                    locals.push(Local {
+                        name: local_function.name().token().to_string(),
+                        identifier: range(local_function.name()),
+                        defined_at: range(local_function.name()).0,
+                        function_expression: None,
+                    });
+                }
+
+                _ => {}
+            }
+        }
+
+        self.blocks.push(BlockLocals {
+            function_depth: self.function_depth,
+            locals,
+        });
+    }
+
+    fn visit_block_end(&mut self, _: &ast::Block) {
+        self.blocks.pop();
+    }
+
+    fn visit_function_body(&mut self, _: &ast::FunctionBody) {
+        self.function_depth += 1;
+    }
+
+    fn visit_function_body_end(&mut self, _: &ast::FunctionBody) {
+        self.function_depth -= 1;
+    }
+
+    fn visit_prefix(&mut self, prefix: &ast::Prefix) {
+        if let ast::Prefix::Name(name) = prefix {
+            self.check_name(name);
+        }
+    }
+
+    fn visit_var(&mut self, var: &ast::Var) {
+        if let ast::Var::Name(name) = var {
+            self.check_name(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::test_util::test_lint, *};
+
+    #[test]
+    fn test_use_before_definition() {
+        test_lint(
+            UseBeforeDefinitionLint::new(()).unwrap(),
+            "use_before_definition",
+            "use_before_definition",
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Parses the string form of argument types in standard libraries, such as `number?` or `table<string, number>`.
+use super::ArgumentType;
+
+pub fn parse_argument_type(text: &str) -> Result<ArgumentType, String> {
+    let mut parser = ArgumentTypeParser { text, index: 0 };
+
+    // Untyped varargs can only be written on their own
+    if text.trim() == "..." {
+        return Ok(ArgumentType::Vararg);
+    }
+
+    let argument_type = if parser.eat("...") {
+        ArgumentType::VarargOf(Box::new(parser.parse_type()?))
+    } else {
+        parser.parse_type()?
+    };
+
+    parser.skip_whitespace();
+    if parser.index < text.len() {
+        return Err(format!(
+            "unexpected `{}` in type {text}",
+            &text[parser.index..]
+        ));
+    }
+
+    Ok(argument_type)
+}
+
+struct ArgumentTypeParser<'a> {
+    text: &'a str,
+    index: usize,
+}
+
+impl ArgumentTypeParser<'_> {
+    fn rest(&self) -> &str {
+        &self.text[self.index..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.index = self.text.len() - self.rest().trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+
+        if self.rest().starts_with(token) {
+            self.index += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(format!("expected `{token}` in type {}", self.text))
+        }
+    }
+
+    fn name(&mut self) -> &str {
+        self.skip_whitespace();
+
+        let length = self
+            .rest()
+            .find(|character: char| !character.is_alphanumeric() && character != '_')
+            .unwrap_or(self.rest().len());
+
+        let start = self.index;
+        self.index += length;
+        &self.text[start..self.index]
+    }
+
+    fn parse_type(&mut self) -> Result<ArgumentType, String> {
+        let mut types = vec![self.parse_nilable()?];
+
+        while self.eat("|") {
+            types.push(self.parse_nilable()?);
+        }
+
+        if types.len() == 1 {
+            return Ok(types.pop().unwrap());
+        }
+
+        // `"a" | "b"` is the same as the constant list ["a", "b"]
+        if types
+            .iter()
+            .all(|argument_type| matches!(argument_type, ArgumentType::Constant(_)))
+        {
+            return Ok(ArgumentType::Constant(
+                types
+                    .into_iter()
+                    .flat_map(|argument_type| match argument_type {
+                        ArgumentType::Constant(constants) => constants,
+                        _ => unreachable!(),
+                    })
+                    .collect(),
+            ));
+        }
+
+        Ok(ArgumentType::Union(
+            types
+                .into_iter()
+                .flat_map(|argument_type| match argument_type {
+                    ArgumentType::Union(types) => types,
+                    other => vec![other],
+                })
+                .collect(),
+        ))
+    }
+
+    fn parse_nilable(&mut self) -> Result<ArgumentType, String> {
+        let mut argument_type = self.parse_simple()?;
+
+        while self.eat("?") {
+            argument_type = ArgumentType::Nilable(Box::new(argument_type));
+        }
+
+        Ok(argument_type)
+    }
+
+    fn parse_simple(&mut self) -> Result<ArgumentType, String> {
+        if self.eat("(") {
+            let argument_type = self.parse_type()?;
+            self.expect(")")?;
+            return Ok(argument_type);
+        }
+
+        if self.eat("\"") {
+            let length = self
+                .rest()
+                .find('"')
+                .ok_or_else(|| format!("unfinished string in type {}", self.text))?;
+
+            let constant = self.rest()[..length].to_owned();
+            self.index += length + 1;
+            return Ok(ArgumentType::Constant(vec![constant]));
+        }
+
+        match self.name() {
+            "any" => Ok(ArgumentType::Any),
+            "bool" => Ok(ArgumentType::Bool),
+            "nil" => Ok(ArgumentType::Nil),
+            "number" => Ok(ArgumentType::Number),
+            "string" => Ok(ArgumentType::String),
+
+            "function" => {
+                if !self.eat("(") {
+                    return Ok(ArgumentType::Function);
+                }
+
+                let mut parameters = Vec::new();
+                while !self.eat(")") {
+                    if self.eat("...") {
+                        parameters.push("...".to_owned());
+                    } else {
+                        let name = self.name().to_owned();
+                        if name.is_empty() {
+                            return Err(format!("expected a parameter name in type {}", self.text));
+                        }
+
+                        parameters.push(name);
+                    }
+
+                    if !self.eat(",") {
+                        self.expect(")")?;
+                        break;
+                    }
+                }
+
+                Ok(ArgumentType::FunctionSignature(parameters))
+            }
+
+            "table" => {
+                if !self.eat("<") {
+                    return Ok(ArgumentType::Table);
+                }
+
+                let key = self.parse_type()?;
+                self.expect(",")?;
+                let value = self.parse_type()?;
+                self.expect(">")?;
+
+                Ok(ArgumentType::TableOf(Box::new(key), Box::new(value)))
+            }
+
+            "" => Err(format!(
+                "unexpected `{}` in type {}",
+                self.rest(),
+                self.text
+            )),
+
+            other => Err(format!("unknown type {other}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_argument_types() {
+        assert_eq!(parse_argument_type("number"), Ok(ArgumentType::Number));
+        assert_eq!(parse_argument_type("..."), Ok(ArgumentType::Vararg));
+        assert_eq!(
+            parse_argument_type("number?"),
+            Ok(ArgumentType::Nilable(Box::new(ArgumentType::Number)))
+        );
+        assert_eq!(
+            parse_argument_type("number | string"),
+            Ok(ArgumentType::Union(vec![
+                ArgumentType::Number,
+                ArgumentType::String
+            ]))
+        );
+        assert_eq!(
+            parse_argument_type("\"a\" | \"b\""),
+            Ok(ArgumentType::Constant(vec!["a".to_owned(), "b".to_owned()]))
+        );
+        assert_eq!(
+            parse_argument_type("table<string, number | bool>"),
+            Ok(ArgumentType::TableOf(
+                Box::new(ArgumentType::String),
+                Box::new(ArgumentType::Union(vec![
+                    ArgumentType::Number,
+                    ArgumentType::Bool
+                ]))
+            ))
+        );
+        assert_eq!(
+            parse_argument_type("function(a, b, ...)"),
+            Ok(ArgumentType::FunctionSignature(vec![
+                "a".to_owned(),
+                "b".to_owned(),
+                "...".to_owned()
+            ]))
+        );
+        assert_eq!(
+            parse_argument_type("...number"),
+            Ok(ArgumentType::VarargOf(Box::new(ArgumentType::Number)))
+        );
+
+        assert_eq!(
+            parse_argument_type("numbr"),
+            Err("unknown type numbr".to_owned())
+        );
+        assert!(parse_argument_type("number string").is_err());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for text in [
+            "number?",
+            "number | string",
+            "(number | string)?",
+            "table<string, \"a\" | \"b\">",
+            "function(a, b)",
+            "...number",
+        ] {
+            assert_eq!(
+                parse_argument_type(text).unwrap().to_string(),
+                text,
+                "{text} did not round trip"
+            );
+        }
+    }
+}
@@ -0,0 +1,1452 @@
+//! Builds a standard library from Luau type definition files, like the ones Roblox ships
+//! (`declare class`, `declare function`, etc).
+//! full_moon doesn't parse definition syntax, so this uses its tokenizer and parses declarations itself.
+use std::collections::{BTreeMap, HashSet};
+
+use full_moon::tokenizer::{Lexer, LexerResult, Position, TokenType};
+
+use super::*;
+
+#[derive(Debug)]
+pub struct LuauDefinitionsError {
+    pub message: String,
+    pub position: Position,
+}
+
+impl fmt::Display for LuauDefinitionsError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{} at line {}, column {}",
+            self.message,
+            self.position.line(),
+            self.position.character()
+        )
+    }
+}
+
+impl std::error::Error for LuauDefinitionsError {}
+
+type DefinitionResult<T> = Result<T, LuauDefinitionsError>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum DefinitionTokenKind {
+    Eof,
+    Identifier,
+    Number,
+    String,
+    Symbol,
+}
+
+#[derive(Clone, Debug)]
+struct DefinitionToken {
+    kind: DefinitionTokenKind,
+    text: String,
+    position: Position,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum DefinitionType {
+    Any,
+    Function(FunctionType),
+    Intersection(Vec<DefinitionType>),
+    Named(String),
+    Nil,
+    Optional(Box<DefinitionType>),
+    Singleton(String),
+    Table(TableType),
+    Union(Vec<DefinitionType>),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct FunctionType {
+    generics: Vec<String>,
+    parameters: Vec<Parameter>,
+    vararg: Option<Box<DefinitionType>>,
+    returns: Vec<DefinitionType>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Parameter {
+    name: Option<String>,
+    parameter_type: DefinitionType,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct TableType {
+    properties: Vec<(String, DefinitionType)>,
+    has_indexer: bool,
+}
+
+#[derive(Debug)]
+enum Declaration {
+    Class {
+        name: String,
+        superclass: Option<String>,
+        members: Vec<ClassMember>,
+    },
+
+    Function {
+        name: String,
+        function_type: FunctionType,
+        deprecated: bool,
+    },
+
+    Global {
+        name: String,
+        global_type: DefinitionType,
+        deprecated: bool,
+    },
+}
+
+#[derive(Debug)]
+enum ClassMember {
+    Indexer,
+
+    Method {
+        name: String,
+        function_type: FunctionType,
+        deprecated: bool,
+    },
+
+    Property {
+        name: String,
+        property_type: DefinitionType,
+        read_only: bool,
+    },
+}
+
+fn tokenize(source: &str) -> DefinitionResult<Vec<DefinitionToken>> {
+    let tokens = match Lexer::new(source, full_moon::LuaVersion::luau()).collect() {
+        LexerResult::Ok(tokens) => tokens,
+        LexerResult::Fatal(errors) | LexerResult::Recovered(_, errors) => {
+            let error = errors.first().expect("lexer failed without any errors");
+
+            return Err(LuauDefinitionsError {
+                message: error.error().to_string(),
+                position: error.position(),
+            });
+        }
+    };
+
+    let mut definition_tokens = Vec::with_capacity(tokens.len());
+
+    for token in tokens {
+        let position = token.start_position();
+
+        let (kind, text) = match token.token_type() {
+            TokenType::Eof => (DefinitionTokenKind::Eof, String::new()),
+            TokenType::Identifier { identifier } => {
+                (DefinitionTokenKind::Identifier, identifier.to_string())
+            }
+            TokenType::Number { text } => (DefinitionTokenKind::Number, text.to_string()),
+            TokenType::StringLiteral { literal, .. } => {
+                (DefinitionTokenKind::String, literal.to_string())
+            }
+            TokenType::Symbol { symbol } => {
+                let symbol = symbol.to_string();
+
+                // Nested generics like `Array<Array<T>>` shouldn't be treated as operators
+                if symbol.starts_with('>') && symbol.len() > 1 {
+                    for character in symbol.chars() {
+                        definition_tokens.push(DefinitionToken {
+                            kind: DefinitionTokenKind::Symbol,
+                            text: character.to_string(),
+                            position,
+                        });
+                    }
+
+                    continue;
+                }
+
+                (DefinitionTokenKind::Symbol, symbol)
+            }
+
+            _ => continue,
+        };
+
+        definition_tokens.push(DefinitionToken {
+            kind,
+            text,
+            position,
+        });
+    }
+
+    Ok(definition_tokens)
+}
+
+struct DefinitionParser {
+    tokens: Vec<DefinitionToken>,
+    index: usize,
+}
+
+impl DefinitionParser {
+    fn peek(&self) -> &DefinitionToken {
+        self.peek_nth(0)
+    }
+
+    fn peek_nth(&self, offset: usize) -> &DefinitionToken {
+        let index = (self.index + offset).min(self.tokens.len() - 1);
+        &self.tokens[index]
+    }
+
+    fn next(&mut self) -> DefinitionToken {
+        let token = self.peek().clone();
+        if self.index < self.tokens.len() - 1 {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> DefinitionResult<T> {
+        Err(LuauDefinitionsError {
+            message: message.into(),
+            position: self.peek().position,
+        })
+    }
+
+    fn is_eof(&self) -> bool {
+        self.peek().kind == DefinitionTokenKind::Eof
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        let token = self.peek();
+        token.kind == DefinitionTokenKind::Symbol && token.text == symbol
+    }
+
+    fn is_identifier(&self, identifier: &str) -> bool {
+        let token = self.peek();
+        token.kind == DefinitionTokenKind::Identifier && token.text == identifier
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        if self.is_symbol(symbol) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> DefinitionResult<()> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            self.error(format!("expected `{symbol}`, got `{}`", self.peek().text))
+        }
+    }
+
+    fn expect_identifier(&mut self) -> DefinitionResult<String> {
+        if self.peek().kind == DefinitionTokenKind::Identifier {
+            Ok(self.next().text)
+        } else {
+            self.error(format!("expected a name, got `{}`", self.peek().text))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> DefinitionResult<()> {
+        if self.is_identifier(keyword) {
+            self.next();
+            Ok(())
+        } else {
+            self.error(format!("expected `{keyword}`, got `{}`", self.peek().text))
+        }
+    }
+
+    /// Skips over everything from the current opening symbol to its matching closing symbol.
+    fn skip_balanced(&mut self, open: &str, close: &str) -> DefinitionResult<()> {
+        self.expect_symbol(open)?;
+
+        let mut depth = 1;
+        while depth > 0 {
+            if self.is_eof() {
+                return self.error(format!("expected `{close}`"));
+            }
+
+            let token = self.next();
+            if token.kind == DefinitionTokenKind::Symbol {
+                if token.text == open {
+                    depth += 1;
+                } else if token.text == close {
+                    depth -= 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses generic parameters, such as `<T, U...>`, returning their names.
+    fn parse_generic_names(&mut self) -> DefinitionResult<Vec<String>> {
+        let mut names = Vec::new();
+        if !self.is_symbol("<") {
+            return Ok(names);
+        }
+
+        let start = self.index;
+        self.skip_balanced("<", ">")?;
+
+        let mut depth = 0;
+        for (index, token) in self.tokens[start..self.index].iter().enumerate() {
+            if token.kind == DefinitionTokenKind::Symbol {
+                match token.text.as_str() {
+                    "<" => depth += 1,
+                    ">" => depth -= 1,
+                    _ => {}
+                }
+            } else if token.kind == DefinitionTokenKind::Identifier && depth == 1 {
+                let previous = &self.tokens[start + index - 1];
+                if previous.text == "<" || previous.text == "," {
+                    names.push(token.text.clone());
+                }
+            }
+        }
+
+        Ok(names)
+    }
+
+    fn parse_attributes(&mut self) -> DefinitionResult<bool> {
+        let mut deprecated = false;
+
+        while self.eat_symbol("@") {
+            if self.expect_identifier()? == "deprecated" {
+                deprecated = true;
+            }
+        }
+
+        Ok(deprecated)
+    }
+
+    fn parse_declarations(&mut self) -> DefinitionResult<Vec<Declaration>> {
+        let mut declarations = Vec::new();
+
+        loop {
+            let deprecated = self.parse_attributes()?;
+
+            if self.is_eof() {
+                break;
+            }
+
+            if self.is_identifier("export") || self.is_identifier("type") {
+                self.skip_type_alias()?;
+                continue;
+            }
+
+            self.expect_keyword("declare")?;
+
+            if self.is_identifier("class") {
+                self.next();
+                declarations.push(self.parse_class(false)?);
+            } else if self.is_identifier("extern") {
+                self.next();
+                self.expect_keyword("type")?;
+                declarations.push(self.parse_class(true)?);
+            } else if self.eat_symbol("function") {
+                let mut name = self.expect_identifier()?;
+                while self.eat_symbol(".") {
+                    name.push('.');
+                    name.push_str(&self.expect_identifier()?);
+                }
+
+                declarations.push(Declaration::Function {
+                    name,
+                    function_type: self.parse_function_signature()?,
+                    deprecated,
+                });
+            } else {
+                let name = self.expect_identifier()?;
+                self.expect_symbol(":")?;
+
+                declarations.push(Declaration::Global {
+                    name,
+                    global_type: self.parse_type()?,
+                    deprecated,
+                });
+            }
+        }
+
+        Ok(declarations)
+    }
+
+    fn skip_type_alias(&mut self) -> DefinitionResult<()> {
+        if self.is_identifier("export") {
+            self.next();
+        }
+
+        self.expect_keyword("type")?;
+        self.expect_identifier()?;
+        self.parse_generic_names()?;
+        self.expect_symbol("=")?;
+        self.parse_type()?;
+
+        Ok(())
+    }
+
+    /// Parses the rest of `declare class Name extends Base ... end`,
+    /// or `declare extern type Name extends Base with ... end`.
+    fn parse_class(&mut self, is_extern: bool) -> DefinitionResult<Declaration> {
+        let name = self.expect_identifier()?;
+
+        let superclass = if self.is_identifier("extends") {
+            self.next();
+            Some(self.expect_identifier()?)
+        } else {
+            None
+        };
+
+        if is_extern {
+            self.expect_keyword("with")?;
+        }
+
+        let mut members = Vec::new();
+
+        loop {
+            let deprecated = self.parse_attributes()?;
+
+            if self.eat_symbol("end") {
+                break;
+            }
+
+            if self.eat_symbol("function") {
+                let name = self.expect_identifier()?;
+
+                members.push(ClassMember::Method {
+                    name,
+                    function_type: self.parse_function_signature()?,
+                    deprecated,
+                });
+            } else if self.is_symbol("[") {
+                self.skip_balanced("[", "]")?;
+                self.expect_symbol(":")?;
+                self.parse_type()?;
+
+                members.push(ClassMember::Indexer);
+            } else {
+                let read_only = self.parse_property_access();
+                let name = self.expect_identifier()?;
+                self.expect_symbol(":")?;
+
+                members.push(ClassMember::Property {
+                    name,
+                    property_type: self.parse_type()?,
+                    read_only,
+                });
+            }
+
+            if !self.eat_symbol(",") {
+                self.eat_symbol(";");
+            }
+        }
+
+        Ok(Declaration::Class {
+            name,
+            superclass,
+            members,
+        })
+    }
+
+    /// Consumes `read` or `write` before a property name, returning whether the property is read only.
+    fn parse_property_access(&mut self) -> bool {
+        if self.peek_nth(1).kind != DefinitionTokenKind::Identifier {
+            return false;
+        }
+
+        if self.is_identifier("read") {
+            self.next();
+            true
+        } else {
+            if self.is_identifier("write") {
+                self.next();
+            }
+
+            false
+        }
+    }
+
+    /// Parses `<T>(a: number, ...: string): ReturnType`, as used by `declare function`.
+    fn parse_function_signature(&mut self) -> DefinitionResult<FunctionType> {
+        let generics = self.parse_generic_names()?;
+        let (parameters, vararg) = self.parse_parameter_list()?;
+
+        let returns = if self.eat_symbol(":") {
+            self.parse_return_type()?
+        } else {
+            Vec::new()
+        };
+
+        Ok(FunctionType {
+            generics,
+            parameters,
+            vararg,
+            returns,
+        })
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn parse_parameter_list(
+        &mut self,
+    ) -> DefinitionResult<(Vec<Parameter>, Option<Box<DefinitionType>>)> {
+        self.expect_symbol("(")?;
+
+        let mut parameters = Vec::new();
+        let mut vararg = None;
+
+        while !self.eat_symbol(")") {
+            if self.eat_symbol("...") {
+                self.eat_symbol(":");
+
+                vararg = Some(Box::new(if self.is_symbol(")") || self.is_symbol(",") {
+                    DefinitionType::Any
+                } else {
+                    self.parse_type()?
+                }));
+            } else if self.peek().kind == DefinitionTokenKind::Identifier
+                && self.peek_nth(1).kind == DefinitionTokenKind::Symbol
+                && (self.peek_nth(1).text == ":"
+                    // Other parameters without a type are types, like in `(number) -> ()`
+                    || (self.peek().text == "self" && [",", ")"].contains(&self.peek_nth(1).text.as_str())))
+            {
+                let name = self.next().text;
+
+                let parameter_type = if self.eat_symbol(":") {
+                    self.parse_type()?
+                } else {
+                    DefinitionType::Any
+                };
+
+                parameters.push(Parameter {
+                    name: Some(name),
+                    parameter_type,
+                });
+            } else {
+                parameters.push(Parameter {
+                    name: None,
+                    parameter_type: self.parse_type()?,
+                });
+            }
+
+            if !self.eat_symbol(",") {
+                self.expect_symbol(")")?;
+                break;
+            }
+        }
+
+        Ok((parameters, vararg))
+    }
+
+    /// Parses what a function returns, such as `number`, `(number, string)`, or `...string`.
+    /// Variadic returns aren't kept, since only a fixed position can be known.
+    fn parse_return_type(&mut self) -> DefinitionResult<Vec<DefinitionType>> {
+        if self.eat_symbol("...") {
+            self.parse_type()?;
+            Ok(Vec::new())
+        } else if self.is_symbol("(") {
+            let (parameters, vararg) = self.parse_parameter_list()?;
+
+            if self.eat_symbol("->") {
+                let returns = self.parse_return_type()?;
+
+                return Ok(vec![DefinitionType::Function(FunctionType {
+                    generics: Vec::new(),
+                    parameters,
+                    vararg,
+                    returns,
+                })]);
+            }
+
+            Ok(parameters
+                .into_iter()
+                .map(|parameter| parameter.parameter_type)
+                .collect())
+        } else {
+            Ok(vec![self.parse_type()?])
+        }
+    }
+
+    fn parse_type(&mut self) -> DefinitionResult<DefinitionType> {
+        // Luau allows a leading separator, such as in `| "a" | "b"`
+        if !self.eat_symbol("|") {
+            self.eat_symbol("&");
+        }
+
+        let first = self.parse_optional_type()?;
+
+        if self.is_symbol("|") {
+            let mut types = vec![first];
+            while self.eat_symbol("|") {
+                types.push(self.parse_optional_type()?);
+            }
+
+            Ok(DefinitionType::Union(types))
+        } else if self.is_symbol("&") {
+            let mut types = vec![first];
+            while self.eat_symbol("&") {
+                types.push(self.parse_optional_type()?);
+            }
+
+            Ok(DefinitionType::Intersection(types))
+        } else {
+            Ok(first)
+        }
+    }
+
+    fn parse_optional_type(&mut self) -> DefinitionResult<DefinitionType> {
+        let mut simple_type = self.parse_simple_type()?;
+
+        // Generic packs, such as `T...`
+        if let DefinitionType::Named(_) = simple_type {
+            self.eat_symbol("...");
+        }
+
+        while self.eat_symbol("?") {
+            simple_type = DefinitionType::Optional(Box::new(simple_type));
+        }
+
+        Ok(simple_type)
+    }
+
+    fn parse_simple_type(&mut self) -> DefinitionResult<DefinitionType> {
+        let token = self.peek().clone();
+
+        match token.kind {
+            DefinitionTokenKind::String => {
+                self.next();
+                Ok(DefinitionType::Singleton(token.text))
+            }
+
+            DefinitionTokenKind::Identifier if token.text == "typeof" => {
+                self.next();
+                self.skip_balanced("(", ")")?;
+                Ok(DefinitionType::Any)
+            }
+
+            DefinitionTokenKind::Identifier => {
+                self.next();
+
+                let mut name = token.text;
+                while self.eat_symbol(".") {
+                    name.push('.');
+                    name.push_str(&self.expect_identifier()?);
+                }
+
+                if self.is_symbol("<") {
+                    self.skip_balanced("<", ">")?;
+                }
+
+                Ok(DefinitionType::Named(name))
+            }
+
+            DefinitionTokenKind::Symbol => match token.text.as_str() {
+                "nil" => {
+                    self.next();
+                    Ok(DefinitionType::Nil)
+                }
+
+                "true" | "false" => {
+                    self.next();
+                    Ok(DefinitionType::Named("boolean".to_owned()))
+                }
+
+                "{" => self.parse_table_type(),
+
+                "<" | "(" => {
+                    let generics = self.parse_generic_names()?;
+                    let (parameters, vararg) = self.parse_parameter_list()?;
+
+                    if self.eat_symbol("->") {
+                        let returns = self.parse_return_type()?;
+
+                        return Ok(DefinitionType::Function(FunctionType {
+                            generics,
+                            parameters,
+                            vararg,
+                            returns,
+                        }));
+                    }
+
+                    match (parameters.as_slice(), vararg) {
+                        ([parameter], None) if generics.is_empty() && parameter.name.is_none() => {
+                            Ok(parameter.parameter_type.clone())
+                        }
+
+                        _ => self.error("expected `->` after function type parameters"),
+                    }
+                }
+
+                other => self.error(format!("expected a type, got `{other}`")),
+            },
+
+            DefinitionTokenKind::Number | DefinitionTokenKind::Eof => {
+                self.error(format!("expected a type, got `{}`", token.text))
+            }
+        }
+    }
+
+    fn parse_table_type(&mut self) -> DefinitionResult<DefinitionType> {
+        self.expect_symbol("{")?;
+
+        let mut table_type = TableType::default();
+
+        let is_array = !self.is_symbol("}")
+            && !self.is_symbol("[")
+            && !(self.peek().kind == DefinitionTokenKind::Identifier
+                && (self.peek_nth(1).text == ":"
+                    || self.peek_nth(1).kind == DefinitionTokenKind::Identifier));
+
+        if is_array {
+            self.parse_type()?;
+            self.expect_symbol("}")?;
+            table_type.has_indexer = true;
+            return Ok(DefinitionType::Table(table_type));
+        }
+
+        while !self.eat_symbol("}") {
+            if self.eat_symbol("[") {
+                if self.peek().kind == DefinitionTokenKind::String && self.peek_nth(1).text == "]" {
+                    let name = self.next().text;
+                    self.expect_symbol("]")?;
+                    self.expect_symbol(":")?;
+                    table_type.properties.push((name, self.parse_type()?));
+                } else {
+                    self.parse_type()?;
+                    self.expect_symbol("]")?;
+                    self.expect_symbol(":")?;
+                    self.parse_type()?;
+                    table_type.has_indexer = true;
+                }
+            } else {
+                self.parse_property_access();
+                let name = self.expect_identifier()?;
+                self.expect_symbol(":")?;
+                table_type.properties.push((name, self.parse_type()?));
+            }
+
+            if !self.eat_symbol(",") && !self.eat_symbol(";") {
+                self.expect_symbol("}")?;
+                break;
+            }
+        }
+
+        Ok(DefinitionType::Table(table_type))
+    }
+}
+
+struct DefinitionConverter<'a> {
+    classes: BTreeMap<&'a str, (&'a Option<String>, &'a [ClassMember])>,
+}
+
+impl DefinitionConverter<'_> {
+    fn argument_type(&self, definition_type: &DefinitionType, generics: &[String]) -> ArgumentType {
+        match definition_type {
+            DefinitionType::Any | DefinitionType::Intersection(_) => ArgumentType::Any,
+            DefinitionType::Function(function_type) => ArgumentType::FunctionSignature(
+                function_type
+                    .parameters
+                    .iter()
+                    .map(|parameter| parameter.name.clone().unwrap_or_else(|| "_".to_owned()))
+                    .chain(function_type.vararg.as_ref().map(|_| "...".to_owned()))
+                    .collect(),
+            ),
+            DefinitionType::Nil => ArgumentType::Nil,
+            DefinitionType::Optional(inner) => self.argument_type(inner, generics),
+            DefinitionType::Singleton(text) => ArgumentType::Constant(vec![text.to_owned()]),
+            DefinitionType::Table(_) => ArgumentType::Table,
+
+            DefinitionType::Named(name) => match name.as_str() {
+                _ if generics.contains(name) => ArgumentType::Any,
+                "any" | "unknown" | "never" => ArgumentType::Any,
+                "boolean" => ArgumentType::Bool,
+                "number" => ArgumentType::Number,
+                "string" => ArgumentType::String,
+                "table" => ArgumentType::Table,
+                _ => ArgumentType::Display(name.to_owned()),
+            },
+
+            DefinitionType::Union(types) => {
+                let mut constants = Vec::new();
+                let mut argument_types = Vec::new();
+
+                // Whether the union can be nil is decided by `is_nilable`
+                for union_type in types {
+                    match self.argument_type(union_type, generics) {
+                        ArgumentType::Any => return ArgumentType::Any,
+                        ArgumentType::Nil => {}
+                        ArgumentType::Constant(mut values) => constants.append(&mut values),
+                        argument_type => argument_types.push(argument_type),
+                    }
+                }
+
+                if !constants.is_empty() {
+                    argument_types.push(ArgumentType::Constant(constants));
+                }
+
+                match argument_types.len() {
+                    0 => ArgumentType::Nil,
+                    1 => argument_types.pop().unwrap(),
+                    _ => ArgumentType::Union(argument_types),
+                }
+            }
+        }
+    }
+
+    fn is_nilable(definition_type: &DefinitionType) -> bool {
+        match definition_type {
+            DefinitionType::Any | DefinitionType::Nil | DefinitionType::Optional(_) => true,
+            DefinitionType::Named(name) => name == "any" || name == "unknown",
+            DefinitionType::Union(types) => types.iter().any(Self::is_nilable),
+            _ => false,
+        }
+    }
+
+    fn function_behavior(&self, function_type: &FunctionType, method: bool) -> FunctionBehavior {
+        let mut parameters = function_type.parameters.as_slice();
+
+        // Function types inside of tables, like `GetEnumItems: (self: any) -> ()`, are methods when they take self
+        let takes_self = matches!(
+            parameters.first(),
+            Some(Parameter { name: Some(name), .. }) if name == "self"
+        );
+
+        if takes_self {
+            parameters = &parameters[1..];
+        }
+
+        let method = method || takes_self;
+
+        let mut arguments: Vec<Argument> = parameters
+            .iter()
+            .map(|parameter| Argument {
+                required: if Self::is_nilable(&parameter.parameter_type) {
+                    Required::NotRequired
+                } else {
+                    Required::Required(None)
+                },
+                argument_type: self
+                    .argument_type(&parameter.parameter_type, &function_type.generics),
+                observes: Observes::ReadWrite,
+                deprecated: None,
+            })
+            .collect();
+
+        if let Some(vararg) = &function_type.vararg {
+            arguments.push(Argument {
+                required: Required::NotRequired,
+                argument_type: match self.argument_type(vararg, &function_type.generics) {
+                    ArgumentType::Any => ArgumentType::Vararg,
+                    argument_type => ArgumentType::VarargOf(Box::new(argument_type)),
+                },
+                observes: Observes::ReadWrite,
+                deprecated: None,
+            });
+        }
+
+        FunctionBehavior {
+            arguments,
+            method,
+            must_use: false,
+            never_returns: false,
+            returns: function_type
+                .returns
+                .iter()
+                .map(|return_type| match self.class_name(return_type) {
+                    Some(class_name) => {
+                        ReturnType::Type(ArgumentType::Struct(class_name.to_owned()))
+                    }
+                    None => {
+                        ReturnType::Type(self.argument_type(return_type, &function_type.generics))
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Combines overloads into one function, where arguments only some overloads use are optional.
+    fn merge_overloads(overloads: Vec<FunctionBehavior>) -> FunctionBehavior {
+        let mut overloads = overloads.into_iter();
+        let mut merged = overloads.next().expect("no overloads to merge");
+
+        for overload in overloads {
+            let varargs: Vec<&ArgumentType> = merged
+                .arguments
+                .last()
+                .into_iter()
+                .chain(overload.arguments.last())
+                .map(|argument| &argument.argument_type)
+                .filter(|argument_type| argument_type.is_vararg())
+                .collect();
+
+            let vararg_type = match varargs.as_slice() {
+                [] => None,
+                [argument_type] => Some((*argument_type).clone()),
+                [ours, theirs] if ours == theirs => Some((*ours).clone()),
+                _ => Some(ArgumentType::Vararg),
+            };
+
+            let mut merged_arguments = Vec::new();
+
+            let fixed = |arguments: &[Argument]| {
+                arguments
+                    .iter()
+                    .filter(|argument| !argument.argument_type.is_vararg())
+                    .cloned()
+                    .collect::<Vec<_>>()
+            };
+
+            let (ours, theirs) = (fixed(&merged.arguments), fixed(&overload.arguments));
+
+            for index in 0..ours.len().max(theirs.len()) {
+                merged_arguments.push(match (ours.get(index), theirs.get(index)) {
+                    (Some(our_argument), Some(their_argument)) => Argument {
+                        required: if our_argument.required == Required::NotRequired
+                            || their_argument.required == Required::NotRequired
+                        {
+                            Required::NotRequired
+                        } else {
+                            our_argument.required.clone()
+                        },
+                        argument_type: merge_argument_types(
+                            &our_argument.argument_type,
+                            &their_argument.argument_type,
+                        ),
+                        ..our_argument.clone()
+                    },
+
+                    (Some(argument), None) | (None, Some(argument)) => Argument {
+                        required: Required::NotRequired,
+                        ..argument.clone()
+                    },
+
+                    (None, None) => unreachable!(),
+                });
+            }
+
+            if let Some(vararg_type) = vararg_type {
+                merged_arguments.push(Argument {
+                    required: Required::NotRequired,
+                    argument_type: vararg_type,
+                    observes: Observes::ReadWrite,
+                    deprecated: None,
+                });
+            }
+
+            merged.arguments = merged_arguments;
+
+            // Only keep what every overload agrees on returning
+            merged.returns = merged
+                .returns
+                .iter()
+                .zip(&overload.returns)
+                .map(|(ours, theirs)| match (ours, theirs) {
+                    _ if ours == theirs => ours.clone(),
+                    (ReturnType::Type(ours), ReturnType::Type(theirs)) => {
+                        ReturnType::Type(merge_argument_types(ours, theirs))
+                    }
+                    _ => ReturnType::Type(ArgumentType::Any),
+                })
+                .collect();
+        }
+
+        merged
+    }
+
+    fn function_field(
+        &self,
+        definition_type: &DefinitionType,
+        method: bool,
+    ) -> Option<FunctionBehavior> {
+        match definition_type {
+            DefinitionType::Function(function_type) => {
+                Some(self.function_behavior(function_type, method))
+            }
+
+            DefinitionType::Intersection(types) => {
+                let overloads = types
+                    .iter()
+                    .map(|overload| match overload {
+                        DefinitionType::Function(function_type) => {
+                            Some(self.function_behavior(function_type, method))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+
+                Some(Self::merge_overloads(overloads))
+            }
+
+            _ => None,
+        }
+    }
+
+    fn class_name<'t>(&self, definition_type: &'t DefinitionType) -> Option<&'t str> {
+        match definition_type {
+            DefinitionType::Named(name) if self.classes.contains_key(name.as_str()) => Some(name),
+            DefinitionType::Optional(inner) => self.class_name(inner),
+            _ => None,
+        }
+    }
+
+    /// Inserts the fields for a value of the given type into `fields`,
+    /// flattening tables into `name.field` keys the same way the YAML format does.
+    fn insert_fields(
+        &self,
+        fields: &mut BTreeMap<String, Field>,
+        name: String,
+        definition_type: &DefinitionType,
+        writability: PropertyWritability,
+    ) {
+        if let Some(function_behavior) = self.function_field(definition_type, false) {
+            fields.insert(
+                name,
+                Field::from_field_kind(FieldKind::Function(function_behavior)),
+            );
+        } else if let Some(class_name) = self.class_name(definition_type) {
+            fields.insert(
+                name,
+                Field::from_field_kind(FieldKind::Struct(class_name.to_owned())),
+            );
+        } else if let DefinitionType::Table(table_type) = definition_type {
+            if table_type.has_indexer {
+                fields.insert(format!("{name}.*"), Field::from_field_kind(FieldKind::Any));
+            }
+
+            if table_type.properties.is_empty() && !table_type.has_indexer {
+                fields.insert(
+                    name,
+                    Field::from_field_kind(FieldKind::Property(writability)),
+                );
+                return;
+            }
+
+            for (property_name, property_type) in &table_type.properties {
+                self.insert_fields(
+                    fields,
+                    format!("{name}.{property_name}"),
+                    property_type,
+                    writability,
+                );
+            }
+        } else if matches!(definition_type, DefinitionType::Any)
+            || matches!(definition_type, DefinitionType::Named(name) if name == "any")
+        {
+            fields.insert(name, Field::from_field_kind(FieldKind::Any));
+        } else {
+            fields.insert(
+                name,
+                Field::from_field_kind(FieldKind::Property(writability)),
+            );
+        }
+    }
+
+    fn class_fields(
+        &self,
+        class_name: &str,
+        seen: &mut HashSet<String>,
+    ) -> BTreeMap<String, Field> {
+        let mut fields = BTreeMap::new();
+
+        // Guard against classes that extend themselves
+        if !seen.insert(class_name.to_owned()) {
+            return fields;
+        }
+
+        let Some((superclass, members)) = self.classes.get(class_name) else {
+            return fields;
+        };
+
+        if let Some(superclass) = superclass {
+            fields.extend(self.class_fields(superclass, seen));
+        }
+
+        for member in *members {
+            match member {
+                ClassMember::Indexer => {
+                    fields.insert("*".to_owned(), Field::from_field_kind(FieldKind::Any));
+                }
+
+                ClassMember::Method {
+                    name,
+                    function_type,
+                    deprecated,
+                } => {
+                    let function_behavior = self.function_behavior(function_type, true);
+
+                    let function_behavior = match fields.remove(name) {
+                        Some(Field {
+                            field_kind: FieldKind::Function(existing),
+                            ..
+                        }) if existing.method => {
+                            Self::merge_overloads(vec![existing, function_behavior])
+                        }
+
+                        _ => function_behavior,
+                    };
+
+                    fields.insert(
+                        name.to_owned(),
+                        Field::from_field_kind(FieldKind::Function(function_behavior))
+                            .with_deprecated(deprecated_field(*deprecated)),
+                    );
+                }
+
+                ClassMember::Property {
+                    name,
+                    property_type,
+                    read_only,
+                } => {
+                    let writability = if *read_only {
+                        PropertyWritability::ReadOnly
+                    } else {
+                        PropertyWritability::OverrideFields
+                    };
+
+                    let mut property_fields = BTreeMap::new();
+                    self.insert_fields(
+                        &mut property_fields,
+                        name.to_owned(),
+                        property_type,
+                        writability,
+                    );
+                    fields.extend(property_fields);
+                }
+            }
+        }
+
+        fields
+    }
+}
+
+/// Combines the types two overloads accept for the same argument.
+fn merge_argument_types(ours: &ArgumentType, theirs: &ArgumentType) -> ArgumentType {
+    if ours == theirs {
+        return ours.clone();
+    }
+
+    let mut argument_types = Vec::new();
+
+    for argument_type in [ours, theirs] {
+        match argument_type {
+            ArgumentType::Any => return ArgumentType::Any,
+            ArgumentType::Union(types) => argument_types.extend(types.iter().cloned()),
+            other => argument_types.push(other.clone()),
+        }
+    }
+
+    argument_types.dedup();
+    ArgumentType::Union(argument_types)
+}
+
+fn deprecated_field(deprecated: bool) -> Option<Deprecated> {
+    deprecated.then(|| Deprecated {
+        message: "marked as @deprecated in its type definition".to_owned(),
+        replace: Vec::new(),
+    })
+}
+
+impl StandardLibrary {
+    /// Creates a standard library from the contents of a Luau type definition file,
+    /// such as `globalTypes.d.luau`.
+    pub fn from_luau_definitions(source: &str) -> Result<StandardLibrary, LuauDefinitionsError> {
+        let mut parser = DefinitionParser {
+            tokens: tokenize(source)?,
+            index: 0,
+        };
+
+        let declarations = parser.parse_declarations()?;
+
+        let converter = DefinitionConverter {
+            classes: declarations
+                .iter()
+                .filter_map(|declaration| match declaration {
+                    Declaration::Class {
+                        name,
+                        superclass,
+                        members,
+                    } => Some((name.as_str(), (superclass, members.as_slice()))),
+                    _ => None,
+                })
+                .collect(),
+        };
+
+        let mut standard_library = StandardLibrary {
+            lua_versions: vec![LuaVersion::Luau],
+            ..StandardLibrary::default()
+        };
+
+        for declaration in &declarations {
+            match declaration {
+                Declaration::Class { name, .. } => {
+                    standard_library.structs.insert(
+                        name.to_owned(),
+                        converter.class_fields(name, &mut HashSet::new()),
+                    );
+                }
+
+                Declaration::Function {
+                    name,
+                    function_type,
+                    deprecated,
+                } => {
+                    let function_behavior = converter.function_behavior(function_type, false);
+
+                    let function_behavior = match standard_library.globals.remove(name) {
+                        Some(Field {
+                            field_kind: FieldKind::Function(existing),
+                            ..
+                        }) => {
+                            DefinitionConverter::merge_overloads(vec![existing, function_behavior])
+                        }
+
+                        _ => function_behavior,
+                    };
+
+                    standard_library.globals.insert(
+                        name.to_owned(),
+                        Field::from_field_kind(FieldKind::Function(function_behavior))
+                            .with_deprecated(deprecated_field(*deprecated)),
+                    );
+                }
+
+                Declaration::Global {
+                    name,
+                    global_type,
+                    deprecated,
+                } => {
+                    let mut fields = BTreeMap::new();
+                    converter.insert_fields(
+                        &mut fields,
+                        name.to_owned(),
+                        global_type,
+                        PropertyWritability::ReadOnly,
+                    );
+
+                    standard_library.globals.extend(fields.into_iter().map(
+                        |(field_name, field)| {
+                            let field = if field_name == *name {
+                                field.with_deprecated(deprecated_field(*deprecated))
+                            } else {
+                                field
+                            };
+
+                            (field_name, field)
+                        },
+                    ));
+                }
+            }
+        }
+
+        Ok(standard_library)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argument(required: bool, argument_type: ArgumentType) -> Argument {
+        Argument {
+            required: if required {
+                Required::Required(None)
+            } else {
+                Required::NotRequired
+            },
+            argument_type,
+            observes: Observes::ReadWrite,
+            deprecated: None,
+        }
+    }
+
+    fn function(std: &StandardLibrary, names: &[&str]) -> FunctionBehavior {
+        match &std
+            .find_global(names)
+            .unwrap_or_else(|| panic!("couldn't find {names:?}"))
+            .field_kind
+        {
+            FieldKind::Function(function_behavior) => function_behavior.clone(),
+            other => panic!("{names:?} is not a function: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_declare_functions() {
+        let std = StandardLibrary::from_luau_definitions(
+            r#"
+            declare function tick(): number
+            declare function wait(seconds: number?): (number, number)
+            declare function print<T...>(...: T...)
+            declare function warn(...: any)
+            declare function typeof(value: any): string
+            declare function max(first: number, ...: number): number
+            declare function format(value: number | string | nil): string
+
+            @deprecated
+            declare function delay(delayTime: number?, callback: (number) -> ())
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(function(&std, &["tick"]).arguments, Vec::new());
+        assert_eq!(
+            function(&std, &["wait"]).arguments,
+            vec![argument(false, ArgumentType::Number)]
+        );
+        assert_eq!(
+            function(&std, &["print"]).arguments,
+            vec![argument(false, ArgumentType::Vararg)]
+        );
+        assert_eq!(
+            function(&std, &["max"]).arguments,
+            vec![
+                argument(true, ArgumentType::Number),
+                argument(
+                    false,
+                    ArgumentType::VarargOf(Box::new(ArgumentType::Number))
+                ),
+            ]
+        );
+        assert_eq!(
+            function(&std, &["format"]).arguments,
+            vec![argument(
+                false,
+                ArgumentType::Union(vec![ArgumentType::Number, ArgumentType::String])
+            )]
+        );
+        assert_eq!(
+            function(&std, &["delay"]).arguments,
+            vec![
+                argument(false, ArgumentType::Number),
+                argument(true, ArgumentType::FunctionSignature(vec!["_".to_owned()])),
+            ]
+        );
+        assert!(std.find_global(&["delay"]).unwrap().deprecated.is_some());
+    }
+
+    #[test]
+    fn test_declare_classes() {
+        let std = StandardLibrary::from_luau_definitions(
+            r#"
+            declare class Instance
+                Name: string
+                read ClassName: string
+                Parent: Instance?
+                function FindFirstChild(self, name: string, recursive: boolean?): Instance?
+                function IsA(self, className: "Part" | "Model"): boolean
+                function GetBounds(self): (number, number)
+            end
+
+            declare class Part extends Instance
+                Anchored: boolean
+            end
+
+            declare workspace: Part
+            declare game: Instance
+            "#,
+        )
+        .unwrap();
+
+        let find_first_child = function(&std, &["workspace", "FindFirstChild"]);
+        assert!(find_first_child.method);
+        assert_eq!(
+            find_first_child.arguments,
+            vec![
+                argument(true, ArgumentType::String),
+                argument(false, ArgumentType::Bool),
+            ]
+        );
+
+        assert_eq!(
+            function(&std, &["game", "IsA"]).arguments,
+            vec![argument(
+                true,
+                ArgumentType::Constant(vec!["Part".to_owned(), "Model".to_owned()])
+            )]
+        );
+
+        assert_eq!(
+            std.find_global(&["workspace", "Anchored"])
+                .unwrap()
+                .field_kind,
+            FieldKind::Property(PropertyWritability::OverrideFields)
+        );
+        assert_eq!(
+            std.find_global(&["workspace", "ClassName"])
+                .unwrap()
+                .field_kind,
+            FieldKind::Property(PropertyWritability::ReadOnly)
+        );
+        assert_eq!(
+            std.find_global(&["game", "Parent"]).unwrap().field_kind,
+            FieldKind::Struct("Instance".to_owned())
+        );
+        assert!(std.find_global(&["game", "Anchored"]).is_none());
+
+        assert_eq!(
+            find_first_child.returns,
+            vec![ReturnType::Type(ArgumentType::Struct(
+                "Instance".to_owned()
+            ))]
+        );
+        assert_eq!(
+            function(&std, &["game", "GetBounds"]).returns,
+            vec![
+                ReturnType::Type(ArgumentType::Number),
+                ReturnType::Type(ArgumentType::Number),
+            ]
+        );
+        assert!(std.find_struct_field("Part", &["Anchored"]).is_some());
+        assert!(std.find_struct_field("Instance", &["Anchored"]).is_none());
+    }
+
+    #[test]
+    fn test_declare_tables_and_overloads() {
+        let std = StandardLibrary::from_luau_definitions(
+            r#"
+            type Callback = (number) -> ()
+            export type Array<T> = { T }
+
+            declare Vector3: {
+                new: ((x: number?, y: number?, z: number?) -> Vector3),
+                zero: Vector3,
+            }
+
+            declare Instance: {
+                new: ((className: "Part") -> Part) & ((className: "Part", parent: Instance?) -> Part),
+            }
+
+            declare shared: { [any]: any }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(function(&std, &["Vector3", "new"]).arguments.len(), 3);
+        assert_eq!(
+            std.find_global(&["Vector3", "zero"]).unwrap().field_kind,
+            FieldKind::Property(PropertyWritability::ReadOnly)
+        );
+
+        assert_eq!(
+            function(&std, &["Instance", "new"]).arguments,
+            vec![
+                argument(true, ArgumentType::Constant(vec!["Part".to_owned()])),
+                argument(false, ArgumentType::Display("Instance".to_owned())),
+            ]
+        );
+
+        assert_eq!(
+            std.find_global(&["shared", "anything"]).unwrap().field_kind,
+            FieldKind::Any
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = StandardLibrary::from_luau_definitions("declare function (): number")
+            .expect_err("parsed an invalid definition file");
+
+        assert_eq!(error.position.line(), 1);
+        assert!(
+            error.message.starts_with("expected a name"),
+            "{}",
+            error.message
+        );
+    }
+}
@@ -1,4 +1,7 @@
+mod argument_types;
 mod lua_versions;
+#[cfg(feature = "roblox")]
+mod luau_definitions;
 pub mod v1;
 mod v1_upgrade;
 
@@ -8,6 +11,8 @@ use std::{
     fmt, io,
 };
 
+#[cfg(feature = "roblox")]
+pub use luau_definitions::LuauDefinitionsError;
 use once_cell::sync::OnceCell;
 use regex::{Captures, Regex};
 use serde::{
@@ -244,13 +249,34 @@ impl StandardLibrary {
             return Some(explicit_global);
         }
 
+        profiling::scope!("find_global: look through global tree cache");
+        self.find_in_tree(self.global_tree_cache(), &self.globals, names)
+    }
+
+    /// Find a field through its name path, starting from a value of the struct `struct_name`,
+    /// such as what a function returns. Resolves fields the same way as `find_global`.
+    pub fn find_struct_field<S: Borrow<str>>(
+        &self,
+        struct_name: &str,
+        names: &[S],
+    ) -> Option<&Field> {
+        assert!(!names.is_empty());
+
+        let strukt = self.structs.get(struct_name)?;
+        self.find_in_tree(&extract_into_tree(strukt), strukt, names)
+    }
+
+    fn find_in_tree<'a, S: Borrow<str>>(
+        &'a self,
+        tree: &BTreeMap<String, GlobalTreeNode>,
+        names_to_fields: &'a BTreeMap<String, Field>,
+        names: &[S],
+    ) -> Option<&'a Field> {
         // TODO: This is really stupid lol
         let mut last_extracted_struct;
 
-        let mut current = self.global_tree_cache();
-        let mut current_names_to_fields = &self.globals;
-
-        profiling::scope!("find_global: look through global tree cache");
+        let mut current = tree;
+        let mut current_names_to_fields = names_to_fields;
 
         for name in names.iter().take(names.len() - 1) {
             let found_segment = current.get(name.borrow()).or_else(|| current.get("*"))?;
@@ -320,8 +346,9 @@ impl StandardLibrary {
                 }),
         );
 
-        // Intentionally not a merge, didn't seem valuable
-        if !other.lua_versions.is_empty() {
+        // Intentionally not a merge, didn't seem valuable.
+        // The library being extended knows its own versions better than its base does.
+        if self.lua_versions.is_empty() {
             self.lua_versions = other.lua_versions;
         }
 
@@ -428,6 +455,56 @@ pub struct FunctionBehavior {
     #[serde(default)]
     #[serde(skip_serializing_if = "is_false")]
     pub must_use: bool,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_false")]
+    pub never_returns: bool,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub returns: Vec<ReturnType>,
+}
+
+impl FunctionBehavior {
+    /// The name of the struct this function returns in the given position, if it returns one.
+    /// `constant_arguments` are the constant strings passed to the function, used by `struct_from_argument`.
+    pub fn return_struct<'a>(
+        &'a self,
+        index: usize,
+        constant_arguments: &[Option<&'a str>],
+    ) -> Option<&'a str> {
+        match self.returns.get(index)? {
+            ReturnType::Type(ArgumentType::Struct(name)) => Some(name),
+            ReturnType::StructFromArgument { argument } => {
+                constant_arguments.get(argument.checked_sub(1)?).copied()?
+            }
+            ReturnType::Type(_) => None,
+        }
+    }
+}
+
+/// What a function returns, in the same format as argument types.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ReturnType {
+    /// The struct named by the constant string passed as the given argument, starting from 1.
+    /// Used by functions like `Instance.new("Part")`.
+    StructFromArgument {
+        #[serde(rename = "struct_from_argument")]
+        argument: usize,
+    },
+
+    Type(ArgumentType),
+}
+
+impl ReturnType {
+    /// Whether the value returned could be nil, such as `string.match` failing to match.
+    pub fn is_nilable(&self) -> bool {
+        match self {
+            ReturnType::StructFromArgument { .. } => false,
+            ReturnType::Type(argument_type) => argument_type.is_nilable(),
+        }
+    }
 }
 
 fn is_false(value: &bool) -> bool {
@@ -442,6 +519,10 @@ pub struct Field {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deprecated: Option<Deprecated>,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versions: Option<FieldVersions>,
 }
 
 impl Field {
@@ -449,6 +530,7 @@ impl Field {
         Self {
             field_kind,
             deprecated: None,
+            versions: None,
         }
     }
 
@@ -457,6 +539,31 @@ impl Field {
     }
 }
 
+/// The Lua versions a field is available in, for fields that not every version of Lua has.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldVersions {
+    /// The first version with this field, such as `lua52` for `table.unpack`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added: Option<lua_versions::LuaVersion>,
+
+    /// The first version without this field, such as `lua52` for `setfenv`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed: Option<lua_versions::LuaVersion>,
+
+    /// What to use instead in versions before this field was added.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added_replacement: Option<String>,
+
+    /// What to use instead in versions after this field was removed.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed_replacement: Option<String>,
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Deprecated {
@@ -636,21 +743,62 @@ pub struct Argument {
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
-// TODO: Nilable types
 pub enum ArgumentType {
     Any,
     Bool,
     Constant(Vec<String>),
     Display(String),
-    // TODO: Optionally specify parameters
     Function,
+    /// A function that will be called with the given parameters, such as `function(a, b)`
+    FunctionSignature(Vec<String>),
     Nil,
+    /// Either the given type or nil, such as `number?`
+    Nilable(Box<ArgumentType>),
     Number,
     String,
-    // TODO: Types for tables
+    /// A table that has the fields of the given struct
+    Struct(String),
     Table,
-    // TODO: Support repeating types (like for string.char)
+    /// A table with keys and values of the given types, such as `table<string, number>`
+    TableOf(Box<ArgumentType>, Box<ArgumentType>),
+    /// Any one of the given types, such as `number | string`
+    Union(Vec<ArgumentType>),
     Vararg,
+    /// Any amount of arguments of the given type, such as `...number`
+    VarargOf(Box<ArgumentType>),
+}
+
+impl ArgumentType {
+    pub fn is_vararg(&self) -> bool {
+        matches!(self, ArgumentType::Vararg | ArgumentType::VarargOf(_))
+    }
+
+    pub fn is_nilable(&self) -> bool {
+        match self {
+            ArgumentType::Nil | ArgumentType::Nilable(_) => true,
+            ArgumentType::Union(types) => types.iter().any(ArgumentType::is_nilable),
+            _ => false,
+        }
+    }
+
+    // Constants are written as unions when they're inside of other types
+    fn fmt_nested(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgumentType::Constant(options) => write!(
+                formatter,
+                "{}",
+                options
+                    .iter()
+                    .map(|string| format!("\"{string}\""))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            ),
+
+            ArgumentType::Union(_) => write!(formatter, "({self})"),
+
+            _ => write!(formatter, "{self}"),
+        }
+    }
 }
 
 impl Serialize for ArgumentType {
@@ -659,11 +807,16 @@ impl Serialize for ArgumentType {
             &ArgumentType::Any
             | &ArgumentType::Bool
             | &ArgumentType::Function
+            | &ArgumentType::FunctionSignature(_)
             | &ArgumentType::Nil
+            | &ArgumentType::Nilable(_)
             | &ArgumentType::Number
             | &ArgumentType::String
             | &ArgumentType::Table
-            | &ArgumentType::Vararg => serializer.serialize_str(&self.to_string()),
+            | &ArgumentType::TableOf(_, _)
+            | &ArgumentType::Union(_)
+            | &ArgumentType::Vararg
+            | &ArgumentType::VarargOf(_) => serializer.serialize_str(&self.to_string()),
 
             ArgumentType::Constant(constants) => {
                 let mut seq = serializer.serialize_seq(Some(constants.len()))?;
@@ -678,6 +831,12 @@ impl Serialize for ArgumentType {
                 map.serialize_entry("display", display)?;
                 map.end()
             }
+
+            ArgumentType::Struct(name) => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry("struct", name)?;
+                map.end()
+            }
         }
     }
 }
@@ -706,9 +865,11 @@ impl<'de> Visitor<'de> for ArgumentTypeVisitor {
 
         if let Some(display) = map.remove("display") {
             Ok(ArgumentType::Display(display))
+        } else if let Some(name) = map.remove("struct") {
+            Ok(ArgumentType::Struct(name))
         } else {
             Err(de::Error::custom(
-                "map value must have a `display` property",
+                "map value must have a `display` or `struct` property",
             ))
         }
     }
@@ -724,17 +885,7 @@ impl<'de> Visitor<'de> for ArgumentTypeVisitor {
     }
 
     fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
-        match value {
-            "any" => Ok(ArgumentType::Any),
-            "bool" => Ok(ArgumentType::Bool),
-            "function" => Ok(ArgumentType::Function),
-            "nil" => Ok(ArgumentType::Nil),
-            "number" => Ok(ArgumentType::Number),
-            "string" => Ok(ArgumentType::String),
-            "table" => Ok(ArgumentType::Table),
-            "..." => Ok(ArgumentType::Vararg),
-            other => Err(de::Error::custom(format!("unknown type {other}"))),
-        }
+        argument_types::parse_argument_type(value).map_err(de::Error::custom)
     }
 }
 
@@ -755,11 +906,41 @@ impl fmt::Display for ArgumentType {
             ),
             ArgumentType::Display(display) => write!(formatter, "{display}"),
             ArgumentType::Function => write!(formatter, "function"),
+            ArgumentType::FunctionSignature(parameters) => {
+                write!(formatter, "function({})", parameters.join(", "))
+            }
             ArgumentType::Nil => write!(formatter, "nil"),
+            ArgumentType::Nilable(argument_type) => {
+                argument_type.fmt_nested(formatter)?;
+                write!(formatter, "?")
+            }
             ArgumentType::Number => write!(formatter, "number"),
             ArgumentType::String => write!(formatter, "string"),
+            ArgumentType::Struct(name) => write!(formatter, "{name}"),
             ArgumentType::Table => write!(formatter, "table"),
+            ArgumentType::TableOf(key, value) => {
+                write!(formatter, "table<")?;
+                key.fmt_nested(formatter)?;
+                write!(formatter, ", ")?;
+                value.fmt_nested(formatter)?;
+                write!(formatter, ">")
+            }
+            ArgumentType::Union(types) => {
+                for (index, argument_type) in types.iter().enumerate() {
+                    if index > 0 {
+                        write!(formatter, " | ")?;
+                    }
+
+                    argument_type.fmt_nested(formatter)?;
+                }
+
+                Ok(())
+            }
             ArgumentType::Vararg => write!(formatter, "..."),
+            ArgumentType::VarargOf(argument_type) => {
+                write!(formatter, "...")?;
+                argument_type.fmt_nested(formatter)
+            }
         }
     }
 }
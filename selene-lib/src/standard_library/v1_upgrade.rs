@@ -85,6 +85,8 @@ fn unpack_v1_field(name: String, v1_field: v1::Field) -> BTreeMap<String, Field>
                             arguments: function.arguments.into_iter().map(Into::into).collect(),
                             method: function.method,
                             must_use: false,
+                            never_returns: false,
+                            returns: Vec::new(),
                         })),
                     );
                 }
@@ -0,0 +1,187 @@
+use crate::CheckerDiagnostic;
+
+/// Combines the diagnostics from checking the same file against several targets, like with `std = ["lua51", "luau"]`.
+/// Diagnostics every target reported are only kept once, and the rest get a note naming the targets that reported them.
+/// `target_count` is the number of targets in total, including ones missing from `targets`, like those the file couldn't be parsed for.
+pub fn merge_target_diagnostics(
+    targets: Vec<(&str, Vec<CheckerDiagnostic>)>,
+    target_count: usize,
+) -> Vec<CheckerDiagnostic> {
+    let mut merged: Vec<(CheckerDiagnostic, Vec<&str>)> = Vec::new();
+
+    for (target, diagnostics) in targets {
+        for diagnostic in diagnostics {
+            // Identical diagnostics from the same target are still reported separately
+            let existing = merged.iter_mut().find(|(existing, reported_by)| {
+                !reported_by.contains(&target)
+                    && existing.diagnostic.code == diagnostic.diagnostic.code
+                    && existing.diagnostic.message == diagnostic.diagnostic.message
+                    && existing.diagnostic.primary_label.range
+                        == diagnostic.diagnostic.primary_label.range
+            });
+
+            match existing {
+                Some((_, reported_by)) => reported_by.push(target),
+                None => merged.push((diagnostic, vec![target])),
+            }
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|(mut diagnostic, reported_by)| {
+            if reported_by.len() < target_count {
+                diagnostic.diagnostic.notes.push(target_note(&reported_by));
+            }
+
+            diagnostic
+        })
+        .collect()
+}
+
+fn target_note(reported_by: &[&str]) -> String {
+    let names = reported_by
+        .iter()
+        .map(|target| format!("`{target}`"))
+        .collect::<Vec<_>>();
+
+    match names.as_slice() {
+        [name] => format!("only on the {name} target"),
+        [rest @ .., last] => format!("only on the {} and {last} targets", rest.join(", ")),
+        [] => unreachable!("diagnostics always come from at least one target"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lints::{Diagnostic, Label, Severity},
+        CheckerConfig,
+    };
+
+    fn diagnostic(message: &str, range: (u32, u32)) -> CheckerDiagnostic {
+        CheckerDiagnostic {
+            diagnostic: Diagnostic::new("test", message.to_owned(), Label::new(range)),
+            severity: Severity::Warning,
+        }
+    }
+
+    #[test]
+    fn test_merge_target_diagnostics() {
+        let merged = merge_target_diagnostics(
+            vec![
+                (
+                    "lua51",
+                    vec![
+                        diagnostic("both", (0, 1)),
+                        diagnostic("lua51 only", (2, 3)),
+                        diagnostic("different ranges", (4, 5)),
+                    ],
+                ),
+                (
+                    "luau",
+                    vec![
+                        diagnostic("both", (0, 1)),
+                        diagnostic("luau only", (2, 3)),
+                        diagnostic("different ranges", (6, 7)),
+                    ],
+                ),
+            ],
+            2,
+        );
+
+        let messages = merged
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.diagnostic.message.as_str(),
+                    diagnostic.diagnostic.notes.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            vec![
+                ("both", vec![]),
+                ("lua51 only", vec!["only on the `lua51` target".to_owned()]),
+                (
+                    "different ranges",
+                    vec!["only on the `lua51` target".to_owned()]
+                ),
+                ("luau only", vec!["only on the `luau` target".to_owned()]),
+                (
+                    "different ranges",
+                    vec!["only on the `luau` target".to_owned()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_same_target_duplicates() {
+        let merged = merge_target_diagnostics(
+            vec![
+                (
+                    "lua51",
+                    vec![diagnostic("twice", (0, 1)), diagnostic("twice", (0, 1))],
+                ),
+                ("luau", vec![diagnostic("twice", (0, 1))]),
+            ],
+            2,
+        );
+
+        assert_eq!(merged.len(), 2);
+        assert!(merged[0].diagnostic.notes.is_empty());
+        assert_eq!(
+            merged[1].diagnostic.notes,
+            vec!["only on the `lua51` target".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_several_targets_note() {
+        let merged = merge_target_diagnostics(
+            vec![
+                ("lua51", vec![diagnostic("no luau", (0, 1))]),
+                ("lua52", vec![diagnostic("no luau", (0, 1))]),
+                ("luajit", vec![diagnostic("no luau", (0, 1))]),
+                ("luau", Vec::new()),
+            ],
+            4,
+        );
+
+        assert_eq!(
+            merged[0].diagnostic.notes,
+            vec!["only on the `lua51`, `lua52` and `luajit` targets".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_missing_targets() {
+        // `lua51` couldn't parse the file, so it isn't in the list
+        let merged =
+            merge_target_diagnostics(vec![("lua53", vec![diagnostic("lua53", (0, 1))])], 2);
+
+        assert_eq!(
+            merged[0].diagnostic.notes,
+            vec!["only on the `lua53` target".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_std_targets() {
+        let single: CheckerConfig<toml::Value> = toml::from_str(r#"std = "lua51+roblox""#).unwrap();
+        assert_eq!(single.std_targets(), vec!["lua51+roblox"]);
+
+        let targets: CheckerConfig<toml::Value> =
+            toml::from_str(r#"std = ["lua51", "luajit", "luau"]"#).unwrap();
+        assert_eq!(targets.std_targets(), vec!["lua51", "luajit", "luau"]);
+
+        let default: CheckerConfig<toml::Value> = toml::from_str("").unwrap();
+        assert_eq!(default.std_targets(), vec!["lua51"]);
+
+        assert!(toml::from_str::<CheckerConfig<toml::Value>>("std = []").is_err());
+    }
+}
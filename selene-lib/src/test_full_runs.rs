@@ -1,7 +1,8 @@
 use crate::{
-    test_util::{test_full_run_config, test_full_run_config_with_output},
-    CheckerConfig,
+    test_util::{test_full_run, test_full_run_config, test_full_run_config_with_output},
+    CheckerConfig, LintVariation,
 };
+use std::collections::HashMap;
 
 #[test]
 fn function_overriding() {
@@ -31,3 +32,29 @@ fn test_std_mistakes() {
 fn test_std_mistakes_roblox() {
     test_full_run_config("std_mistakes", "roblox_mistakes", CheckerConfig::default());
 }
+
+#[test]
+fn test_use_before_definition() {
+    test_full_run("use_before_definition", "use_before_definition");
+}
+
+#[test]
+fn test_use_before_definition_allowed_in_config() {
+    test_full_run_config(
+        "use_before_definition",
+        "allowed_in_config",
+        CheckerConfig {
+            lints: {
+                let mut map = HashMap::new();
+                map.insert("use_before_definition".to_owned(), LintVariation::Allow);
+                map
+            },
+            ..CheckerConfig::default()
+        },
+    );
+}
+
+#[test]
+fn test_use_before_definition_allowed_by_comment() {
+    test_full_run("use_before_definition", "allowed_by_comment");
+}
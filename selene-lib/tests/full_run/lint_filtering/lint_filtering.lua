@@ -1,6 +1,6 @@
 --# selene: allow(type_check_inside_call)
 
--- selene: allow(unused_variable)
+-- selene: allow(unused_variable, unused_parameter)
 local function a(x, y)
     local unusedHereToo = true
 end
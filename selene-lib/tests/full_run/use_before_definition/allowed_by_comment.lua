@@ -0,0 +1,10 @@
+-- selene: allow(use_before_definition)
+local fib = function(n)
+    if n < 2 then
+        return n
+    end
+
+    return fib(n - 1) + fib(n - 2)
+end
+
+return fib
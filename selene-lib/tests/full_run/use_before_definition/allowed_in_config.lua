@@ -0,0 +1,15 @@
+local fib = function(n)
+    if n < 2 then
+        return n
+    end
+
+    return fib(n - 1) + fib(n - 2)
+end
+
+local function uses_later_local()
+    return later
+end
+
+local later = 1
+
+return fib, uses_later_local, later
@@ -0,0 +1,19 @@
+local enabled = condition and false or true
+local value = condition and nil or default
+local parenthesized = condition and (false) or default
+
+local match = condition and string.match(text, "%d+") or "none"
+local found = isLooking and string.find(text, "x") or 0
+local number = useNumber and tonumber(input) or 0
+
+-- The middle value can't be falsy
+local name = condition and "yes" or "no"
+local length = condition and #list or 0
+local upper = condition and string.upper(text) or text
+
+-- Unknown functions aren't checked
+local unknown = condition and getValue() or default
+
+-- Locals named like standard library functions aren't either
+local tonumber = function() return 1 end
+local shadowed = condition and tonumber(input) or 0
@@ -0,0 +1,2 @@
+local enabled = condition and false or true
+local match = condition and string.match(text, "%d+") or "none"
@@ -0,0 +1,39 @@
+print(string.format("%d apples", 5))
+print(string.format("%s has %d apples", "Bob", 5))
+print(string.format("100%% done"))
+print(string.format("%5.2f", 1.5))
+print(string.format("%-10s|", "left"))
+
+-- Too few values
+print(string.format("%s has %d apples", "Bob"))
+
+-- Too many values
+print(string.format("%s", "a", "b", "c"))
+
+-- Invalid conversions
+print(string.format("%y", 1))
+print(string.format("50%", 1))
+print(string.format("%100d", 1))
+
+-- Obvious type mismatches
+print(string.format("%d", "hello"))
+print(string.format("%d", "10"))
+print(string.format("%f", {}))
+print(string.format("%x", nil))
+print(string.format("%s", nil))
+
+-- Any number of values could come from a call or `...`
+print(string.format("%s %s", ...))
+print(string.format("%s %s", unpack({ "a", "b" })))
+print(string.format("%s", "a", unpack({ "b" })))
+
+-- Method calls on literals
+print(("%d + %d"):format(1))
+print(("%d"):format(1))
+
+-- Formats that aren't literals can't be checked
+local format = "%d %d"
+print(string.format(format, 1))
+
+local string = { format = function() end }
+print(string.format("%d %d", 1))
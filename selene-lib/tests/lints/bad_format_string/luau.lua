@@ -0,0 +1,3 @@
+print(string.format("%* and %*", {}, nil))
+print(string.format("%*", 1, 2))
+print(string.pack("i4", 1))
@@ -0,0 +1,10 @@
+print(os.date("%Y-%m-%d %H:%M:%S"))
+print(os.date("!%c"))
+print(os.date("*t"))
+print(os.date("!*t", 0))
+print(os.date("%Ey %Od"))
+print(os.date("%%"))
+
+print(os.date("%Q"))
+print(os.date("%Ea"))
+print(os.date("today is %"))
@@ -0,0 +1,15 @@
+print(string.pack("i4", 100))
+print(string.pack("<i4 s1 z", 100, "hello", "world"))
+print(string.pack("!4 i2 Xi4 d", 1, 2.5))
+print(string.unpack("i4", "...."))
+print(string.packsize("i4 i8"))
+
+print(string.pack("i4 i4", 1))
+print(string.pack("i4", 1, 2))
+print(string.pack("i4 s", 1, {}))
+print(string.pack("i4", "hello"))
+print(string.pack("q", 1))
+print(string.pack("i17", 1))
+print(string.unpack("X", "...."))
+print(string.packsize("i4 s4"))
+print(("i4 i4"):pack(1))
@@ -0,0 +1,44 @@
+local text = "hello world"
+
+print(string.match(text, "%a+"))
+print(string.find(text, "^(%w+)%s*(%w+)$"))
+print(string.match(text, "[%w_]+"))
+print(string.match(text, "[]]"))
+print(string.match(text, "[^%]]"))
+print(string.match(text, "%b()"))
+print(string.match(text, "%f[%w]%w+"))
+print(string.match(text, "(a)%1"))
+print(string.match(text, "()"))
+print(text:match("%d+%.%d+"))
+print(text:gmatch("%S+"))
+print(string.match(text, "\\%d"))
+
+-- Malformed patterns
+print(string.match(text, "50%"))
+print(string.match(text, "[abc"))
+print(string.match(text, "[%a"))
+print(string.match(text, "%b("))
+print(string.match(text, "%f%w"))
+print(string.match(text, "(%a+"))
+print(string.match(text, "%a+)"))
+print(string.match(text, "(a)%2"))
+print(string.match(text, "(a%1)"))
+print(text:find("[%"))
+print(text:gmatch("%d+("))
+
+-- Escapes that aren't classes only match the letter
+print(string.match(text, "%y"))
+print(string.match(text, "[%q]"))
+print(string.match(text, "\x25y"))
+
+-- Plain finds don't use patterns
+print(string.find(text, "[", 1, true))
+print(text:find("(", 1, true))
+print(text:find("%y", 1, false))
+
+-- Patterns that aren't literals can't be checked
+local pattern = "[abc"
+print(string.match(text, pattern))
+
+local string = { match = function() end }
+print(string.match(text, "[abc"))
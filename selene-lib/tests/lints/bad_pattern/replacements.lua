@@ -0,0 +1,13 @@
+local text = "hello world"
+
+print(string.gsub(text, "(%w+) (%w+)", "%2 %1"))
+print(string.gsub(text, "%w+", "<%0>"))
+print(string.gsub(text, "%w+", "<%1>"))
+print(string.gsub(text, "%%", "%%"))
+print(text:gsub("o", "0"))
+
+print(string.gsub(text, "(%w+)", "%2"))
+print(string.gsub(text, "%w+", "%2"))
+print(string.gsub(text, "%w+", "100%"))
+print(string.gsub(text, "%w+", "%s"))
+print(text:gsub("(o)", "[%3]"))
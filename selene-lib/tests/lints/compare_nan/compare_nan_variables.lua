@@ -10,4 +10,5 @@ local _ = y.foo() == 0/0
 local _ = y.bar() ~= 0/0
 
 local _ = x == 1
-local _ = 1 == 0
\ No newline at end of file
+local _ = 1 == 0
+local _ = x ~= (0 / 0.0)
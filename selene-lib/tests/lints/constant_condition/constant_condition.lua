@@ -0,0 +1,70 @@
+if true then
+    print("always")
+end
+
+if false then
+    print("never")
+elseif "string" then
+    print("always")
+end
+
+if nil then end
+if 1 == 2 then end
+if not (1 == 1) then end
+
+while 1 do
+    print("forever")
+end
+
+while false do
+    print("never")
+end
+
+while 1 do
+    if done() then
+        break
+    end
+end
+
+while "x" do
+    for i = 1, 10 do
+        break
+    end
+end
+
+local function f()
+    while 1 do
+        return
+    end
+end
+
+local greater = a.b > a.b
+local equal = "a" == "b"
+local lessThan = 1 < 2
+
+-- Always the same, and not a NaN check
+local concatenated = x .. "a" == x .. "a"
+local notConcatenated = x .. "a" ~= x .. "a"
+
+-- Checking for NaN
+local isNan = x ~= x
+local fieldIsNan = a.b ~= a.b
+local isNotNan = x == x
+local fieldIsNotNan = a.b == a.b
+
+-- Returning early
+local function stopEarly()
+    if true then
+        return
+    end
+
+    print("skipped")
+end
+
+-- Not constant
+if x then end
+if x == y then end
+while true do
+    print("idiomatic")
+end
+local calls = f() == f()
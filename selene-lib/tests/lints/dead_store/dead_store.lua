@@ -0,0 +1,83 @@
+local t = {}
+t = load()
+print(t)
+
+local count = 0
+count = 10
+count = 20
+print(count)
+
+local function f(a)
+    a = 1
+    a = 2
+    return a
+end
+
+-- Read before being overwritten
+local read = 1
+print(read)
+read = 2
+print(read)
+
+local incremented = 1
+incremented = incremented + 1
+print(incremented)
+
+-- Only overwritten on some paths
+local maybe = 1
+if condition then
+    maybe = 2
+end
+print(maybe)
+
+-- Overwritten on every path after a branch that doesn't read it
+local always = 1
+if condition then
+    print("hello")
+end
+always = 2
+print(always)
+
+-- nil is just a placeholder
+local placeholder = nil
+placeholder = 1
+print(placeholder)
+
+-- Underscore prefixed names are ignored
+local _ignored = 1
+_ignored = 2
+
+-- Closures could read it whenever they're called
+local captured = 1
+local function read_captured()
+    return captured
+end
+read_captured()
+captured = 2
+read_captured()
+
+-- Jumps could skip the second assignment
+local result
+while true do
+    result = 1
+    if condition then
+        break
+    end
+    result = 2
+end
+print(result)
+
+-- Loops read on the next iteration
+local loop = 1
+while condition do
+    print(loop)
+    loop = 2
+end
+
+-- Globals aren't tracked
+global = 1
+global = 2
+
+local a, b = 1, 2
+a, b = b, a
+print(a, b)
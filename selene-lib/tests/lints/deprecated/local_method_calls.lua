@@ -0,0 +1,10 @@
+local ws = workspace
+ws:FindFirstChild("Part")
+ws:findFirstChild("Part")
+
+local part = Instance.new("Part")
+part.Anchored = true
+print(part.BrickColor)
+
+local unknown = {}
+unknown:findFirstChild("Part")
@@ -3,4 +3,7 @@ local _ = 5 / 0
 local _ = 0 / 5
 local _ = x / 0
 local _ = 0 / 0
-local _ = -1 / 0
\ No newline at end of file
+local _ = -1 / 0
+local _ = 1 / (1 - 1)
+local _ = x / 0.0
+local _ = (1 - 1) / 0
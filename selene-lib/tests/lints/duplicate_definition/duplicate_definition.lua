@@ -0,0 +1,55 @@
+local M = {}
+
+function M.foo()
+    return 1
+end
+
+function M.bar()
+    return M.foo()
+end
+
+function M.foo()
+    return 2
+end
+
+function M:method() end
+M.method = function() end
+
+M.nested = {}
+function M.nested.value() end
+function M.nested.value() end
+
+local function helper() end
+helper = function() end
+
+function globalFunction() end
+function globalFunction() end
+
+-- Different paths
+function M.a() end
+function M.b() end
+
+-- Wrapping the previous definition is fine
+local oldFoo = M.foo
+function M.foo()
+    return oldFoo() + 1
+end
+
+-- Passing the module along is fine
+register(M)
+function M.foo() end
+
+-- Definitions in different blocks are fine
+if condition then
+    function M.conditional() end
+else
+    function M.conditional() end
+end
+
+local function shadowed() end
+local function shadowed() end
+
+M.value = 1
+M.value = 2
+
+return M
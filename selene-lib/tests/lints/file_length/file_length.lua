@@ -0,0 +1,7 @@
+local a = 1
+local b = 2
+
+-- comment
+
+local c = 3
+local d = 4
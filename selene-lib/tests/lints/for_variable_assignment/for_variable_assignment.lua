@@ -0,0 +1,38 @@
+for i = 1, 10 do
+    if skip(i) then
+        i = i + 1
+    end
+
+    print(i)
+end
+
+for index = 10, 1, -1 do
+    index, other = index - 1, 2
+end
+
+-- Shadowing the loop variable is fine
+for i = 1, 10 do
+    local i = i * 2
+    i = i + 1
+end
+
+-- Assigning a field of the loop variable isn't assigning the loop variable
+for i = 1, 10 do
+    i.x = 1
+end
+
+-- Generic for loops are not checked
+for _, value in ipairs(list) do
+    value = value + 1
+end
+
+local i = 0
+for _ = 1, 10 do
+    i = i + 1
+end
+
+for i = 1, 10 do
+    local function f()
+        i = 2
+    end
+end
@@ -0,0 +1,17 @@
+local function short()
+    return 1
+end
+
+local function long()
+    local a = 1
+    local b = 2
+    return a + b
+end
+
+local callback = function(x)
+    print(x)
+    print(x)
+    print(x)
+end
+
+function module.oneLine() return 1 end
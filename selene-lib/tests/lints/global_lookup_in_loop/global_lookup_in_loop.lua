@@ -0,0 +1,31 @@
+for i = 1, 10 do
+    print(math.floor(i / 2), math.floor(i / 3))
+    print(math.pi)
+    print(string.format("%d", i))
+end
+
+while running do
+    for _, value in ipairs(values) do
+        total = total + math.floor(value)
+    end
+end
+
+-- Already localized
+local floor = math.floor
+for i = 1, 10 do
+    print(floor(i))
+end
+
+-- Shadowed
+local math = { floor = function() end }
+for i = 1, 10 do
+    math.floor(i)
+end
+
+-- Not in the standard library
+for i = 1, 10 do
+    game.workspace(i)
+    print(i)
+end
+
+print(string.rep("a", 3))
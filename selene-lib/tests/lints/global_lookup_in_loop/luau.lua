@@ -0,0 +1,3 @@
+for i = 1, 10 do
+    print(math.floor(i / 2))
+end
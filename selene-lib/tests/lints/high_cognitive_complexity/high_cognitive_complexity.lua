@@ -0,0 +1,46 @@
+-- 1 (if) + 2 (nested for) + 3 (nested if) = 6
+local function nested()
+    if a then
+        for i = 1, 10 do
+            if i then
+                print(i)
+            end
+        end
+    end
+end
+
+-- 1 (if) + 1 (elseif) + 1 (else) = 3
+local function flat()
+    if a then
+        print(1)
+    elseif b then
+        print(2)
+    else
+        print(3)
+    end
+end
+
+-- 1 (if) + 1 (`and` run) + 1 (`or` run) + 1 (`and` run) = 4
+local function operators()
+    if a and b and c or d and e then
+        print(1)
+    end
+end
+
+-- The inner function is measured on its own, 1 (while) + 1 (`or`) = 2
+local function outer()
+    return function()
+        while a or b do
+            print(1)
+        end
+    end
+end
+
+-- 1 (repeat) + 2 (nested if) + 1 (`and`) = 4
+local callback = function()
+    repeat
+        if a and b then
+            print(1)
+        end
+    until true
+end
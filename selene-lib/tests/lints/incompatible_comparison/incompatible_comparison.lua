@@ -0,0 +1,40 @@
+if type(x) == "strnig" then end
+if "tabel" ~= type(x) then end
+if type(x) == "integer" then end
+if type(x) == "Vector3" then end
+
+local kind = type(x)
+if kind == "fucntion" then end
+
+-- Valid type names
+if type(x) == "string" or type(x) == "nil" then end
+if kind == "function" then end
+
+-- typeof is only a type function on Roblox
+if typeof(x) == "Vector3" then end
+
+-- A local `type` isn't the builtin one
+local function check(type)
+    return type(x) == "strnig"
+end
+
+for i = 1, 10 do
+    if i == "1" then end
+    if i ~= "first" then end
+end
+
+local count = 0
+if count == "0" then end
+if #list == "3" then end
+if (a + b) == "3" then end
+
+-- Could be a string
+local maybe = 0
+maybe = "0"
+if maybe == "0" then end
+if x == "1" then end
+
+for i = 1, 10 do
+    i = tostring(i)
+    if i == "1" then end
+end
@@ -0,0 +1,7 @@
+if typeof(x) == "Vector3" then end
+if typeof(x) == "Instance" then end
+if typeof(x) == "buffer" then end
+if typeof(x) == "Part" then end
+if typeof(x) == "Vecter3" then end
+if typeof(x) == "cframe" then end
+if type(x) == "Vector3" then end
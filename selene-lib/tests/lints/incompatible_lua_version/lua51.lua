@@ -0,0 +1,19 @@
+local values = table.unpack({ 1, 2, 3 })
+local packed = table.pack(1, 2, 3)
+local flags = bit32.band(0xFF, 0x0F)
+local length = rawlen({})
+local moved = table.move({ 1 }, 1, 1, 1, {})
+
+-- Available in Lua 5.1
+local unpacked = unpack({ 1, 2, 3 })
+setfenv(1, {})
+local power = math.pow(2, 3)
+
+-- Locals aren't the standard library
+local table = {}
+table.unpack({})
+
+local bit32 = require("bit32")
+bit32.band(1, 2)
+
+local pack = string.pack
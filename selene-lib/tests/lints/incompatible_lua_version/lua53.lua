@@ -0,0 +1,20 @@
+local flags = bit32.band(0xFF, 0x0F)
+local bor = bit32.bor
+setfenv(1, {})
+local env = getfenv()
+local values = unpack({ 1, 2, 3 })
+local chunk = loadstring("return 1")
+local power = math.pow(2, 3)
+local log = math.log10(100)
+
+-- Available in Lua 5.3
+local unpacked = table.unpack({ 1, 2, 3 })
+local packed = string.pack("i4", 1)
+local length = utf8.len("hello")
+local moved = table.move({ 1 }, 1, 1, 1, {})
+
+local function unpack(list)
+    return list[1]
+end
+
+unpack({ 1 })
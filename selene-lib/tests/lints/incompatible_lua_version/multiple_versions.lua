@@ -0,0 +1,11 @@
+-- Only in LuaJIT
+setfenv(1, {})
+local values = unpack({ 1, 2, 3 })
+
+-- Only in Lua 5.4
+local unpacked = table.unpack({ 1, 2, 3 })
+local length = utf8.len("hello")
+
+-- In both
+local joined = table.concat({ "a", "b" })
+local floor = math.floor(1.5)
@@ -0,0 +1,71 @@
+local function fallsOffEnd(x)
+    if x then
+        return 1
+    end
+end
+
+local function bareReturn(x)
+    if x then
+        return
+    end
+
+    return x
+end
+
+local function allReturn(x)
+    if x then
+        return 1
+    else
+        return 2
+    end
+end
+
+local function errors(x)
+    if x then
+        return 1
+    end
+
+    error("x is required")
+end
+
+local function noReturns(x)
+    if x then
+        print(x)
+    end
+end
+
+local function tailCall(x)
+    if x then
+        return print(x)
+    end
+end
+
+local function loops()
+    while true do
+        if math.random() > 0.5 then
+            return 1
+        end
+    end
+end
+
+local t = {
+    callback = function(x)
+        for _, value in ipairs(x) do
+            if value then
+                return value
+            end
+        end
+    end,
+}
+
+function t.method(x)
+    repeat
+        if x then
+            return
+        end
+    until x
+
+    return 1
+end
+
+return fallsOffEnd, bareReturn, allReturn, errors, noReturns, tailCall, loops, t
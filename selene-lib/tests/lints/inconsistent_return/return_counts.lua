@@ -0,0 +1,43 @@
+local function counts(x)
+    if x then
+        return 1, 2
+    end
+
+    return 1
+end
+
+local function errorTuple(x)
+    if x then
+        return nil, "x is required"
+    end
+
+    return x
+end
+
+local function variadic(...)
+    if ... then
+        return 1, ...
+    end
+
+    return 1
+end
+
+local function parenthesized(f)
+    if f then
+        return (f())
+    end
+
+    return 1
+end
+
+local function manyCounts(x)
+    if x == 1 then
+        return 1
+    elseif x == 2 then
+        return 1, 2
+    else
+        return 1, 2, 3
+    end
+end
+
+return counts, errorTuple, variadic, parenthesized, manyCounts
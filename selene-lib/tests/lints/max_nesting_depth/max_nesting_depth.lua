@@ -0,0 +1,41 @@
+if a then
+    for i = 1, 10 do
+        print(i)
+    end
+end
+
+if a then
+    for i = 1, 10 do
+        while b do
+            if c then
+                print(c)
+            end
+        end
+    end
+else
+    do
+        repeat
+            print(1)
+        until true
+    end
+end
+
+local function f()
+    -- Functions start over from 0
+    if a then
+        if b then
+            print(b)
+        end
+    end
+end
+
+for _ = 1, 10 do
+    local function g()
+        if a then
+            while b do
+                repeat
+                until true
+            end
+        end
+    end
+end
@@ -0,0 +1,8 @@
+local function fine(a, b) end
+local function tooMany(a, b, c) end
+local function varargs(a, b, ...) end
+local callback = function(a, b, c, d) end
+
+local Class = {}
+function Class:method(a, b) end
+function Class.func(a, b, c) end
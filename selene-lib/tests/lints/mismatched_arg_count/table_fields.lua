@@ -0,0 +1,30 @@
+local M = {}
+
+function M.f(a) end
+M.f(1)
+M.f(1, 2)
+
+function M:m(a) end
+M:m(1)
+M:m(1, 2)
+M.m(M, 1, 2)
+
+local N = {
+    g = function(a, b) end,
+}
+
+N.g(1, 2)
+N.g(1, 2, 3)
+
+-- Fields that are reassigned can be called with the arguments of any definition
+local O = {}
+O.h = function(a) end
+O.h = function(a, b) end
+O.h(1, 2)
+O.h(1, 2, 3)
+
+-- Tables written to with dynamic keys could have any field replaced
+local P = {}
+function P.i(a) end
+P[getKey()] = function(...) end
+P.i(1, 2)
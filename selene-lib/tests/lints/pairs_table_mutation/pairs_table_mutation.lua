@@ -0,0 +1,56 @@
+local t = {}
+
+for key, value in pairs(t) do
+    t[key .. "_copy"] = value
+    t.extra = true
+    table.insert(t, value)
+end
+
+for key in next, t do
+    t[#t + 1] = key
+end
+
+-- Assigning to the current key or clearing keys is allowed
+for key, value in pairs(t) do
+    t[key] = value * 2
+    t[key] = nil
+    t.other = nil
+end
+
+-- Other tables are fine to change
+local copy = {}
+for key, value in pairs(t) do
+    copy[key .. "_copy"] = value
+    table.insert(copy, value)
+end
+
+-- ipairs isn't checked
+for index, value in ipairs(t) do
+    t[index + 1] = value
+end
+
+-- The key of another loop isn't the key being iterated
+for key in pairs(t) do
+    for otherKey in pairs(copy) do
+        t[otherKey] = true
+    end
+end
+
+-- Globals are checked too
+for key, value in pairs(globalTable) do
+    globalTable[value] = key
+end
+
+-- A local `pairs` might not be the builtin one
+local function check()
+    local pairs = myPairs
+
+    for key, value in pairs(t) do
+        t[value] = key
+    end
+end
+
+-- Nested fields aren't the table being iterated over
+for key, value in pairs(t) do
+    t[key].child = value
+end
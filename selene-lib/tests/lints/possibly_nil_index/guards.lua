@@ -0,0 +1,77 @@
+local function ifGuard(text)
+    local name = string.match(text, "%a+")
+
+    if name then
+        print(name:upper())
+    end
+
+    if name ~= nil and name:len() > 1 then
+        print(name:upper())
+    end
+
+    print(name:upper())
+end
+
+local function notGuard(text)
+    local name = string.match(text, "%a+")
+
+    if not name then
+        print(name:upper())
+        return
+    end
+
+    print(name:upper())
+end
+
+local function nilGuard(text)
+    local name = string.match(text, "%a+")
+
+    if name == nil then
+        error("no name")
+    end
+
+    print(name:upper())
+end
+
+local function assertGuard(text)
+    local name = string.match(text, "%a+")
+    assert(name, "no name")
+    print(name:upper())
+end
+
+local function defaultGuard(text)
+    local name = string.match(text, "%a+")
+    name = name or "default"
+    print(name:upper())
+end
+
+local function shortCircuit(text)
+    local name = string.match(text, "%a+")
+    print(name and name:upper())
+    print(not name or name:upper())
+    print(name or name:upper())
+end
+
+local function elseGuard(text)
+    local name = string.match(text, "%a+")
+
+    if not name then
+        name = "default"
+    elseif name:len() > 10 then
+        name = name:sub(1, 10)
+    end
+
+    print(name:upper())
+end
+
+local function loopGuard(text)
+    local name = string.match(text, "%a+")
+
+    while name == nil do
+        name = string.match(text, "%d+")
+    end
+
+    print(name:upper())
+end
+
+return ifGuard, notGuard, nilGuard, assertGuard, defaultGuard, shortCircuit, elseGuard, loopGuard
@@ -0,0 +1,14 @@
+local map = workspace:FindFirstChild("Map")
+print(map.Name)
+
+local spawn = workspace:WaitForChild("Spawn")
+print(spawn.Name)
+
+-- Locals holding structs are followed too
+local found = spawn:FindFirstChild("Location")
+found.Name = "Location"
+
+local checked = spawn:FindFirstChild("Checked")
+if checked then
+    checked.Name = "Checked"
+end
@@ -0,0 +1,41 @@
+local function unguarded(text)
+    local name = string.match(text, "%a+")
+    print(name:upper())
+    -- `name` would've errored above if it was nil
+    print(name:lower())
+end
+
+local function fields(t)
+    local index = string.find(t.text, "x")
+    print(index.x)
+end
+
+local function conditional(text, check)
+    local name = string.match(text, "%a+")
+
+    if check then
+        name = "default"
+    end
+
+    print(name:upper())
+end
+
+local function inLoop(list)
+    for _, text in ipairs(list) do
+        local number = tonumber(text)
+        number.x = 1
+    end
+end
+
+local function shadowed(text)
+    local string = { match = function() return "" end }
+    local name = string.match(text, "%a+")
+    print(name:upper())
+end
+
+local function unknown(text)
+    local name = getName(text)
+    print(name:upper())
+end
+
+return unguarded, fields, conditional, inLoop, shadowed, unknown
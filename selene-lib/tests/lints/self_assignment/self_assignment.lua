@@ -0,0 +1,39 @@
+local x = 1
+x = x
+
+local a = { b = {} }
+a.b = a.b
+a.b.c = a.b.c
+a["b"] = a["b"]
+
+local y = 2
+x, y = x, y
+
+-- Not self assignments
+x, y = y, x
+a.b = a.c
+a[f()] = a[f()]
+call().b = call().b
+x = (x)
+
+local z = 3
+local z = z
+
+local function f(parameter)
+    local parameter = parameter
+    return parameter
+end
+
+-- Caching a global or an outer local is useful
+local print = print
+local math = math
+
+local outer = 1
+do
+    local outer = outer
+    outer = 2
+end
+
+for i = 1, 10 do
+    local i = i
+end
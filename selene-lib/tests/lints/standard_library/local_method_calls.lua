@@ -0,0 +1,17 @@
+local ws = workspace
+ws:FindFirstChild("Part")
+ws:FindFirstChld("Part")
+ws:FindFirstChild()
+ws.FindFirstChild("Part")
+
+local services = {
+    players = game:GetService("Players"),
+}
+
+print(services.players.LocalPlayer)
+print(services.players.Nonsense)
+services.players:GetPlayers()
+services.players:GetPlayers(1)
+
+local alias = ws
+alias:FindFirstChild(1)
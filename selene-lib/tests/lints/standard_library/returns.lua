@@ -0,0 +1,34 @@
+local part = Instance.new("Part")
+part.Anchored = true
+part.Nonsense = 1
+print(part.Anchored, part.Nonsense)
+
+-- Properties holding a struct can be set, and are still checked
+part.Parent = Instance.new("Folder")
+part.Parent.Name = "Parent"
+part.Parent.Size = 5
+part.Parent:Nonsense()
+
+local folder = Instance.new("Folder")
+folder.Name = "Folder"
+folder.Size = 5
+
+local workspace = game:GetService("Workspace")
+workspace.Gravity = 10
+workspace.Nonsense = 10
+
+local first, second = makeFolders()
+first.Name = "First"
+second.Nonsense = "Second"
+
+-- Unknown structs and non-struct returns aren't checked
+local unknown = Instance.new("Unknown")
+unknown.Nonsense = 1
+
+local count = getCount()
+count.Nonsense = 1
+
+-- Locals that are reassigned could be anything
+local reassigned = Instance.new("Part")
+reassigned = {}
+reassigned.Nonsense = 1
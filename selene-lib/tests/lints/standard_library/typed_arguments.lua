@@ -0,0 +1,28 @@
+maybe(1)
+maybe(nil)
+maybe("1")
+
+either(1)
+either("1")
+either(true)
+
+options("fast")
+options(nil)
+options("medium")
+
+counts({ a = 1, b = 2 })
+counts({ a = "1" })
+counts({ 1, 2 })
+
+tween({ Time = 1, Style = "Linear" })
+tween({ Time = 1, Speed = 2 })
+tween(5)
+
+each({}, function(key, value) end)
+each({}, function(key) end)
+each({}, function(...) end)
+each({}, function(key, value, extra) end)
+
+max(1)
+max(1, 2, 3)
+max(1, 2, "3")
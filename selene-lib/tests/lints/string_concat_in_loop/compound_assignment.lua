@@ -0,0 +1,8 @@
+local output = ""
+for _, line in lines do
+    output ..= line
+end
+
+for i = 1, 10 do
+    output += 1
+end
@@ -0,0 +1,40 @@
+local output = ""
+for _, line in ipairs(lines) do
+    output = output .. line .. "\n"
+end
+
+local state = {}
+while running do
+    state.buffer = state.buffer .. read()
+end
+
+-- Declared inside the loop, so it starts over every iteration
+for _, row in ipairs(rows) do
+    local line = ""
+    line = line .. row.name
+    print(line)
+end
+
+-- Only the innermost loop matters
+for _, row in ipairs(rows) do
+    local line = ""
+    for _, cell in ipairs(row) do
+        line = line .. cell
+    end
+    print(line)
+end
+
+-- Prepending isn't an accumulation of the same variable
+for i = 1, 10 do
+    output = "x" .. output
+end
+
+-- Not in a loop
+output = output .. "done"
+
+-- Only defined in the loop, not run by it
+for i = 1, 10 do
+    callbacks[i] = function()
+        output = output .. i
+    end
+end
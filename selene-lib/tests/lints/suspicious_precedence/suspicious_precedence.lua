@@ -0,0 +1,25 @@
+if not x == y then end
+if not player.Health > 0 then end
+local isMissing = not value ~= nil
+
+local squared = -x ^ 2
+local negative = -2^2
+
+local message = "total: " .. count + 1
+local label = a * b .. "px"
+local both = a + 1 .. b - 1
+
+-- Parenthesized forms are clear
+if not (x == y) then end
+if (not x) == y then end
+local explicit = -(x ^ 2)
+local negativeSquared = (-x) ^ 2
+local concatenated = "total: " .. (count + 1)
+
+-- Not confusing
+if not x then end
+if not x and y then end
+local power = x ^ -2
+local subtract = -x + 2
+local joined = a .. b .. c
+local compared = a + 1 == b
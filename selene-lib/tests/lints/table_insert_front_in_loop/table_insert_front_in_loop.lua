@@ -0,0 +1,22 @@
+local reversed = {}
+for _, value in ipairs(list) do
+    table.insert(reversed, 1, value)
+end
+
+while true do
+    table.insert(queue, (1), next())
+end
+
+-- Inserting at the end is fine
+for _, value in ipairs(list) do
+    table.insert(reversed, value)
+    table.insert(reversed, #reversed + 1, value)
+end
+
+-- Not in a loop
+table.insert(reversed, 1, "first")
+
+local table = {}
+for _, value in ipairs(list) do
+    table.insert(reversed, 1, value)
+end
@@ -0,0 +1,25 @@
+local isEven, isOdd
+
+function isEven(n)
+    if n == 0 then
+        return true
+    end
+
+    return isOdd(n - 1)
+end
+
+function isOdd(n)
+    if n == 0 then
+        return false
+    end
+
+    return isEven(n - 1)
+end
+
+local helper
+
+function helper()
+    return 1
+end
+
+print(helper() + 1, isEven(4))
@@ -0,0 +1,47 @@
+local function in_loop(items)
+    local last
+    for _, item in ipairs(items) do
+        last = item
+    end
+
+    print(last.name)
+end
+
+local function assigned_in_loop(items)
+    local total
+    for _ = 1, #items do
+        total = 0
+    end
+
+    return total
+end
+
+local function while_loop()
+    local value
+    while value == nil do
+        value = math.random()
+    end
+
+    print(value + 1)
+end
+
+local function repeat_loop()
+    local value
+    repeat
+        value = math.random()
+    until value > 0.5
+
+    print(value + 1)
+end
+
+local function previous_iteration(items)
+    local previous
+    for _, item in ipairs(items) do
+        if previous then
+            print(previous + item)
+        end
+
+        print(previous * 2)
+        previous = item
+    end
+end
@@ -0,0 +1,106 @@
+local function maybe(condition)
+    local x
+    if condition then
+        x = 1
+    end
+
+    print(x + 1)
+end
+
+local function definitely()
+    local y
+    print(y)
+end
+
+local function every_path(condition)
+    local z
+    if condition then
+        z = 1
+    else
+        z = 2
+    end
+
+    print(z + 1)
+end
+
+local function checked(condition)
+    local x
+    if condition then
+        x = {}
+    end
+
+    -- Checking for nil is fine
+    if x then
+        print(x.field)
+    end
+
+    print(x ~= nil)
+end
+
+local function reported_once(condition)
+    local x
+    if condition then
+        x = "hello"
+    end
+
+    print(#x)
+    print(#x)
+end
+
+local function indexing(condition)
+    local t
+    if condition then
+        t = {}
+    end
+
+    t.field = 1
+end
+
+local function calling(condition)
+    local callback
+    if condition then
+        callback = print
+    end
+
+    callback()
+end
+
+local function closure_assigned()
+    local value
+    local function set()
+        value = 1
+    end
+
+    set()
+    print(value + 1)
+end
+
+local function multiple_names()
+    local a, b
+    a = 1
+    print(a + 1, b .. "")
+end
+
+local function returned_early(condition)
+    local x
+    if not condition then
+        return
+    end
+
+    x = 1
+    print(x + 1)
+end
+
+local function error_path(condition)
+    local x
+    if condition then
+        x = 1
+    else
+        error("no value")
+    end
+
+    print(x + 1)
+end
+
+local top
+print(top)
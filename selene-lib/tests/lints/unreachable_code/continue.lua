@@ -0,0 +1,23 @@
+for i = 1, 10 do
+    if i % 2 == 0 then
+        continue
+    end
+
+    print(i)
+end
+
+for i = 1, 10 do
+    do
+        continue
+    end
+
+    print("unreachable")
+end
+
+local function f()
+    error("oops")
+    type Unused = number
+    print("unreachable")
+end
+
+return f
@@ -0,0 +1,23 @@
+for i = 1, 10 do
+    if i % 2 == 0 then
+        goto continue
+    end
+
+    print(i)
+
+    ::continue::
+end
+
+goto skip
+print("unreachable")
+::skip::
+
+print("reachable")
+
+do
+    ::top::
+    print("loops forever")
+    goto top
+end
+
+print("unreachable")
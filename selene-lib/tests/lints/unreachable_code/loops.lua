@@ -0,0 +1,57 @@
+local function infiniteWhile()
+    while true do
+        print("forever")
+    end
+
+    print("unreachable")
+end
+
+local function whileWithBreak()
+    while true do
+        if math.random() > 0.5 then
+            break
+        end
+    end
+
+    print("reachable")
+end
+
+local function infiniteRepeat()
+    repeat
+        print("forever")
+    until false
+
+    print("unreachable")
+end
+
+local function breakInDo()
+    for _ in ipairs({}) do
+        do
+            break
+        end
+
+        print("unreachable")
+    end
+
+    print("reachable")
+end
+
+local function returnInLoop()
+    while math.random() > 0.5 do
+        return
+    end
+
+    print("reachable")
+end
+
+local function nestedBreak()
+    while true do
+        for _ = 1, 10 do
+            break
+        end
+    end
+
+    print("unreachable")
+end
+
+return infiniteWhile, whileWithBreak, infiniteRepeat, breakInDo, returnInLoop, nestedBreak
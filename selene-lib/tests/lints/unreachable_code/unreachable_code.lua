@@ -0,0 +1,58 @@
+local function returns()
+    print("reachable")
+    do
+        return
+    end
+    print("unreachable")
+    print("also unreachable")
+end
+
+local function errors(x)
+    if x then
+        error("x")
+        print("unreachable")
+    end
+
+    print("reachable")
+end
+
+local function allBranches(x)
+    if x then
+        return 1
+    elseif x == nil then
+        error("nil")
+    else
+        return 2
+    end
+
+    print("unreachable")
+end
+
+local function exits()
+    os.exit(1)
+    print("unreachable")
+end
+
+local function shadowed()
+    local error = print
+    error("not the real error")
+    print("reachable")
+end
+
+local function nested()
+    error("outer")
+
+    local function inner()
+        return
+    end
+end
+
+local function closures()
+    pcall(function()
+        error("inner")
+    end)
+
+    print("reachable")
+end
+
+return returns, errors, allBranches, exits, shadowed, nested, closures
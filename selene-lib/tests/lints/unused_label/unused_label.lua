@@ -0,0 +1,26 @@
+for i = 1, 10 do
+    if i % 2 == 0 then
+        goto continue
+    end
+
+    print(i)
+
+    ::continue::
+end
+
+::unused::
+
+local function f()
+    ::retry::
+    if math.random() > 0.5 then
+        goto retry
+    end
+
+    ::done::
+end
+
+goto finish
+local function g()
+    ::finish::
+end
+::finish::
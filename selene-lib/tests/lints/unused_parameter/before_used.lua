@@ -0,0 +1,7 @@
+local function trailing(a, b, c)
+    print(b)
+end
+
+local function ignored(_a, b)
+    print(b)
+end
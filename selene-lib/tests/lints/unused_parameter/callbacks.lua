@@ -0,0 +1,15 @@
+signal:Connect(function(player, character)
+    print(player)
+end)
+
+table.sort(list, function(a, b)
+    return true
+end)
+
+local function notCallback(a)
+    print("hi")
+end
+
+callback = function(a)
+    print("hi")
+end
@@ -0,0 +1,38 @@
+local function unused(a, b)
+    print("hi")
+end
+
+local function trailing(a, b, c)
+    print(b)
+end
+
+local function ignored(_a, _b)
+    print("hi")
+end
+
+local function written(a)
+    a = 1
+end
+
+local function fallback(a)
+    a = a or 1
+    print(a)
+end
+
+local function varargs(a, ...)
+    print(a)
+end
+
+local Class = {}
+
+function Class:method(event)
+    print(self)
+end
+
+signal:Connect(function(player, character)
+    print(player)
+end)
+
+return function(value)
+    table.insert(value, 1)
+end
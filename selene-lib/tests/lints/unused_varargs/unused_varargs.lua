@@ -0,0 +1,19 @@
+local function unused(a, ...)
+    print(a)
+end
+
+local function used(...)
+    print(...)
+end
+
+local function selected(...)
+    return select("#", ...)
+end
+
+local function nested(...)
+    return function(...)
+        print(...)
+    end
+end
+
+print(...)
@@ -0,0 +1,52 @@
+local fib = function(n)
+    if n < 2 then
+        return n
+    end
+
+    return fib(n - 1) + fib(n - 2)
+end
+
+local function fine(n)
+    if n > 0 then
+        return fine(n - 1)
+    end
+end
+
+local function a()
+    return b()
+end
+
+local function b()
+    return a()
+end
+
+local function uses_later_local()
+    print(config.value)
+end
+
+local config = {}
+
+-- Globals from the standard library are fine
+local function uses_print()
+    print("hello")
+end
+
+local print = print
+
+local later = 1
+
+local function outer()
+    local function inner()
+        return counter + 1
+    end
+
+    local counter = 0
+    return inner
+end
+
+local already = 1
+local function uses_already()
+    return already
+end
+
+print(fib, fine, a, b, uses_later_local, config, uses_print, later, outer, already, uses_already)
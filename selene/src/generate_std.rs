@@ -0,0 +1,879 @@
+//! Generates a standard library from LuaLS / EmmyLua annotations, such as `---@class` and `---@param`.
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
+};
+
+use full_moon::{
+    ast::{self, Ast},
+    node::Node,
+    tokenizer::{Token, TokenType},
+};
+use selene_lib::standard_library::*;
+
+pub fn generate_std_from_annotations(
+    directory: &Path,
+    patterns: &[String],
+) -> Result<StandardLibrary, Box<dyn std::error::Error>> {
+    let mut generator = AnnotationGenerator::default();
+
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        for entry in glob::glob(&format!("{}/{pattern}", directory.to_string_lossy()))? {
+            paths.push(entry?);
+        }
+    }
+
+    paths.sort();
+    paths.dedup();
+
+    for path in paths {
+        let contents = std::fs::read_to_string(&path)?;
+
+        let ast = full_moon::parse(&contents).map_err(|errors| {
+            format!(
+                "couldn't parse {}: {}",
+                path.display(),
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+
+        generator.add_ast(&ast);
+    }
+
+    Ok(generator.finish())
+}
+
+#[derive(Default)]
+struct AnnotationGenerator {
+    globals: BTreeMap<String, Field>,
+    classes: BTreeMap<String, BTreeMap<String, Field>>,
+    superclasses: Vec<(String, String)>,
+}
+
+#[derive(Default)]
+struct Annotations {
+    /// The last `---@class` in the block, which is what the annotated statement defines
+    class: Option<String>,
+    params: HashMap<String, String>,
+    deprecated: Option<String>,
+    nodiscard: bool,
+    returns: Vec<String>,
+}
+
+impl AnnotationGenerator {
+    fn add_ast(&mut self, ast: &Ast) {
+        // Locals annotated with `---@class`, whose functions belong to that class
+        let mut local_classes = HashMap::new();
+        let mut locals = HashSet::new();
+
+        for stmt in ast.nodes().stmts() {
+            let annotations = self.read_annotations(stmt.surrounding_trivia().0);
+
+            match stmt {
+                ast::Stmt::LocalAssignment(local_assignment) => {
+                    for name in local_assignment.names() {
+                        let name = name.token().to_string();
+
+                        match &annotations.class {
+                            Some(class) => local_classes.insert(name.clone(), class.clone()),
+                            None => local_classes.remove(&name),
+                        };
+
+                        locals.insert(name);
+                    }
+                }
+
+                ast::Stmt::LocalFunction(local_function) => {
+                    let name = local_function.name().token().to_string();
+                    local_classes.remove(&name);
+                    locals.insert(name);
+                }
+
+                ast::Stmt::Assignment(assignment) => {
+                    for (var, expression) in
+                        assignment.variables().iter().zip(assignment.expressions())
+                    {
+                        let ast::Var::Name(name) = var else {
+                            continue;
+                        };
+
+                        let name = name.token().to_string();
+                        if locals.contains(&name) {
+                            continue;
+                        }
+
+                        if let Some(class) = &annotations.class {
+                            self.globals.insert(
+                                name,
+                                Field::from_field_kind(FieldKind::Struct(class.clone())),
+                            );
+                        } else if let ast::Expression::Function(function) = expression {
+                            self.globals
+                                .insert(name, function_field(function.body(), false, &annotations));
+                        }
+                    }
+                }
+
+                ast::Stmt::FunctionDeclaration(function_declaration) => {
+                    let function_name = function_declaration.name();
+                    let names: Vec<String> = function_name
+                        .names()
+                        .iter()
+                        .map(|name| name.token().to_string())
+                        .chain(
+                            function_name
+                                .method_name()
+                                .map(|name| name.token().to_string()),
+                        )
+                        .collect();
+
+                    let field = function_field(
+                        function_declaration.body(),
+                        function_name.method_name().is_some(),
+                        &annotations,
+                    );
+
+                    let class = local_classes.get(&names[0]).cloned().or_else(|| {
+                        match self.globals.get(&names[0]) {
+                            Some(Field {
+                                field_kind: FieldKind::Struct(class),
+                                ..
+                            }) if !locals.contains(&names[0]) => Some(class.clone()),
+                            _ => None,
+                        }
+                    });
+
+                    match class {
+                        Some(class) if names.len() == 2 => {
+                            self.classes
+                                .entry(class)
+                                .or_default()
+                                .insert(names[1].clone(), field);
+                        }
+
+                        Some(_) => {}
+
+                        None if !locals.contains(&names[0]) => {
+                            self.globals.insert(names.join("."), field);
+                        }
+
+                        None => {}
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        // Files that only declare types, such as LuaLS meta files, can have annotations with nothing after them
+        self.read_annotations(ast.eof().leading_trivia().collect());
+    }
+
+    /// Reads the annotations attached to a statement.
+    /// Annotations separated from the statement by a blank line only contribute classes and fields.
+    fn read_annotations(&mut self, trivia: Vec<&Token>) -> Annotations {
+        let mut annotations = Annotations::default();
+
+        for token in trivia {
+            let comment = match token.token_type() {
+                TokenType::SingleLineComment { comment } => comment,
+
+                TokenType::Whitespace { characters } => {
+                    if characters.matches('\n').count() > 1 {
+                        annotations = Annotations::default();
+                    }
+
+                    continue;
+                }
+
+                _ => continue,
+            };
+
+            let Some(annotation) = comment.strip_prefix('-').map(str::trim_start) else {
+                continue;
+            };
+
+            let Some(annotation) = annotation.strip_prefix('@') else {
+                continue;
+            };
+
+            let (tag, rest) = annotation
+                .split_once(char::is_whitespace)
+                .unwrap_or((annotation, ""));
+            let rest = rest.trim();
+
+            match tag {
+                "class" => {
+                    let (name, superclass) = match rest.split_once(':') {
+                        Some((name, superclass)) => (name.trim(), Some(superclass)),
+                        None => (rest, None),
+                    };
+
+                    let Some(name) = name.split_whitespace().next_back() else {
+                        continue;
+                    };
+
+                    self.classes.entry(name.to_owned()).or_default();
+
+                    if let Some(superclass) = superclass {
+                        for superclass in split_top_level(superclass, ',') {
+                            if let Some(superclass) = superclass.split_whitespace().next() {
+                                self.superclasses
+                                    .push((name.to_owned(), superclass.to_owned()));
+                            }
+                        }
+                    }
+
+                    annotations.class = Some(name.to_owned());
+                }
+
+                "field" => {
+                    let Some(class) = &annotations.class else {
+                        continue;
+                    };
+
+                    let rest = rest
+                        .strip_prefix("public ")
+                        .or_else(|| rest.strip_prefix("private "))
+                        .or_else(|| rest.strip_prefix("protected "))
+                        .or_else(|| rest.strip_prefix("package "))
+                        .unwrap_or(rest)
+                        .trim_start();
+
+                    let (name, field_type) = if rest.starts_with('[') {
+                        (
+                            "*",
+                            split_type(rest.split_once(']').map_or("", |(_, rest)| rest)).0,
+                        )
+                    } else {
+                        let (name, rest) =
+                            rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                        (name.trim_end_matches('?'), split_type(rest).0)
+                    };
+
+                    let field = match function_type(field_type) {
+                        Some((function_behavior, _)) => {
+                            Field::from_field_kind(FieldKind::Function(function_behavior))
+                        }
+
+                        None if name == "*" => Field::from_field_kind(FieldKind::Any),
+
+                        None => Field::from_field_kind(FieldKind::Property(
+                            PropertyWritability::OverrideFields,
+                        )),
+                    };
+
+                    self.classes
+                        .entry(class.clone())
+                        .or_default()
+                        .insert(name.to_owned(), field);
+                }
+
+                "param" => {
+                    let (name, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                    let param_type = split_type(rest).0;
+
+                    // `---@param name? type` is the same as `---@param name type?`
+                    let param_type = match name.strip_suffix('?') {
+                        Some(_) => format!("{param_type}?"),
+                        None => param_type.to_owned(),
+                    };
+
+                    annotations
+                        .params
+                        .insert(name.trim_end_matches('?').to_owned(), param_type);
+                }
+
+                "deprecated" => {
+                    annotations.deprecated = Some(rest.to_owned());
+                }
+
+                "nodiscard" => {
+                    annotations.nodiscard = true;
+                }
+
+                // `---@return number count, string name # description`
+                "return" => {
+                    let rest = rest.split_once('#').map_or(rest, |(rest, _)| rest);
+
+                    for return_type in split_top_level(rest, ',') {
+                        annotations
+                            .returns
+                            .push(split_type(return_type).0.to_owned());
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        annotations
+    }
+
+    fn finish(mut self) -> StandardLibrary {
+        // Resolve inheritance repeatedly so that chains of classes get every field
+        for _ in 0..self.superclasses.len() {
+            for (class, superclass) in &self.superclasses {
+                let Some(superclass_fields) = self.classes.get(superclass).cloned() else {
+                    continue;
+                };
+
+                let fields = self.classes.entry(class.clone()).or_default();
+                for (name, field) in superclass_fields {
+                    fields.entry(name).or_insert(field);
+                }
+            }
+        }
+
+        // Returns are read before every class is known, so class names are resolved into structs last
+        let classes = &self.classes;
+        let resolve_returns = |field: &mut Field| {
+            if let FieldKind::Function(function_behavior) = &mut field.field_kind {
+                for return_type in &mut function_behavior.returns {
+                    if let ReturnType::Type(ArgumentType::Display(name)) = return_type {
+                        *return_type = ReturnType::Type(if classes.contains_key(name.as_str()) {
+                            ArgumentType::Struct(name.clone())
+                        } else {
+                            ArgumentType::Any
+                        });
+                    }
+                }
+            }
+        };
+
+        let mut structs = self.classes.clone();
+        self.globals.values_mut().for_each(resolve_returns);
+        structs
+            .values_mut()
+            .flat_map(BTreeMap::values_mut)
+            .for_each(resolve_returns);
+
+        let mut std = StandardLibrary::default();
+        std.globals = self.globals;
+        std.structs = structs;
+        std
+    }
+}
+
+fn function_field(body: &ast::FunctionBody, method: bool, annotations: &Annotations) -> Field {
+    let arguments = body
+        .parameters()
+        .iter()
+        .filter_map(|parameter| match parameter {
+            ast::Parameter::Ellipsis(_) => Some(vararg_argument(
+                annotations.params.get("...").map_or("any", String::as_str),
+            )),
+
+            ast::Parameter::Name(name) => {
+                let name = name.token().to_string();
+
+                // `function Class.method(self)` is called with a colon
+                if name == "self" && !method {
+                    return None;
+                }
+
+                Some(match annotations.params.get(&name) {
+                    Some(param_type) => argument(param_type),
+                    None => Argument {
+                        required: Required::NotRequired,
+                        argument_type: ArgumentType::Any,
+                        observes: Observes::ReadWrite,
+                        deprecated: None,
+                    },
+                })
+            }
+
+            _ => None,
+        })
+        .collect();
+
+    let takes_self = matches!(
+        body.parameters().iter().next(),
+        Some(ast::Parameter::Name(name)) if name.token().to_string() == "self"
+    );
+
+    Field::from_field_kind(FieldKind::Function(FunctionBehavior {
+        arguments,
+        method: method || takes_self,
+        must_use: annotations.nodiscard,
+        never_returns: false,
+        returns: annotations
+            .returns
+            .iter()
+            .map(|return_type| match argument_type(return_type) {
+                (argument_type @ (ArgumentType::Any | ArgumentType::Nil), _)
+                | (argument_type, false) => ReturnType::Type(argument_type),
+                (argument_type, true) => ReturnType::Type(match nested_type(argument_type) {
+                    ArgumentType::Any => ArgumentType::Any,
+                    argument_type => ArgumentType::Nilable(Box::new(argument_type)),
+                }),
+            })
+            .collect(),
+    }))
+    .with_deprecated(annotations.deprecated.as_ref().map(|message| Deprecated {
+        message: if message.is_empty() {
+            "marked as @deprecated in its annotations".to_owned()
+        } else {
+            message.to_owned()
+        },
+        replace: Vec::new(),
+    }))
+}
+
+fn argument(annotation_type: &str) -> Argument {
+    let (argument_type, nilable) = argument_type(annotation_type);
+
+    Argument {
+        required: if nilable {
+            Required::NotRequired
+        } else {
+            Required::Required(None)
+        },
+        argument_type,
+        observes: Observes::ReadWrite,
+        deprecated: None,
+    }
+}
+
+/// Converts an annotation type into an argument type, and whether it can be nil.
+fn argument_type(annotation_type: &str) -> (ArgumentType, bool) {
+    let annotation_type = annotation_type.trim();
+
+    if let Some(inner_type) = annotation_type.strip_suffix('?') {
+        return (argument_type(inner_type).0, true);
+    }
+
+    let union = split_top_level(annotation_type, '|');
+    if union.len() > 1 {
+        let mut nilable = false;
+        let mut constants = Vec::new();
+        let mut types = Vec::new();
+
+        for union_type in union {
+            match argument_type(union_type) {
+                (ArgumentType::Nil, _) => nilable = true,
+                (ArgumentType::Constant(mut values), type_nilable) => {
+                    nilable |= type_nilable;
+                    constants.append(&mut values);
+                }
+                (other, type_nilable) => {
+                    nilable |= type_nilable;
+                    types.push(nested_type(other));
+                }
+            }
+        }
+
+        if !constants.is_empty() {
+            types.push(ArgumentType::Constant(constants));
+        }
+
+        return match types.len() {
+            0 => (ArgumentType::Nil, true),
+            1 => (types.pop().unwrap(), nilable),
+            _ if types.contains(&ArgumentType::Any) => (ArgumentType::Any, true),
+            _ => (ArgumentType::Union(types), nilable),
+        };
+    }
+
+    let base_type = annotation_type
+        .split(['<', '('])
+        .next()
+        .unwrap_or_default()
+        .trim();
+
+    let argument_type = match base_type {
+        "any" | "unknown" => return (ArgumentType::Any, true),
+        "nil" => return (ArgumentType::Nil, true),
+        "boolean" | "true" | "false" => ArgumentType::Bool,
+        "function" => ArgumentType::Function,
+        "fun" => match function_type(annotation_type) {
+            Some((_, parameters)) => ArgumentType::FunctionSignature(parameters),
+            None => ArgumentType::Function,
+        },
+        "integer" | "number" => ArgumentType::Number,
+        "string" => ArgumentType::String,
+        "table" => match annotation_type
+            .strip_prefix("table<")
+            .and_then(|generics| generics.strip_suffix('>'))
+            .map(|generics| split_top_level(generics, ','))
+            .as_deref()
+        {
+            Some([key, value]) => ArgumentType::TableOf(
+                Box::new(nested_type(argument_type(key).0)),
+                Box::new(nested_type(argument_type(value).0)),
+            ),
+            _ => ArgumentType::Table,
+        },
+        _ if annotation_type.ends_with("[]") => ArgumentType::TableOf(
+            Box::new(ArgumentType::Number),
+            Box::new(nested_type(
+                argument_type(&annotation_type[..annotation_type.len() - 2]).0,
+            )),
+        ),
+        _ if annotation_type.starts_with('{') => ArgumentType::Table,
+        _ if is_string_literal(annotation_type) => ArgumentType::Constant(vec![annotation_type
+            [1..annotation_type.len() - 1]
+            .to_owned()]),
+        _ => ArgumentType::Display(annotation_type.to_owned()),
+    };
+
+    (argument_type, false)
+}
+
+/// Class names can't be written inside of other types in standard libraries, so they accept anything.
+fn nested_type(argument_type: ArgumentType) -> ArgumentType {
+    match argument_type {
+        ArgumentType::Display(_) => ArgumentType::Any,
+        other => other,
+    }
+}
+
+/// Converts a `fun(a: number, b?: string): boolean` type into a function, along with its parameter names.
+fn function_type(annotation_type: &str) -> Option<(FunctionBehavior, Vec<String>)> {
+    let parameters = annotation_type.trim().strip_prefix("fun")?.trim_start();
+    let parameters = parameters.strip_prefix('(')?;
+
+    let mut depth = 1;
+    let end = parameters.char_indices().find_map(|(index, character)| {
+        match character {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+
+        (depth == 0).then_some(index)
+    })?;
+
+    let mut method = false;
+    let mut arguments = Vec::new();
+    let mut names = Vec::new();
+
+    for (index, parameter) in split_top_level(&parameters[..end], ',')
+        .into_iter()
+        .enumerate()
+    {
+        if parameter.is_empty() {
+            continue;
+        }
+
+        let (name, parameter_type) = parameter.split_once(':').unwrap_or((parameter, "any"));
+        let name = name.trim();
+        names.push(name.trim_end_matches('?').to_owned());
+
+        if index == 0 && name == "self" {
+            method = true;
+            continue;
+        }
+
+        if name == "..." {
+            arguments.push(vararg_argument(parameter_type));
+        } else if name.ends_with('?') {
+            arguments.push(argument(&format!("{}?", parameter_type.trim())));
+        } else {
+            arguments.push(argument(parameter_type));
+        }
+    }
+
+    Some((
+        FunctionBehavior {
+            arguments,
+            method,
+            must_use: false,
+            never_returns: false,
+            returns: Vec::new(),
+        },
+        names,
+    ))
+}
+
+fn vararg_argument(annotation_type: &str) -> Argument {
+    Argument {
+        required: Required::NotRequired,
+        argument_type: match nested_type(argument_type(annotation_type).0) {
+            ArgumentType::Any => ArgumentType::Vararg,
+            argument_type => ArgumentType::VarargOf(Box::new(argument_type)),
+        },
+        observes: Observes::ReadWrite,
+        deprecated: None,
+    }
+}
+
+fn is_string_literal(text: &str) -> bool {
+    text.len() >= 2
+        && ["\"", "'", "`"]
+            .iter()
+            .any(|quote| text.starts_with(quote) && text.ends_with(quote))
+}
+
+/// Splits text by a separator, ignoring separators inside brackets and strings.
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+
+    for (index, character) in text.char_indices() {
+        match (quote, character) {
+            (Some(open), _) if character == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'' | '`') => quote = Some(character),
+            (None, '(' | '<' | '{' | '[') => depth += 1,
+            (None, ')' | '>' | '}' | ']') => depth -= 1,
+            (None, _) if character == separator && depth == 0 => {
+                parts.push(text[start..index].trim());
+                start = index + character.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(text[start..].trim());
+    parts
+}
+
+/// Splits a type off the start of an annotation, returning it and the description after it.
+/// Types can have spaces in them, such as `string | number` or `fun(a: number)`.
+fn split_type(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut previous = None;
+    for (index, character) in text.char_indices() {
+        match (quote, character) {
+            (Some(open), _) if character == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'' | '`') => quote = Some(character),
+            (None, '(' | '<' | '{' | '[') => depth += 1,
+            (None, ')' | '>' | '}' | ']') => depth -= 1,
+
+            (None, _) if character.is_whitespace() && depth == 0 => {
+                let next = text[index..].trim_start().chars().next();
+
+                let continues = matches!(previous, Some('|' | ',' | ':'))
+                    || matches!(next, Some('|' | '?'))
+                    || (previous == Some(')') && next == Some(':'));
+
+                if !continues {
+                    return (&text[..index], text[index..].trim());
+                }
+
+                continue;
+            }
+
+            _ => {}
+        }
+
+        if !character.is_whitespace() {
+            previous = Some(character);
+        }
+    }
+
+    (text, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate(code: &str) -> StandardLibrary {
+        let mut generator = AnnotationGenerator::default();
+        generator.add_ast(&full_moon::parse(code).unwrap());
+        generator.finish()
+    }
+
+    fn function(std: &StandardLibrary, names: &[&str]) -> FunctionBehavior {
+        match &std.find_global(names).unwrap().field_kind {
+            FieldKind::Function(function_behavior) => function_behavior.clone(),
+            other => panic!("{names:?} is not a function: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_split_type() {
+        assert_eq!(split_type("string the name"), ("string", "the name"));
+        assert_eq!(
+            split_type("string | number the value"),
+            ("string | number", "the value")
+        );
+        assert_eq!(
+            split_type("fun(a: number, b: string): boolean callback"),
+            ("fun(a: number, b: string): boolean", "callback")
+        );
+        assert_eq!(
+            split_type("table<string, number>"),
+            ("table<string, number>", "")
+        );
+    }
+
+    #[test]
+    fn test_argument_types() {
+        assert_eq!(argument_type("integer"), (ArgumentType::Number, false));
+        assert_eq!(argument_type("string?"), (ArgumentType::String, true));
+        assert_eq!(argument_type("number|nil"), (ArgumentType::Number, true));
+        assert_eq!(
+            argument_type("string[]"),
+            (
+                ArgumentType::TableOf(
+                    Box::new(ArgumentType::Number),
+                    Box::new(ArgumentType::String)
+                ),
+                false
+            )
+        );
+        assert_eq!(
+            argument_type("\"left\" | \"right\""),
+            (
+                ArgumentType::Constant(vec!["left".to_owned(), "right".to_owned()]),
+                false
+            )
+        );
+        assert_eq!(
+            argument_type("number | string"),
+            (
+                ArgumentType::Union(vec![ArgumentType::Number, ArgumentType::String]),
+                false
+            )
+        );
+        assert_eq!(
+            argument_type("table<string, integer>"),
+            (
+                ArgumentType::TableOf(
+                    Box::new(ArgumentType::String),
+                    Box::new(ArgumentType::Number)
+                ),
+                false
+            )
+        );
+        assert_eq!(
+            argument_type("fun(key: string, value: any)"),
+            (
+                ArgumentType::FunctionSignature(vec!["key".to_owned(), "value".to_owned()]),
+                false
+            )
+        );
+        assert_eq!(
+            argument_type("Vector"),
+            (ArgumentType::Display("Vector".to_owned()), false)
+        );
+    }
+
+    #[test]
+    fn test_global_functions() {
+        let std = generate(
+            r#"
+            ---@param name string
+            ---@param count? integer
+            ---@return string
+            ---@nodiscard
+            function greet(name, count) end
+
+            ---@deprecated use `greet` instead
+            ---@param ... any
+            function shout(...) end
+
+            ---@param ... number
+            function total(...) end
+
+            local function helper(a) end
+            "#,
+        );
+
+        let greet = function(&std, &["greet"]);
+        assert!(greet.must_use);
+        assert_eq!(greet.arguments.len(), 2);
+        assert_eq!(greet.arguments[0].required, Required::Required(None));
+        assert_eq!(greet.arguments[0].argument_type, ArgumentType::String);
+        assert_eq!(greet.arguments[1].required, Required::NotRequired);
+        assert_eq!(greet.arguments[1].argument_type, ArgumentType::Number);
+
+        assert_eq!(
+            function(&std, &["shout"]).arguments[0].argument_type,
+            ArgumentType::Vararg
+        );
+        assert_eq!(
+            function(&std, &["total"]).arguments[0].argument_type,
+            ArgumentType::VarargOf(Box::new(ArgumentType::Number))
+        );
+        assert_eq!(
+            std.find_global(&["shout"])
+                .unwrap()
+                .deprecated
+                .as_ref()
+                .unwrap()
+                .message,
+            "use `greet` instead"
+        );
+
+        assert!(std.find_global(&["helper"]).is_none());
+    }
+
+    #[test]
+    fn test_classes() {
+        let std = generate(
+            r#"
+            ---@class Shape
+            ---@field name string
+            ---@field area fun(self: Shape): number
+
+            ---@class Circle : Shape
+            ---@field radius number
+            local Circle = {}
+
+            ---@param scale number
+            function Circle:grow(scale) end
+
+            ---@class shapes
+            shapes = {}
+
+            ---@param radius number
+            ---@return Circle
+            function shapes.circle(radius) end
+
+            ---@return Point? point, string # the point, and why it wasn't found
+            function shapes.find() end
+
+            ---@class Point
+            ---@field x number
+            "#,
+        );
+
+        let circle = &std.structs["Circle"];
+        assert!(circle.contains_key("radius"));
+        assert!(circle.contains_key("name"));
+        assert!(circle.contains_key("area"));
+
+        match &circle["grow"].field_kind {
+            FieldKind::Function(function_behavior) => {
+                assert!(function_behavior.method);
+                assert_eq!(function_behavior.arguments.len(), 1);
+            }
+
+            other => panic!("grow is not a function: {other:?}"),
+        }
+
+        assert_eq!(
+            function(&std, &["shapes", "circle"]).arguments[0].argument_type,
+            ArgumentType::Number
+        );
+
+        assert!(std.structs["Point"].contains_key("x"));
+        assert!(std.find_global(&["Circle"]).is_none());
+
+        assert_eq!(
+            function(&std, &["shapes", "circle"]).returns,
+            vec![ReturnType::Type(ArgumentType::Struct("Circle".to_owned()))]
+        );
+        assert_eq!(
+            function(&std, &["shapes", "find"]).returns,
+            vec![
+                ReturnType::Type(ArgumentType::Any),
+                ReturnType::Type(ArgumentType::String),
+            ]
+        );
+    }
+}
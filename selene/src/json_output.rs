@@ -1,4 +1,8 @@
-use std::io::{self, Write};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    time::Duration,
+};
 
 use codespan_reporting::diagnostic::{
     Diagnostic as CodespanDiagnostic, Label as CodespanLabel, LabelStyle, Severity,
@@ -6,13 +10,17 @@ use codespan_reporting::diagnostic::{
 use serde::Serialize;
 use termcolor::StandardStream;
 
+use crate::statistics::{sorted_lint_counts, sorted_timings, LintCount};
+
 #[derive(Serialize)]
 #[serde(tag = "type")]
 pub enum JsonOutput {
     Capabilities(serde_json::Value),
     Diagnostic(JsonDiagnostic),
     InvalidConfig(crate::validate_config::InvalidConfigError),
+    Statistics(JsonStatistics),
     Summary(JsonSummary),
+    Timings(JsonTimings),
 }
 
 #[derive(Serialize)]
@@ -22,6 +30,39 @@ pub struct JsonSummary {
     parse_errors: usize,
 }
 
+#[derive(Serialize)]
+pub struct JsonStatistics {
+    lints: Vec<JsonLintStatistics>,
+}
+
+#[derive(Serialize)]
+struct JsonLintStatistics {
+    name: &'static str,
+    count: usize,
+    severity: &'static str,
+    errors: usize,
+    warnings: usize,
+    files: usize,
+    top_files: Vec<JsonFileCount>,
+}
+
+#[derive(Serialize)]
+struct JsonFileCount {
+    filename: String,
+    count: usize,
+}
+
+#[derive(Serialize)]
+pub struct JsonTimings {
+    lints: Vec<JsonLintTiming>,
+}
+
+#[derive(Serialize)]
+struct JsonLintTiming {
+    name: &'static str,
+    milliseconds: f64,
+}
+
 #[derive(Serialize)]
 pub struct JsonDiagnostic {
     severity: Severity,
@@ -115,6 +156,60 @@ pub fn log_total_json(
     Ok(())
 }
 
+pub fn log_statistics_json(
+    mut stdout: StandardStream,
+    lint_counts: &HashMap<&'static str, LintCount>,
+) -> io::Result<()> {
+    let lints = sorted_lint_counts(lint_counts)
+        .into_iter()
+        .map(|(name, count)| JsonLintStatistics {
+            name,
+            count: count.total(),
+            severity: count.severity(),
+            errors: count.errors,
+            warnings: count.warnings,
+            files: count.files.len(),
+            top_files: count
+                .top_files()
+                .into_iter()
+                .map(|(filename, count)| JsonFileCount {
+                    filename: filename.to_owned(),
+                    count,
+                })
+                .collect(),
+        })
+        .collect();
+
+    writeln!(
+        stdout,
+        "{}",
+        serde_json::to_string(&JsonOutput::Statistics(JsonStatistics { lints }))?
+    )?;
+
+    Ok(())
+}
+
+pub fn log_timings_json(
+    mut stdout: StandardStream,
+    timings: &HashMap<&'static str, Duration>,
+) -> io::Result<()> {
+    let lints = sorted_timings(timings)
+        .into_iter()
+        .map(|(name, duration)| JsonLintTiming {
+            name,
+            milliseconds: duration.as_secs_f64() * 1000.0,
+        })
+        .collect();
+
+    writeln!(
+        stdout,
+        "{}",
+        serde_json::to_string(&JsonOutput::Timings(JsonTimings { lints }))?
+    )?;
+
+    Ok(())
+}
+
 pub fn print_json(output: JsonOutput) {
     println!(
         "{}",
@@ -1,22 +1,26 @@
 use std::{
+    collections::HashMap,
     ffi::OsString,
     fmt, fs,
     io::{self, Read, Write},
     path::{Path, PathBuf},
     sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc, RwLock,
+        Arc, Mutex, RwLock,
     },
+    time::Duration,
 };
 
 use codespan_reporting::{
-    diagnostic::{
-        Diagnostic as CodespanDiagnostic, Label as CodespanLabel, Severity as CodespanSeverity,
-    },
+    diagnostic::{Diagnostic as CodespanDiagnostic, Severity as CodespanSeverity},
     term::DisplayStyle as CodespanDisplayStyle,
 };
 use full_moon::LuaVersion;
-use selene_lib::{lints::Severity, standard_library::LuaVersionError, *};
+use selene_lib::{
+    lints::{Diagnostic, Label, Severity},
+    standard_library::LuaVersionError,
+    *,
+};
 use structopt::{clap, StructOpt};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 use threadpool::ThreadPool;
@@ -25,14 +29,20 @@ use upgrade_std::upgrade_std;
 #[cfg(feature = "roblox")]
 use selene_lib::standard_library::StandardLibrary;
 
-use crate::{json_output::log_total_json, opts::DisplayStyle};
+use crate::{
+    json_output::{log_statistics_json, log_timings_json, log_total_json},
+    opts::DisplayStyle,
+    statistics::LintCount,
+};
 
 mod capabilities;
+mod generate_std;
 mod json_output;
 mod opts;
 #[cfg(feature = "roblox")]
 mod roblox;
 mod standard_library;
+mod statistics;
 mod upgrade_std;
 mod validate_config;
 
@@ -48,6 +58,8 @@ macro_rules! error {
 
 lazy_static::lazy_static! {
     static ref OPTIONS: RwLock<Option<opts::Options>> = RwLock::new(None);
+    static ref LINT_COUNTS: Mutex<HashMap<&'static str, LintCount>> = Mutex::new(HashMap::new());
+    static ref LINT_TIMINGS: Mutex<HashMap<&'static str, Duration>> = Mutex::new(HashMap::new());
 }
 
 static LINT_ERRORS: AtomicUsize = AtomicUsize::new(0);
@@ -124,6 +136,32 @@ fn log_total_text(
     Ok(())
 }
 
+fn log_statistics(lint_counts: &HashMap<&'static str, LintCount>) -> io::Result<()> {
+    let lock = OPTIONS.read().unwrap();
+    let opts = lock.as_ref().unwrap();
+
+    let mut stdout = StandardStream::stdout(get_color());
+    stdout.reset()?;
+
+    match opts.display_style {
+        Some(DisplayStyle::Json2) => log_statistics_json(stdout, lint_counts),
+        _ => statistics::log_statistics_text(stdout, lint_counts),
+    }
+}
+
+fn log_timings(timings: &HashMap<&'static str, Duration>) -> io::Result<()> {
+    let lock = OPTIONS.read().unwrap();
+    let opts = lock.as_ref().unwrap();
+
+    let mut stdout = StandardStream::stdout(get_color());
+    stdout.reset()?;
+
+    match opts.display_style {
+        Some(DisplayStyle::Json2) => log_timings_json(stdout, timings),
+        _ => statistics::log_timings_text(stdout, timings),
+    }
+}
+
 fn emit_codespan(
     writer: &mut impl termcolor::WriteColor,
     files: &codespan::Files<&str>,
@@ -180,12 +218,67 @@ fn emit_codespan_locked(
     emit_codespan(&mut stdout, files, diagnostic);
 }
 
-fn read<R: Read>(
-    checker: &Checker<toml::value::Value>,
-    filename: &Path,
+/// A standard library every file is checked against, from `std`.
+struct Target {
+    name: String,
+    checker: Checker<toml::value::Value>,
     lua_version: LuaVersion,
-    mut reader: R,
-) {
+}
+
+fn parse_error_diagnostics(errors: Vec<full_moon::Error>) -> Vec<CheckerDiagnostic> {
+    errors
+        .into_iter()
+        .map(|error| {
+            let diagnostic = match error {
+                full_moon::Error::AstError(ast_error) => {
+                    let token = ast_error.token();
+
+                    Diagnostic::new(
+                        "parse_error",
+                        format!("unexpected token `{token}`"),
+                        Label::new_with_message(
+                            (token.start_position().bytes(), token.end_position().bytes()),
+                            ast_error.error_message().to_string(),
+                        ),
+                    )
+                }
+
+                full_moon::Error::TokenizerError(error) => Diagnostic::new(
+                    "parse_error",
+                    match error.error() {
+                        full_moon::tokenizer::TokenizerErrorType::UnclosedComment => {
+                            "unclosed comment".to_string()
+                        }
+
+                        full_moon::tokenizer::TokenizerErrorType::UnclosedString => {
+                            "unclosed string".to_string()
+                        }
+
+                        full_moon::tokenizer::TokenizerErrorType::UnexpectedToken(character) => {
+                            format!("unexpected character {character}")
+                        }
+
+                        full_moon::tokenizer::TokenizerErrorType::InvalidNumber => {
+                            "invalid number".to_string()
+                        }
+
+                        full_moon::tokenizer::TokenizerErrorType::InvalidSymbol(symbol) => {
+                            format!("invalid symbol {symbol}")
+                        }
+                    },
+                    Label::new((error.position().bytes(), error.position().bytes())),
+                ),
+            };
+
+            CheckerDiagnostic {
+                diagnostic,
+                severity: Severity::Error,
+            }
+        })
+        .collect()
+}
+
+fn read<R: Read>(targets: &[Target], filename: &Path, mut reader: R) {
     let mut buffer = Vec::new();
     if let Err(error) = reader.read_to_end(&mut buffer) {
         error!(
@@ -206,95 +299,76 @@ fn read<R: Read>(
     let mut files = codespan::Files::new();
     let source_id = files.add(filename.as_os_str(), &*contents);
 
-    let ast = {
-        profiling::scope!("full_moon::parse");
-
-        match full_moon::parse_fallible(&contents, lua_version).into_result() {
-            Ok(ast) => ast,
-            Err(errors) => {
-                for error in errors {
-                    PARSE_ERRORS.fetch_add(1, Ordering::SeqCst);
-                    match error {
-                        full_moon::Error::AstError(ast_error) => {
-                            let token = ast_error.token();
-
-                            emit_codespan_locked(
-                                &files,
-                                &CodespanDiagnostic {
-                                    severity: CodespanSeverity::Error,
-                                    code: Some("parse_error".to_owned()),
-                                    message: format!("unexpected token `{token}`"),
-                                    labels: vec![CodespanLabel::primary(
-                                        source_id,
-                                        codespan::Span::new(
-                                            token.start_position().bytes() as u32,
-                                            token.end_position().bytes() as u32,
-                                        ),
-                                    )
-                                    .with_message(ast_error.error_message())],
-                                    notes: Vec::new(),
-                                },
-                            )
-                        }
+    // Each target can support different syntax, so the file is parsed once per target
+    let mut target_parse_errors = Vec::with_capacity(targets.len());
+    let mut target_diagnostics = Vec::with_capacity(targets.len());
 
-                        full_moon::Error::TokenizerError(error) => emit_codespan_locked(
-                            &files,
-                            &CodespanDiagnostic {
-                                severity: CodespanSeverity::Error,
-                                code: Some("parse_error".to_owned()),
-                                message: match error.error() {
-                                    full_moon::tokenizer::TokenizerErrorType::UnclosedComment => {
-                                        "unclosed comment".to_string()
-                                    }
+    for target in targets {
+        let ast = {
+            profiling::scope!("full_moon::parse");
 
-                                    full_moon::tokenizer::TokenizerErrorType::UnclosedString => {
-                                        "unclosed string".to_string()
-                                    }
+            match full_moon::parse_fallible(&contents, target.lua_version).into_result() {
+                Ok(ast) => ast,
+                Err(errors) => {
+                    target_parse_errors
+                        .push((target.name.as_str(), parse_error_diagnostics(errors)));
+                    continue;
+                }
+            }
+        };
 
-                                    full_moon::tokenizer::TokenizerErrorType::UnexpectedToken(
-                                        character,
-                                    ) => {
-                                        format!("unexpected character {character}")
-                                    }
+        target_parse_errors.push((target.name.as_str(), Vec::new()));
 
-                                    full_moon::tokenizer::TokenizerErrorType::InvalidNumber => {
-                                        "invalid number".to_string()
-                                    }
+        let (diagnostics, timings) = target.checker.test_on_with_timings(&ast);
+        target_diagnostics.push((target.name.as_str(), diagnostics));
 
-                                    full_moon::tokenizer::TokenizerErrorType::InvalidSymbol(
-                                        symbol,
-                                    ) => {
-                                        format!("invalid symbol {symbol}")
-                                    }
-                                },
-                                labels: vec![CodespanLabel::primary(
-                                    source_id,
-                                    codespan::Span::new(
-                                        error.position().bytes() as u32,
-                                        error.position().bytes() as u32,
-                                    ),
-                                )],
-                                notes: Vec::new(),
-                            },
-                        ),
-                    }
-                }
+        if opts.timings {
+            let mut lint_timings = LINT_TIMINGS.lock().unwrap();
 
-                return;
+            for (lint_name, duration) in timings {
+                *lint_timings.entry(lint_name).or_default() += duration;
             }
         }
-    };
+    }
+
+    for parse_error in targets::merge_target_diagnostics(target_parse_errors, targets.len()) {
+        PARSE_ERRORS.fetch_add(1, Ordering::SeqCst);
+
+        emit_codespan_locked(
+            &files,
+            &parse_error
+                .diagnostic
+                .into_codespan_diagnostic(source_id, CodespanSeverity::Error),
+        );
+    }
 
-    let mut diagnostics = checker.test_on(&ast);
+    if target_diagnostics.is_empty() {
+        return;
+    }
+
+    let mut diagnostics = targets::merge_target_diagnostics(target_diagnostics, targets.len());
     diagnostics.sort_by_key(|diagnostic| diagnostic.diagnostic.start_position());
 
     let (mut errors, mut warnings) = (0, 0);
-    for diagnostic in &diagnostics {
-        match diagnostic.severity {
-            Severity::Allow => {}
-            Severity::Error => errors += 1,
-            Severity::Warning => warnings += 1,
-        };
+    {
+        let mut lint_counts = LINT_COUNTS.lock().unwrap();
+        let filename = filename.display().to_string();
+
+        for diagnostic in &diagnostics {
+            let (diagnostic_errors, diagnostic_warnings) = match diagnostic.severity {
+                Severity::Allow => continue,
+                Severity::Error => (1, 0),
+                Severity::Warning => (0, 1),
+            };
+
+            errors += diagnostic_errors;
+            warnings += diagnostic_warnings;
+
+            let lint_count = lint_counts.entry(diagnostic.diagnostic.code).or_default();
+            lint_count.errors += diagnostic_errors;
+            lint_count.warnings += diagnostic_warnings;
+            *lint_count.files.entry(filename.clone()).or_default() += 1;
+        }
     }
 
     LINT_ERRORS.fetch_add(errors, Ordering::SeqCst);
@@ -391,11 +465,10 @@ fn read<R: Read>(
     }
 }
 
-fn read_file(checker: &Checker<toml::value::Value>, lua_version: LuaVersion, filename: &Path) {
+fn read_file(targets: &[Target], filename: &Path) {
     read(
-        checker,
+        targets,
         filename,
-        lua_version,
         match fs::File::open(filename) {
             Ok(file) => file,
             Err(error) => {
@@ -491,6 +564,19 @@ fn start(mut options: opts::Options) {
             return;
         }
 
+        Some(opts::Command::GenerateStd {
+            from_annotations,
+            output,
+        }) => {
+            if let Err(error) = generate_std(from_annotations, output.as_deref(), &options.pattern)
+            {
+                error!("Couldn't generate standard library: {error}");
+                std::process::exit(1);
+            }
+
+            return;
+        }
+
         Some(opts::Command::UpgradeStd { filename }) => {
             if let Err(error) = upgrade_std(filename) {
                 error!("Couldn't upgrade standard library: {error}");
@@ -547,50 +633,6 @@ fn start(mut options: opts::Options) {
 
     let current_dir = std::env::current_dir().unwrap();
 
-    let standard_library = match standard_library::collect_standard_library(
-        &config,
-        config.std(),
-        &current_dir,
-        &config_directory,
-    ) {
-        Ok(Some(library)) => library,
-
-        Ok(None) => {
-            error!("Standard library was empty.");
-            std::process::exit(1);
-        }
-
-        Err(error) => {
-            let missing_files: Vec<_> = config
-                .std()
-                .split('+')
-                .filter(|name| {
-                    !PathBuf::from(format!("{name}.yml")).exists()
-                        && !PathBuf::from(format!("{name}.yaml")).exists()
-                        && !PathBuf::from(format!("{name}.toml")).exists()
-                })
-                .filter(|name| !cfg!(feature = "roblox") || *name != "roblox")
-                .collect();
-
-            if !missing_files.is_empty() {
-                eprintln!(
-                    "`std = \"{}\"`, but some libraries could not be found:",
-                    config.std()
-                );
-
-                for library_name in missing_files {
-                    eprintln!("  `{library_name}`");
-                }
-
-                error!("Could not find all standard library files");
-                std::process::exit(1);
-            }
-
-            error!("Could not collect standard library: {error}");
-            std::process::exit(1);
-        }
-    };
-
     let mut builder = globset::GlobSetBuilder::new();
     for pattern in &config.exclude {
         builder.add(match globset::Glob::new(pattern) {
@@ -610,49 +652,111 @@ fn start(mut options: opts::Options) {
         }
     };
 
-    let (lua_version, problems) = standard_library.lua_version();
-    if !problems.is_empty() {
-        for problem in problems {
-            match problem {
-                LuaVersionError::FeatureNotEnabled(feature) => {
-                    error!("lua version {feature} in standard library, but feature for it is not enabled");
+    let budgets = config.budgets.clone();
+
+    let mut targets = Vec::new();
+
+    for target in config.std_targets() {
+        let standard_library = match standard_library::collect_standard_library(
+            &config,
+            target,
+            &current_dir,
+            &config_directory,
+        ) {
+            Ok(Some(library)) => library,
+
+            Ok(None) => {
+                error!("Standard library was empty.");
+                std::process::exit(1);
+            }
+
+            // Only worth looking for missing files when that's what went wrong,
+            // otherwise errors like a malformed definitions file would be hidden
+            Err(error @ standard_library::StandardLibraryError::NotFound { .. }) => {
+                let missing_files: Vec<_> = target
+                    .split('+')
+                    .filter(|name| {
+                        !PathBuf::from(format!("{name}.yml")).exists()
+                            && !PathBuf::from(format!("{name}.yaml")).exists()
+                            && !PathBuf::from(format!("{name}.toml")).exists()
+                            && !PathBuf::from(name).exists()
+                    })
+                    .filter(|name| !cfg!(feature = "roblox") || *name != "roblox")
+                    .collect();
+
+                if !missing_files.is_empty() {
+                    eprintln!("`std = \"{target}\"`, but some libraries could not be found:");
+
+                    for library_name in missing_files {
+                        eprintln!("  `{library_name}`");
+                    }
+
+                    error!("Could not find all standard library files");
+                    std::process::exit(1);
                 }
 
-                LuaVersionError::Unknown(version) => {
-                    error!("unknown lua version {version} in standard library");
+                error!("Could not collect standard library: {error}");
+                std::process::exit(1);
+            }
+
+            Err(error) => {
+                error!("Could not collect standard library: {error}");
+                std::process::exit(1);
+            }
+        };
+
+        let (lua_version, problems) = standard_library.lua_version();
+        if !problems.is_empty() {
+            for problem in problems {
+                match problem {
+                    LuaVersionError::FeatureNotEnabled(feature) => {
+                        error!("lua version {feature} in standard library, but feature for it is not enabled");
+                    }
+
+                    LuaVersionError::Unknown(version) => {
+                        error!("unknown lua version {version} in standard library");
+                    }
                 }
             }
         }
+
+        let checker = match Checker::new(config.for_target(target), standard_library) {
+            Ok(checker) => checker,
+            Err(error) => {
+                error!("{error}");
+                std::process::exit(1);
+            }
+        };
+
+        targets.push(Target {
+            name: target.to_owned(),
+            checker,
+            lua_version,
+        });
     }
 
-    let checker = Arc::new(match Checker::new(config, standard_library) {
-        Ok(checker) => checker,
-        Err(error) => {
-            error!("{error}");
-            std::process::exit(1);
-        }
-    });
+    let targets = Arc::new(targets);
 
     let pool = ThreadPool::new(options.num_threads);
 
     for filename in &options.files {
         if filename == "-" {
-            let checker = Arc::clone(&checker);
-            pool.execute(move || read(&checker, Path::new("-"), lua_version, io::stdin().lock()));
+            let targets = Arc::clone(&targets);
+            pool.execute(move || read(&targets, Path::new("-"), io::stdin().lock()));
             continue;
         }
 
         match fs::metadata(filename) {
             Ok(metadata) => {
                 if metadata.is_file() {
-                    let checker = Arc::clone(&checker);
+                    let targets = Arc::clone(&targets);
                     let filename = filename.to_owned();
 
                     if !options.no_exclude && exclude_set.is_match(&filename) {
                         continue;
                     }
 
-                    pool.execute(move || read_file(&checker, lua_version, Path::new(&filename)));
+                    pool.execute(move || read_file(&targets, Path::new(&filename)));
                 } else if metadata.is_dir() {
                     for pattern in &options.pattern {
                         let glob = match glob::glob(&format!(
@@ -674,9 +778,9 @@ fn start(mut options: opts::Options) {
                                         continue;
                                     }
 
-                                    let checker = Arc::clone(&checker);
+                                    let targets = Arc::clone(&targets);
 
-                                    pool.execute(move || read_file(&checker, lua_version, &path));
+                                    pool.execute(move || read_file(&targets, &path));
                                 }
 
                                 Err(error) => {
@@ -719,16 +823,77 @@ fn start(mut options: opts::Options) {
         log_total(parse_errors, lint_errors, lint_warnings).ok();
     }
 
-    let error_count =
-        parse_errors + lint_errors + lint_warnings + standard_library_errors + pool.panic_count();
-    if error_count > 0 {
-        let lock = OPTIONS.read().unwrap();
-        let opts = lock.as_ref().unwrap();
+    let lint_counts = LINT_COUNTS.lock().unwrap();
+
+    if !options.luacheck && options.statistics {
+        log_statistics(&lint_counts).ok();
+    }
+
+    if !options.luacheck && options.timings {
+        log_timings(&LINT_TIMINGS.lock().unwrap()).ok();
+    }
+
+    // Lints with a budget are only checked against that budget, not the usual error/warning rules.
+    let (mut budgeted_errors, mut budgeted_warnings) = (0, 0);
+    for lint_name in budgets.keys() {
+        if let Some(count) = lint_counts.get(lint_name.as_str()) {
+            budgeted_errors += count.errors;
+            budgeted_warnings += count.warnings;
+        }
+    }
+
+    let exceeded_budgets = exceeded_budgets(&budgets, &lint_counts);
+    for (lint_name, count, budget) in &exceeded_budgets {
+        error!("`{lint_name}` exceeded its budget ({count} > {budget})");
+    }
+
+    let lint_warnings = lint_warnings - budgeted_warnings;
+
+    let error_count = parse_errors
+        + (lint_errors - budgeted_errors)
+        + lint_warnings
+        + standard_library_errors
+        + pool.panic_count();
+
+    let lock = OPTIONS.read().unwrap();
+    let opts = lock.as_ref().unwrap();
 
-        if error_count != lint_warnings || !opts.allow_warnings {
+    if let Some(max_warnings) = opts.max_warnings {
+        if lint_warnings > max_warnings {
+            error!("too many warnings ({lint_warnings} > {max_warnings})");
             std::process::exit(1);
         }
     }
+
+    if !exceeded_budgets.is_empty() {
+        std::process::exit(1);
+    }
+
+    if error_count > 0
+        && (error_count != lint_warnings || !(opts.allow_warnings || opts.max_warnings.is_some()))
+    {
+        std::process::exit(1);
+    }
+}
+
+/// Returns every lint that has more diagnostics than its budget allows, sorted by name.
+fn exceeded_budgets(
+    budgets: &HashMap<String, usize>,
+    lint_counts: &HashMap<&'static str, LintCount>,
+) -> Vec<(String, usize, usize)> {
+    let mut exceeded: Vec<_> = budgets
+        .iter()
+        .filter_map(|(lint_name, &budget)| {
+            let count = lint_counts
+                .get(lint_name.as_str())
+                .map_or(0, LintCount::total);
+
+            (count > budget).then(|| (lint_name.to_owned(), count, budget))
+        })
+        .collect();
+
+    exceeded.sort();
+    exceeded
 }
 
 fn main() -> color_eyre::Result<()> {
@@ -812,6 +977,26 @@ fn generate_roblox_std() -> color_eyre::Result<StandardLibrary> {
     Ok(std)
 }
 
+fn generate_std(
+    directory: &Path,
+    output: Option<&Path>,
+    patterns: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let std = generate_std::generate_std_from_annotations(directory, patterns)?;
+
+    let contents = format!(
+        "# This file was @generated by generate-std --from-annotations\n{}",
+        serde_yaml::to_string(&std)?
+    );
+
+    match output {
+        Some(output) => fs::write(output, contents)?,
+        None => print!("{contents}"),
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -841,4 +1026,35 @@ mod tests {
 
         assert!(get_opts_safe(args(vec!["--fail", "files"]), true).is_ok());
     }
+
+    #[test]
+    fn test_exceeded_budgets() {
+        let mut budgets = HashMap::new();
+        budgets.insert("shadowing".to_owned(), 2);
+        budgets.insert("unused_variable".to_owned(), 5);
+        budgets.insert("empty_if".to_owned(), 0);
+
+        let mut lint_counts = HashMap::new();
+        lint_counts.insert(
+            "shadowing",
+            LintCount {
+                errors: 1,
+                warnings: 2,
+                ..LintCount::default()
+            },
+        );
+        lint_counts.insert(
+            "unused_variable",
+            LintCount {
+                errors: 0,
+                warnings: 5,
+                ..LintCount::default()
+            },
+        );
+
+        assert_eq!(
+            exceeded_budgets(&budgets, &lint_counts),
+            vec![("shadowing".to_owned(), 3, 2)]
+        );
+    }
 }
@@ -49,10 +49,22 @@ pub struct Options {
     #[structopt(long, short)]
     pub no_summary: bool,
 
+    /// Print how many diagnostics each lint reported, and in which files
+    #[structopt(long)]
+    pub statistics: bool,
+
+    /// Print how long each lint took to run across all files
+    #[structopt(long)]
+    pub timings: bool,
+
     /// Pass when only warnings occur
     #[structopt(long)]
     pub allow_warnings: bool,
 
+    /// Pass when the number of warnings does not exceed this amount
+    #[structopt(long)]
+    pub max_warnings: Option<usize>,
+
     /// Whether to pretend to be luacheck for existing consumers
     #[structopt(long, hidden(true))]
     pub luacheck: bool,
@@ -103,6 +115,17 @@ pub enum Command {
     #[cfg(feature = "roblox")]
     UpdateRobloxStd,
 
+    /// Generates a standard library from LuaLS / EmmyLua annotations
+    GenerateStd {
+        /// The directory of annotated Lua files to read
+        #[structopt(long, parse(from_os_str))]
+        from_annotations: PathBuf,
+
+        /// The file to write the standard library to, printing it if not given
+        #[structopt(long, parse(from_os_str))]
+        output: Option<PathBuf>,
+    },
+
     UpgradeStd {
         #[structopt(parse(from_os_str))]
         filename: PathBuf,
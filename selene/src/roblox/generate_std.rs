@@ -8,6 +8,19 @@ use selene_lib::standard_library::*;
 const API_DUMP: &str =
     "https://raw.githubusercontent.com/CloneTrooper1019/Roblox-Client-Tracker/roblox/API-Dump.json";
 
+// The API dump doesn't say which methods can return nil, so these are the common ones.
+// They return `any?` rather than an `Instance?`, as struct types can't be written inside other types.
+const NILABLE_METHODS: &[&str] = &[
+    "FindFirstAncestor",
+    "FindFirstAncestorOfClass",
+    "FindFirstAncestorWhichIsA",
+    "FindFirstChild",
+    "FindFirstChildOfClass",
+    "FindFirstChildWhichIsA",
+    "FindFirstDescendant",
+    "GetPlayerFromCharacter",
+];
+
 pub struct RobloxGenerator {
     pub std: StandardLibrary,
 }
@@ -130,6 +143,14 @@ impl RobloxGenerator {
                                 .collect(),
                             method: true,
                             must_use: false,
+                            never_returns: false,
+                            returns: if NILABLE_METHODS.contains(&name.as_str()) {
+                                vec![ReturnType::Type(ArgumentType::Nilable(Box::new(
+                                    ArgumentType::Any,
+                                )))]
+                            } else {
+                                Vec::new()
+                            },
                         },
                     ))),
                 ),
@@ -219,6 +240,8 @@ impl RobloxGenerator {
                     arguments: vec![],
                     method: true,
                     must_use: true,
+                    never_returns: false,
+                    returns: Vec::new(),
                 })),
             );
 
@@ -246,7 +269,7 @@ impl RobloxGenerator {
     }
 
     fn write_instance_new(&mut self, api: &ApiDump) {
-        let instance_names = api
+        let instance_names: Vec<String> = api
             .classes
             .iter()
             .filter_map(|class| {
@@ -258,6 +281,10 @@ impl RobloxGenerator {
             })
             .collect();
 
+        for instance_name in &instance_names {
+            self.write_class_struct(api, instance_name);
+        }
+
         self.std.globals.insert(
             "Instance.new".to_owned(),
             Field::from_field_kind(FieldKind::Function(FunctionBehavior {
@@ -282,12 +309,15 @@ impl RobloxGenerator {
 
                 // Only true because we don't allow the second parameter
                 must_use: true,
+                never_returns: false,
+
+                returns: vec![ReturnType::StructFromArgument { argument: 1 }],
             })),
         );
     }
 
     fn write_get_service(&mut self, api: &ApiDump) {
-        let service_names = api
+        let service_names: Vec<String> = api
             .classes
             .iter()
             .filter_map(|class| {
@@ -299,6 +329,10 @@ impl RobloxGenerator {
             })
             .collect();
 
+        for service_name in &service_names {
+            self.write_class_struct(api, service_name);
+        }
+
         let data_model = self.std.structs.get_mut("DataModel").unwrap();
 
         *data_model.get_mut("GetService").unwrap() =
@@ -311,6 +345,8 @@ impl RobloxGenerator {
                 }],
                 method: true,
                 must_use: true,
+                never_returns: false,
+                returns: vec![ReturnType::StructFromArgument { argument: 1 }],
             }));
     }
 
@@ -338,3 +374,50 @@ impl RobloxGenerator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(name: &str, superclass: &str, tags: &[&str]) -> serde_json::Value {
+        serde_json::json!({
+            "Name": name,
+            "Superclass": superclass,
+            "Members": [],
+            "Tags": tags,
+        })
+    }
+
+    #[test]
+    fn test_instance_new_and_get_service_structs() {
+        let mut data_model = class("DataModel", "Instance", &["NotCreatable"]);
+        data_model["Members"] = serde_json::json!([{
+            "MemberType": "Function",
+            "Name": "GetService",
+            "Parameters": [{}],
+        }]);
+
+        let api: ApiDump = serde_json::from_value(serde_json::json!({
+            "Classes": [
+                class("Instance", "<<<ROOT>>>", &["NotCreatable"]),
+                data_model,
+                class("Part", "Instance", &[]),
+                class("Players", "Instance", &["NotCreatable", "Service"]),
+            ],
+            "Enums": [],
+        }))
+        .unwrap();
+
+        let mut generator = RobloxGenerator {
+            std: StandardLibrary::roblox_base(),
+        };
+
+        generator.write_class(&api, "game", "DataModel");
+        generator.write_instance_new(&api);
+        generator.write_get_service(&api);
+
+        // `Instance.new` and `GetService` return the struct named by their argument
+        assert!(generator.std.structs.contains_key("Part"));
+        assert!(generator.std.structs.contains_key("Players"));
+    }
+}
@@ -20,6 +20,17 @@ pub enum StandardLibraryError {
         path: PathBuf,
     },
 
+    #[cfg(feature = "roblox")]
+    LuauDefinitions {
+        source: selene_lib::standard_library::LuauDefinitionsError,
+        path: PathBuf,
+    },
+
+    #[cfg(not(feature = "roblox"))]
+    LuauDefinitionsUnsupported {
+        path: PathBuf,
+    },
+
     NotFound {
         name: String,
     },
@@ -55,6 +66,24 @@ impl Display for StandardLibraryError {
                 )
             }
 
+            #[cfg(feature = "roblox")]
+            StandardLibraryError::LuauDefinitions { source, path } => {
+                write!(
+                    formatter,
+                    "failed to parse luau definitions file `{}`: {source}",
+                    path.display(),
+                )
+            }
+
+            #[cfg(not(feature = "roblox"))]
+            StandardLibraryError::LuauDefinitionsUnsupported { path } => {
+                write!(
+                    formatter,
+                    "failed to load luau definitions file `{}`: selene was built without the roblox feature, which is required for luau definitions files",
+                    path.display(),
+                )
+            }
+
             StandardLibraryError::NotFound { name } => {
                 write!(formatter, "failed to find standard library: {name}")
             }
@@ -139,6 +168,34 @@ fn collect_roblox_standard_library<V>(
     unreachable!()
 }
 
+fn is_luau_definitions_file(standard_library_name: &str) -> bool {
+    standard_library_name.ends_with(".d.luau") || standard_library_name.ends_with(".d.lua")
+}
+
+#[cfg(feature = "roblox")]
+fn from_luau_definitions(definitions_file: &Path) -> Result<StandardLibrary, StandardLibraryError> {
+    let content =
+        fs::read_to_string(definitions_file).map_err(|error| StandardLibraryError::Io {
+            source: error,
+            path: definitions_file.to_path_buf(),
+        })?;
+
+    StandardLibrary::from_luau_definitions(&content).map_err(|error| {
+        StandardLibraryError::LuauDefinitions {
+            source: error,
+            path: definitions_file.to_path_buf(),
+        }
+    })
+}
+
+// The definitions are tokenized as Luau, which full_moon only supports with the roblox feature
+#[cfg(not(feature = "roblox"))]
+fn from_luau_definitions(definitions_file: &Path) -> Result<StandardLibrary, StandardLibraryError> {
+    Err(StandardLibraryError::LuauDefinitionsUnsupported {
+        path: definitions_file.to_path_buf(),
+    })
+}
+
 fn from_name<V>(
     config: &CheckerConfig<V>,
     standard_library_name: &str,
@@ -153,6 +210,16 @@ fn from_name<V>(
     };
 
     for directory in directories {
+        if is_luau_definitions_file(standard_library_name) {
+            let definitions_file = directory.join(standard_library_name);
+            if !definitions_file.exists() {
+                continue;
+            }
+
+            library = Some(from_luau_definitions(&definitions_file)?);
+            break;
+        }
+
         let toml_file = directory.join(format!("{standard_library_name}.toml"));
         if toml_file.exists() {
             let content =
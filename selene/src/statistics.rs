@@ -0,0 +1,173 @@
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    time::Duration,
+};
+
+use termcolor::{ColorSpec, StandardStream, WriteColor};
+
+/// How many of the files with the most diagnostics to show for each lint.
+const TOP_FILES_COUNT: usize = 3;
+
+#[derive(Clone, Debug, Default)]
+pub struct LintCount {
+    pub errors: usize,
+    pub warnings: usize,
+    pub files: HashMap<String, usize>,
+}
+
+impl LintCount {
+    pub fn total(&self) -> usize {
+        self.errors + self.warnings
+    }
+
+    pub fn severity(&self) -> &'static str {
+        match (self.errors > 0, self.warnings > 0) {
+            (true, true) => "mixed",
+            (true, false) => "error",
+            (false, _) => "warning",
+        }
+    }
+
+    pub fn top_files(&self) -> Vec<(&str, usize)> {
+        let mut files: Vec<_> = self
+            .files
+            .iter()
+            .map(|(filename, count)| (filename.as_str(), *count))
+            .collect();
+
+        files.sort_by(|(a_name, a_count), (b_name, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+        });
+        files.truncate(TOP_FILES_COUNT);
+        files
+    }
+}
+
+/// Lints sorted from most to least diagnostics.
+pub fn sorted_lint_counts<'a>(
+    lint_counts: &'a HashMap<&'static str, LintCount>,
+) -> Vec<(&'static str, &'a LintCount)> {
+    let mut lint_counts: Vec<_> = lint_counts
+        .iter()
+        .map(|(lint_name, count)| (*lint_name, count))
+        .collect();
+
+    lint_counts.sort_by(|(a_name, a_count), (b_name, b_count)| {
+        b_count
+            .total()
+            .cmp(&a_count.total())
+            .then_with(|| a_name.cmp(b_name))
+    });
+
+    lint_counts
+}
+
+/// Lints sorted from slowest to fastest.
+pub fn sorted_timings(timings: &HashMap<&'static str, Duration>) -> Vec<(&'static str, Duration)> {
+    let mut timings: Vec<_> = timings
+        .iter()
+        .map(|(lint_name, duration)| (*lint_name, *duration))
+        .collect();
+
+    timings.sort_by(|(a_name, a_duration), (b_name, b_duration)| {
+        b_duration.cmp(a_duration).then_with(|| a_name.cmp(b_name))
+    });
+
+    timings
+}
+
+fn write_header(stdout: &mut StandardStream, header: &str) -> io::Result<()> {
+    stdout.set_color(ColorSpec::new().set_bold(true))?;
+    write!(stdout, "{header}")?;
+    stdout.reset()?;
+    writeln!(stdout)
+}
+
+pub fn log_statistics_text(
+    mut stdout: StandardStream,
+    lint_counts: &HashMap<&'static str, LintCount>,
+) -> io::Result<()> {
+    let lint_counts = sorted_lint_counts(lint_counts);
+    let name_width = lint_counts
+        .iter()
+        .map(|(lint_name, _)| lint_name.len())
+        .chain(std::iter::once("lint".len()))
+        .max()
+        .unwrap();
+
+    writeln!(stdout, "Statistics:")?;
+    write_header(
+        &mut stdout,
+        &format!(
+            "{:name_width$}  {:>6}  {:8}  {:>6}  top files",
+            "lint", "count", "severity", "files"
+        ),
+    )?;
+
+    for (lint_name, count) in lint_counts {
+        writeln!(
+            stdout,
+            "{lint_name:name_width$}  {:>6}  {:8}  {:>6}  {}",
+            count.total(),
+            count.severity(),
+            count.files.len(),
+            count
+                .top_files()
+                .into_iter()
+                .map(|(filename, count)| format!("{filename} ({count})"))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn log_timings_text(
+    mut stdout: StandardStream,
+    timings: &HashMap<&'static str, Duration>,
+) -> io::Result<()> {
+    let timings = sorted_timings(timings);
+    let name_width = timings
+        .iter()
+        .map(|(lint_name, _)| lint_name.len())
+        .chain(std::iter::once("lint".len()))
+        .max()
+        .unwrap();
+
+    writeln!(stdout, "Timings:")?;
+    write_header(
+        &mut stdout,
+        &format!("{:name_width$}  {:>12}", "lint", "time"),
+    )?;
+
+    for (lint_name, duration) in timings {
+        writeln!(
+            stdout,
+            "{lint_name:name_width$}  {:>10.3}ms",
+            duration.as_secs_f64() * 1000.0
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_files() {
+        let mut count = LintCount::default();
+        count.files.insert("a.lua".to_owned(), 1);
+        count.files.insert("b.lua".to_owned(), 5);
+        count.files.insert("c.lua".to_owned(), 3);
+        count.files.insert("d.lua".to_owned(), 3);
+
+        assert_eq!(
+            count.top_files(),
+            vec![("b.lua", 5), ("c.lua", 3), ("d.lua", 3)]
+        );
+    }
+}
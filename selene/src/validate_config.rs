@@ -120,9 +120,9 @@ pub fn validate_config(
         ErrorRange { start, end }
     });
 
-    let Err(error) =
-        crate::standard_library::collect_standard_library(&config, config.std(), directory, &None)
-    else {
+    let Some(error) = config.std_targets().into_iter().find_map(|target| {
+        crate::standard_library::collect_standard_library(&config, target, directory, &None).err()
+    }) else {
         return Ok(());
     };
 
@@ -133,6 +133,28 @@ pub fn validate_config(
             range: std_range,
         })),
 
+        #[cfg(feature = "roblox")]
+        StandardLibraryError::LuauDefinitions {
+            ref source,
+            ref path,
+        } => Err(Box::new(InvalidConfigError {
+            source: path.clone(),
+            range: Some(ErrorRange {
+                start: source.position.bytes(),
+                end: source.position.bytes(),
+            }),
+            error,
+        })),
+
+        #[cfg(not(feature = "roblox"))]
+        StandardLibraryError::LuauDefinitionsUnsupported { .. } => {
+            Err(Box::new(InvalidConfigError {
+                source: config_path_absolute,
+                range: std_range,
+                error,
+            }))
+        }
+
         StandardLibraryError::NotFound { .. } => Err(Box::new(InvalidConfigError {
             source: config_path_absolute,
             range: std_range,
@@ -204,8 +226,17 @@ mod tests {
                 .unwrap();
             let rich_output = String::from_utf8(rich_output_buffer.into_inner()).unwrap();
 
+            // Luau definitions files can't be loaded without the roblox feature, so those tests
+            // expect a different error
+            let no_roblox_output_path = validate_config_test
+                .path()
+                .join("rich_output_no_roblox.txt");
             let expected_rich_output =
-                std::fs::read_to_string(validate_config_test.path().join("rich_output.txt"));
+                if cfg!(not(feature = "roblox")) && no_roblox_output_path.exists() {
+                    std::fs::read_to_string(no_roblox_output_path)
+                } else {
+                    std::fs::read_to_string(validate_config_test.path().join("rich_output.txt"))
+                };
 
             if let Ok(expected_rich_output) = expected_rich_output {
                 if rich_output != expected_rich_output {
@@ -0,0 +1,33 @@
+use std::{path::Path, process::Command};
+
+fn run_selene(directory: &str, args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_selene"))
+        .args(args)
+        .current_dir(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/cli")
+                .join(directory),
+        )
+        .output()
+        .expect("couldn't run selene");
+
+    (
+        output.status.success(),
+        String::from_utf8(output.stderr).expect("stderr wasn't utf-8"),
+    )
+}
+
+#[test]
+fn malformed_luau_definitions() {
+    let (success, stderr) = run_selene("malformed_luau_definitions", &["main.lua"]);
+
+    assert!(!success);
+    assert!(!stderr.contains("could not be found"), "{stderr}");
+    assert!(stderr.contains("globals.d.luau"), "{stderr}");
+
+    if cfg!(feature = "roblox") {
+        assert!(stderr.contains("at line 3, column 10"), "{stderr}");
+    } else {
+        assert!(stderr.contains("without the roblox feature"), "{stderr}");
+    }
+}
@@ -0,0 +1,4 @@
+declare function tick(): number
+declare class Instance
+    Name string
+end